@@ -0,0 +1,183 @@
+use std::io::Write;
+use std::ops::Range;
+use std::path::PathBuf;
+
+use alloy_primitives::{Address, TxKind, B256};
+use clap::{Args, ValueEnum};
+use eth_trie_proofs::proof::InclusionProof;
+use eth_trie_proofs::tx_receipt_trie::TxReceiptsMptHandler;
+use eth_trie_proofs::tx_trie::TxsMptHandler;
+use serde::Serialize;
+
+#[derive(Debug, Args)]
+pub(crate) struct BlockArgs {
+    /// Target block number
+    block_number: u64,
+    /// Ethereum node RPC URL
+    rpc_url: Option<String>,
+    /// Kind of proofs to generate
+    #[arg(long, value_enum, default_value_t = ProofKind::Tx)]
+    kind: ProofKind,
+    /// Transaction index range, e.g. `10..20`, `10..` or `..20` (end exclusive)
+    #[arg(long, value_parser = parse_range)]
+    range: Option<Range<u64>>,
+    /// Only generate proofs for transactions sent to this address
+    #[arg(long)]
+    filter_to: Option<Address>,
+    /// Write one JSON file per proof into this directory instead of NDJSON to stdout
+    #[arg(long)]
+    out_dir: Option<PathBuf>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub(crate) enum ProofKind {
+    Tx,
+    Receipt,
+    Both,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase", bound = "")]
+struct BlockProof<K> {
+    kind: &'static str,
+    tx_hash: B256,
+    #[serde(flatten)]
+    proof: InclusionProof<K>,
+}
+
+impl<K> BlockProof<K> {
+    /// Writes the proof to its own file in `out_dir`, or as a NDJSON line to `stdout`.
+    fn write(
+        &self,
+        out_dir: Option<&PathBuf>,
+        stdout: &mut impl Write,
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        let json = serde_json::to_string(self)?;
+        match out_dir {
+            Some(out_dir) => {
+                let path = out_dir.join(format!("{}-{}.json", self.kind, self.proof.index));
+                std::fs::write(path, json)?;
+            }
+            None => writeln!(stdout, "{json}")?,
+        }
+        Ok(())
+    }
+}
+
+pub(crate) async fn run(args: BlockArgs) -> Result<(), Box<dyn std::error::Error>> {
+    let rpc_url = url::Url::parse(
+        args.rpc_url
+            .as_deref()
+            .unwrap_or("https://ethereum-rpc.publicnode.com"),
+    )?;
+
+    // The transaction trie is always built, as it provides the hashes and
+    // recipients of the transactions.
+    let mut txs_mpt_handler = TxsMptHandler::new(rpc_url.clone())?;
+    txs_mpt_handler
+        .build_tx_tree_from_block(args.block_number)
+        .await?;
+    let txs = txs_mpt_handler.get_elements()?;
+
+    let mut tx_receipts_mpt_handler = if args.kind != ProofKind::Tx {
+        let mut handler = TxReceiptsMptHandler::new(rpc_url)?;
+        handler
+            .build_tx_receipts_tree_from_block(args.block_number)
+            .await?;
+        Some(handler)
+    } else {
+        None
+    };
+
+    let range = args.range.unwrap_or(0..u64::MAX);
+    let end = range.end.min(txs.len() as u64);
+    if let Some(out_dir) = &args.out_dir {
+        std::fs::create_dir_all(out_dir)?;
+    }
+    let mut stdout = std::io::stdout().lock();
+
+    for index in range.start..end {
+        let tx = &txs[index as usize];
+        if let Some(filter_to) = args.filter_to {
+            if tx.to() != TxKind::Call(filter_to) {
+                continue;
+            }
+        }
+        let tx_hash = tx.0.tx_hash();
+
+        if args.kind != ProofKind::Receipt {
+            let block_proof = BlockProof {
+                kind: "tx",
+                tx_hash,
+                proof: txs_mpt_handler.get_inclusion_proof(index)?,
+            };
+            block_proof.write(args.out_dir.as_ref(), &mut stdout)?;
+        }
+        if let Some(handler) = tx_receipts_mpt_handler.as_mut() {
+            let block_proof = BlockProof {
+                kind: "receipt",
+                tx_hash,
+                proof: handler.get_inclusion_proof(index)?,
+            };
+            block_proof.write(args.out_dir.as_ref(), &mut stdout)?;
+        }
+    }
+
+    Ok(())
+}
+
+fn parse_range(value: &str) -> Result<Range<u64>, String> {
+    let parse = |bound: &str, default: u64| -> Result<u64, String> {
+        if bound.is_empty() {
+            Ok(default)
+        } else {
+            bound
+                .parse()
+                .map_err(|_| format!("invalid range bound `{bound}`"))
+        }
+    };
+
+    if value.is_empty() {
+        return Err("empty range".to_string());
+    }
+    let range = match value.split_once("..") {
+        Some((start, end)) => parse(start, 0)?..parse(end, u64::MAX)?,
+        None => {
+            let index = parse(value, 0)?;
+            index..index.saturating_add(1)
+        }
+    };
+    if range.is_empty() {
+        return Err(format!("range `{value}` selects no transaction"));
+    }
+    Ok(range)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_range() {
+        assert_eq!(parse_range("10..20"), Ok(10..20));
+        assert_eq!(parse_range("10..11"), Ok(10..11));
+        assert_eq!(parse_range("7"), Ok(7..8));
+    }
+
+    #[test]
+    fn test_parse_range_open_ended() {
+        assert_eq!(parse_range("10.."), Ok(10..u64::MAX));
+        assert_eq!(parse_range("..20"), Ok(0..20));
+        assert_eq!(parse_range(".."), Ok(0..u64::MAX));
+    }
+
+    #[test]
+    fn test_parse_range_invalid() {
+        assert!(parse_range("").is_err());
+        assert!(parse_range("10..10").is_err());
+        assert!(parse_range("20..10").is_err());
+        assert!(parse_range("a..10").is_err());
+        assert!(parse_range("10..b").is_err());
+        assert!(parse_range("-1").is_err());
+    }
+}
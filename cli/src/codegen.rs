@@ -0,0 +1,37 @@
+use std::path::PathBuf;
+
+use clap::Subcommand;
+use eth_trie_proofs::codegen::render_receipt_prover;
+
+#[derive(Debug, Subcommand)]
+pub(crate) enum CodegenCommands {
+    #[command(about = "Generate a contract proving receipts and extracting their logs")]
+    ReceiptProver {
+        /// Output file, printed to stdout if omitted
+        #[arg(long)]
+        out: Option<PathBuf>,
+        /// Name of the generated contract
+        #[arg(long, default_value = "ReceiptProver")]
+        contract_name: String,
+        /// Import directory of the `MerkleTrie`, `RLPReader` and `BytesUtils` libraries
+        #[arg(long, default_value = "./lib")]
+        lib_path: String,
+    },
+}
+
+pub(crate) fn run(command: CodegenCommands) -> Result<(), Box<dyn std::error::Error>> {
+    match command {
+        CodegenCommands::ReceiptProver {
+            out,
+            contract_name,
+            lib_path,
+        } => {
+            let contract = render_receipt_prover(&contract_name, &lib_path);
+            match out {
+                Some(out) => std::fs::write(out, contract)?,
+                None => print!("{}", contract),
+            }
+        }
+    }
+    Ok(())
+}
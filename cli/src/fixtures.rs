@@ -0,0 +1,174 @@
+use std::collections::BTreeMap;
+use std::path::PathBuf;
+
+use alloy_primitives::hex::FromHex;
+use alloy_primitives::B256;
+use clap::{Args, ValueEnum};
+use eth_trie_proofs::fixture::{
+    render_foundry_test, render_json_fixtures, render_receipt_prover_test, ProofFixture,
+};
+use eth_trie_proofs::tx_receipt_trie::TxReceiptsMptHandler;
+use eth_trie_proofs::tx_trie::TxsMptHandler;
+use url::Url;
+
+#[derive(Debug, Args)]
+pub(crate) struct FixturesArgs {
+    /// Transaction hash to generate a transaction proof fixture for, repeatable
+    #[arg(long = "tx")]
+    txs: Vec<String>,
+    /// Transaction hash to generate a receipt proof fixture for, repeatable
+    #[arg(long = "receipt")]
+    receipts: Vec<String>,
+    /// `<TRANSACTION_HASH>:<LOG_INDEX>` to generate a log proof fixture for, repeatable
+    #[arg(long = "log")]
+    logs: Vec<String>,
+    /// Output file
+    #[arg(long)]
+    out: PathBuf,
+    /// Fixture format
+    #[arg(long, value_enum, default_value_t = FixtureFormat::Solidity)]
+    format: FixtureFormat,
+    /// Name of the generated Foundry test contract
+    #[arg(long, default_value = "ProofFixturesTest")]
+    contract_name: String,
+    /// Import path of the `MerkleTrie` library from the generated test
+    #[arg(long, default_value = "../src/lib/MerkleTrie.sol")]
+    merkle_trie_path: String,
+    /// Name of the prover contract tested by the `receipt-prover` format
+    #[arg(long, default_value = "ReceiptProver")]
+    prover_name: String,
+    /// Import path of the prover contract from the generated test
+    #[arg(long, default_value = "../src/ReceiptProver.sol")]
+    prover_path: String,
+    /// Ethereum node RPC URL
+    #[arg(long)]
+    rpc_url: Option<String>,
+}
+
+#[derive(Debug, Clone, Copy, ValueEnum)]
+pub(crate) enum FixtureFormat {
+    /// Foundry test contract checking every fixture with `MerkleTrie.get`
+    Solidity,
+    /// JSON array of fixtures
+    Json,
+    /// Foundry test checking every log fixture with a generated `ReceiptProver`
+    ReceiptProver,
+}
+
+/// Element to generate a fixture for.
+enum Target {
+    Tx,
+    Receipt,
+    Log(u64),
+}
+
+pub(crate) async fn run(args: FixturesArgs) -> Result<(), Box<dyn std::error::Error>> {
+    let rpc_url = Url::parse(
+        args.rpc_url
+            .as_deref()
+            .unwrap_or("https://ethereum-rpc.publicnode.com"),
+    )?;
+
+    let txs = args
+        .txs
+        .iter()
+        .map(|tx_hash| Ok((B256::from_hex(tx_hash)?, Target::Tx)));
+    let receipts = args
+        .receipts
+        .iter()
+        .map(|tx_hash| Ok((B256::from_hex(tx_hash)?, Target::Receipt)));
+    let logs = args
+        .logs
+        .iter()
+        .map(|log| parse_log(log).map(|(tx_hash, log_index)| (tx_hash, Target::Log(log_index))));
+    let targets = txs
+        .chain(receipts)
+        .chain(logs)
+        .collect::<Result<Vec<_>, Box<dyn std::error::Error>>>()?;
+
+    // Targets are grouped by block, so that each trie is built once.
+    let lookup = TxsMptHandler::new(rpc_url.clone())?;
+    let mut blocks: BTreeMap<u64, Vec<(usize, B256, Target)>> = BTreeMap::new();
+    for (position, (tx_hash, target)) in targets.into_iter().enumerate() {
+        let (block_number, _) = lookup.get_tx_block(tx_hash).await?;
+        blocks
+            .entry(block_number)
+            .or_default()
+            .push((position, tx_hash, target));
+    }
+
+    let mut fixtures = vec![];
+    for (block_number, targets) in blocks {
+        // The transaction trie is always built, as it provides the indices of the
+        // transactions.
+        let mut txs_mpt_handler = TxsMptHandler::new(rpc_url.clone())?;
+        txs_mpt_handler
+            .build_tx_tree_from_block(block_number)
+            .await?;
+        let mut tx_receipts_mpt_handler = None;
+        if targets
+            .iter()
+            .any(|(_, _, target)| !matches!(target, Target::Tx))
+        {
+            let mut handler = TxReceiptsMptHandler::new(rpc_url.clone())?;
+            handler
+                .build_tx_receipts_tree_from_block(block_number)
+                .await?;
+            tx_receipts_mpt_handler = Some(handler);
+        }
+
+        for (position, tx_hash, target) in targets {
+            let index = txs_mpt_handler.tx_hash_to_tx_index(tx_hash)?;
+            let fixture = match (target, tx_receipts_mpt_handler.as_mut()) {
+                (Target::Tx, _) => {
+                    let name = format!("tx_{block_number}_{index}");
+                    ProofFixture::tx(name, txs_mpt_handler.get_inclusion_proof(index)?)
+                }
+                (Target::Receipt, Some(handler)) => {
+                    let name = format!("receipt_{block_number}_{index}");
+                    ProofFixture::receipt(name, handler.get_inclusion_proof(index)?)
+                }
+                (Target::Log(log_index), Some(handler)) => {
+                    let name = format!("log_{block_number}_{index}_{log_index}");
+                    ProofFixture::log(name, handler.get_inclusion_proof(index)?, log_index)?
+                }
+                (_, None) => unreachable!("built for receipt and log targets"),
+            };
+            fixtures.push((position, fixture));
+        }
+    }
+    // Fixtures are written in the order of the arguments.
+    fixtures.sort_by_key(|(position, _)| *position);
+    let fixtures: Vec<ProofFixture> = fixtures.into_iter().map(|(_, fixture)| fixture).collect();
+
+    for fixture in &fixtures {
+        fixture.verify()?;
+    }
+
+    let rendered = match args.format {
+        FixtureFormat::Solidity => {
+            render_foundry_test(&args.contract_name, &args.merkle_trie_path, &fixtures)
+        }
+        FixtureFormat::Json => render_json_fixtures(&fixtures),
+        FixtureFormat::ReceiptProver => render_receipt_prover_test(
+            &args.contract_name,
+            &args.prover_name,
+            &args.prover_path,
+            &fixtures,
+        ),
+    };
+    std::fs::write(&args.out, rendered)?;
+    eprintln!(
+        "wrote {} fixtures to {}",
+        fixtures.len(),
+        args.out.display()
+    );
+    Ok(())
+}
+
+fn parse_log(log: &str) -> Result<(B256, u64), Box<dyn std::error::Error>> {
+    let (tx_hash, log_index) = log
+        .split_once(':')
+        .ok_or_else(|| format!("invalid log `{log}`, expected <TRANSACTION_HASH>:<LOG_INDEX>"))?;
+    Ok((B256::from_hex(tx_hash)?, log_index.parse()?))
+}
@@ -0,0 +1,112 @@
+use alloy_primitives::{hex, Bytes};
+use alloy_sol_types::SolValue;
+use clap::ValueEnum;
+use eth_trie_proofs::proof::InclusionProof;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub(crate) enum OutputFormat {
+    /// JSON inclusion proof, with the block, root, index, key, proof nodes and value
+    Json,
+    /// ABI-encoded `(bytes key, bytes proof, bytes32 root)`
+    Abi,
+    /// RLP list of the proof nodes, as `MerkleTrie.get` expects
+    Rlp,
+}
+
+/// Proof nodes wrapped in a single RLP list, each node encoded as a byte string.
+pub(crate) fn rlp_proof<K>(proof: &InclusionProof<K>) -> Vec<u8> {
+    let mut out = vec![];
+    alloy_rlp::encode_list::<_, [u8]>(&proof.nodes, &mut out);
+    out
+}
+
+/// ABI-encoded `(bytes key, bytes proof, bytes32 root)`, ready for
+/// `abi.decode(data, (bytes, bytes, bytes32))`.
+pub(crate) fn abi_encode<K>(proof: &InclusionProof<K>) -> Vec<u8> {
+    (proof.key.clone(), Bytes::from(rlp_proof(proof)), proof.root).abi_encode_params()
+}
+
+/// Formats a proof for printing.
+///
+/// JSON output always holds the RLP-encoded key. With `print_key`, the other formats
+/// are preceded by a line holding it.
+pub(crate) fn format_proof<K>(
+    proof: &InclusionProof<K>,
+    format: OutputFormat,
+    print_key: bool,
+) -> String {
+    let key = print_key.then(|| hex::encode_prefixed(&proof.key));
+    match format {
+        OutputFormat::Json => serde_json::to_string(proof).unwrap(),
+        OutputFormat::Abi | OutputFormat::Rlp => {
+            let payload = match format {
+                OutputFormat::Abi => abi_encode(proof),
+                _ => rlp_proof(proof),
+            };
+            let payload = hex::encode_prefixed(payload);
+            match key {
+                Some(key) => format!("{key}\n{payload}"),
+                None => payload,
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_primitives::B256;
+    use eth_trie_proofs::proof::TxInclusionProof;
+
+    fn proof(index: u64) -> TxInclusionProof {
+        TxInclusionProof::new(
+            1,
+            B256::repeat_byte(0xbb),
+            B256::repeat_byte(0xaa),
+            index,
+            vec![vec![0xc0; 40], vec![0x01]],
+            vec![0x02],
+        )
+    }
+
+    #[test]
+    fn test_rlp_key() {
+        assert_eq!(proof(0).key.to_vec(), vec![0x80]);
+        assert_eq!(proof(1).key.to_vec(), vec![0x01]);
+        assert_eq!(proof(127).key.to_vec(), vec![0x7f]);
+        assert_eq!(proof(128).key.to_vec(), vec![0x81, 0x80]);
+        assert_eq!(proof(256).key.to_vec(), vec![0x82, 0x01, 0x00]);
+    }
+
+    #[test]
+    fn test_rlp_proof() {
+        let mut expected = vec![0xea, 0xa8];
+        expected.extend([0xc0; 40]);
+        expected.push(0x01);
+        assert_eq!(rlp_proof(&proof(0)), expected);
+    }
+
+    #[test]
+    fn test_abi_encode() {
+        let proof = proof(128);
+        let encoded = abi_encode(&proof);
+        let (key, nodes, root) = <(Bytes, Bytes, B256)>::abi_decode_params(&encoded, true).unwrap();
+        assert_eq!(key, proof.key);
+        assert_eq!(nodes.to_vec(), rlp_proof(&proof));
+        assert_eq!(root, proof.root);
+    }
+
+    #[test]
+    fn test_format_proof_key() {
+        let proof = proof(1);
+        let json = format_proof(&proof, OutputFormat::Json, false);
+        let decoded: TxInclusionProof = serde_json::from_str(&json).unwrap();
+        assert_eq!(decoded, proof);
+        assert!(json.contains("\"key\":\"0x01\""));
+
+        let rlp = format_proof(&proof, OutputFormat::Rlp, true);
+        let (key, payload) = rlp.split_once('\n').unwrap();
+        assert_eq!(key, "0x01");
+        assert_eq!(payload, format_proof(&proof, OutputFormat::Rlp, false));
+    }
+}
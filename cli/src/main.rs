@@ -2,12 +2,20 @@
 use alloy_primitives::hex::FromHex;
 use alloy_primitives::B256;
 use clap::{Parser, Subcommand};
+use eth_trie_proofs::proof::{ReceiptInclusionProof, TxInclusionProof};
 use eth_trie_proofs::tx_trie::TxsMptHandler;
-use serde::Serialize;
-use serde_with::serde_as;
 
 use eth_trie_proofs::tx_receipt_trie::TxReceiptsMptHandler;
 use eth_trie_proofs::EthTrieError;
+use format::{format_proof, OutputFormat};
+
+mod block;
+mod codegen;
+mod fixtures;
+mod format;
+mod serve;
+mod starknet;
+mod verify;
 
 #[derive(Debug, Parser)]
 #[command(name = "eth-trie-proof")]
@@ -25,6 +33,12 @@ enum Commands {
         tx_hash: String,
         /// Ethereum node RPC URL
         rpc_url: Option<String>,
+        /// Output format of the proof
+        #[arg(long, value_enum, default_value_t = OutputFormat::Json)]
+        format: OutputFormat,
+        /// Also print the RLP-encoded trie key of the proven element, always in the JSON output
+        #[arg(long)]
+        print_key: bool,
     },
     #[command(about = "Generate a MPT proof for a transaction receipt")]
     Receipt {
@@ -32,60 +46,106 @@ enum Commands {
         tx_hash: String,
         /// Ethereum node RPC URL
         rpc_url: Option<String>,
+        /// Output format of the proof
+        #[arg(long, value_enum, default_value_t = OutputFormat::Json)]
+        format: OutputFormat,
+        /// Also print the RLP-encoded trie key of the proven element, always in the JSON output
+        #[arg(long)]
+        print_key: bool,
+    },
+    #[command(about = "Generate MPT proofs for every transaction or receipt of a block")]
+    Block(block::BlockArgs),
+    #[command(about = "Generate Solidity contracts verifying proofs")]
+    Codegen {
+        #[command(subcommand)]
+        command: codegen::CodegenCommands,
+    },
+    #[command(about = "Generate Foundry test or JSON fixtures from real proofs")]
+    Fixtures(fixtures::FixturesArgs),
+    #[command(about = "Serve proofs over a local HTTP JSON-RPC server")]
+    Serve(serve::ServeArgs),
+    #[command(about = "Verify a MPT proof printed by the `tx` or `receipt` commands")]
+    Verify {
+        #[command(subcommand)]
+        command: verify::VerifyCommands,
+    },
+    #[command(about = "Generate Starknet transaction and receipt proofs")]
+    Starknet {
+        #[command(subcommand)]
+        command: starknet::StarknetCommands,
     },
-}
-
-#[serde_with::serde_as]
-#[derive(Debug, Serialize)]
-struct MptProof {
-    root: B256,
-    #[serde_as(as = "Vec<serde_with::hex::Hex>")]
-    proof: Vec<Vec<u8>>,
-    index: u64,
 }
 
 #[tokio::main]
-async fn main() -> Result<(), EthTrieError> {
+async fn main() -> Result<(), Box<dyn std::error::Error>> {
     let cli = Cli::parse();
     match cli.command {
-        Commands::Tx { tx_hash, rpc_url } => {
-            generate_tx_proof(
+        Commands::Tx {
+            tx_hash,
+            rpc_url,
+            format,
+            print_key,
+        } => {
+            let mpt_proof = generate_tx_proof(
                 &tx_hash,
                 rpc_url
                     .unwrap_or("https://ethereum-rpc.publicnode.com".parse().unwrap())
                     .as_str(),
             )
             .await?;
+            print!("{}", format_proof(&mpt_proof, format, print_key));
         }
-        Commands::Receipt { tx_hash, rpc_url } => {
-            generate_receipt_proof(
+        Commands::Receipt {
+            tx_hash,
+            rpc_url,
+            format,
+            print_key,
+        } => {
+            let mpt_proof = generate_receipt_proof(
                 &tx_hash,
                 rpc_url
                     .unwrap_or("https://ethereum-rpc.publicnode.com".parse().unwrap())
                     .as_str(),
             )
             .await?;
+            print!("{}", format_proof(&mpt_proof, format, print_key));
+        }
+        Commands::Block(args) => {
+            block::run(args).await?;
+        }
+        Commands::Codegen { command } => {
+            codegen::run(command)?;
+        }
+        Commands::Fixtures(args) => {
+            fixtures::run(args).await?;
+        }
+        Commands::Serve(args) => {
+            serve::run(args).await?;
+        }
+        Commands::Verify { command } => {
+            verify::run(command)?;
+        }
+        Commands::Starknet { command } => {
+            starknet::run(command).await?;
         }
     }
 
     Ok(())
 }
 
-async fn generate_tx_proof(tx_hash: &str, rpc_url: &str) -> Result<(), EthTrieError> {
+async fn generate_tx_proof(tx_hash: &str, rpc_url: &str) -> Result<TxInclusionProof, EthTrieError> {
     let rpc_url = url::Url::parse(rpc_url).expect("Invalid URL");
     let mut txs_mpt_handler = TxsMptHandler::new(rpc_url)?;
     let tx_hash = B256::from_hex(tx_hash).unwrap();
     txs_mpt_handler.build_tx_tree_from_tx_hash(tx_hash).await?;
     let index = txs_mpt_handler.tx_hash_to_tx_index(tx_hash)?;
-    let proof = txs_mpt_handler.get_proof(index)?;
-    let root = txs_mpt_handler.get_root()?;
-
-    let mpt_proof = MptProof { root, proof, index };
-    print!("{}", serde_json::to_string(&mpt_proof).unwrap());
-    Ok(())
+    txs_mpt_handler.get_inclusion_proof(index)
 }
 
-async fn generate_receipt_proof(tx_hash: &str, rpc_url: &str) -> Result<(), EthTrieError> {
+async fn generate_receipt_proof(
+    tx_hash: &str,
+    rpc_url: &str,
+) -> Result<ReceiptInclusionProof, EthTrieError> {
     let rpc_url = url::Url::parse(rpc_url).expect("Invalid URL");
     let mut tx_receipts_mpt_handler = TxReceiptsMptHandler::new(rpc_url)?;
     let tx_hash = B256::from_hex(tx_hash).unwrap();
@@ -93,10 +153,5 @@ async fn generate_receipt_proof(tx_hash: &str, rpc_url: &str) -> Result<(), EthT
         .build_tx_receipt_tree_from_tx_hash(tx_hash)
         .await?;
     let index = tx_receipts_mpt_handler.tx_hash_to_tx_index(tx_hash).await?;
-    let proof = tx_receipts_mpt_handler.get_proof(index)?;
-    let root = tx_receipts_mpt_handler.get_root()?;
-
-    let mpt_proof = MptProof { root, proof, index };
-    print!("{}", serde_json::to_string(&mpt_proof).unwrap());
-    Ok(())
+    tx_receipts_mpt_handler.get_inclusion_proof(index)
 }
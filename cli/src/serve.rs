@@ -0,0 +1,507 @@
+use std::collections::HashMap;
+use std::convert::Infallible;
+use std::net::SocketAddr;
+use std::num::NonZeroUsize;
+use std::sync::{Arc, Mutex};
+
+use alloy_primitives::hex::FromHex;
+use alloy_primitives::{Bytes, Log, B256};
+use clap::Args;
+use eth_trie_proofs::proof::{
+    InclusionProof, ReceiptInclusionProof, ReceiptTrie, TxInclusionProof, TxTrie,
+};
+use eth_trie_proofs::tx_receipt_trie::TxReceiptsMptHandler;
+use eth_trie_proofs::tx_trie::TxsMptHandler;
+use eth_trie_proofs::EthTrieError;
+use hyper::service::{make_service_fn, service_fn};
+use hyper::{header, Body, Method, Request, Response, StatusCode};
+use lru::LruCache;
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use url::Url;
+
+#[derive(Debug, Args)]
+pub(crate) struct ServeArgs {
+    /// Ethereum node RPC URL
+    rpc_url: Option<String>,
+    /// Address the JSON-RPC server listens on
+    #[arg(long, default_value = "127.0.0.1:3030")]
+    addr: SocketAddr,
+    /// Maximum number of blocks whose tries are kept in memory
+    #[arg(long, default_value = "64")]
+    cache_size: NonZeroUsize,
+}
+
+pub(crate) async fn run(args: ServeArgs) -> Result<(), Box<dyn std::error::Error>> {
+    let rpc_url = Url::parse(
+        args.rpc_url
+            .as_deref()
+            .unwrap_or("https://ethereum-rpc.publicnode.com"),
+    )?;
+    let server = Arc::new(ProofServer::new(rpc_url, args.cache_size));
+
+    let make_service = make_service_fn(move |_| {
+        let server = server.clone();
+        async move {
+            Ok::<_, Infallible>(service_fn(move |request| {
+                let server = server.clone();
+                async move { Ok::<_, Infallible>(server.handle_http(request).await) }
+            }))
+        }
+    });
+
+    let http_server = hyper::Server::try_bind(&args.addr)?.serve(make_service);
+    eprintln!("listening on http://{}", http_server.local_addr());
+    http_server.await?;
+    Ok(())
+}
+
+/// Number of times a block is fetched again when a reorg replaces it while its trie is built.
+const REORG_RETRIES: usize = 2;
+
+/// The tries of a block, built once and shared by every request for that block.
+struct CachedBlock {
+    block_number: u64,
+    block_hash: B256,
+    txs: TxsMptHandler,
+    /// Built on the first receipt or log request for the block.
+    receipts: Option<TxReceiptsMptHandler>,
+}
+
+type SharedBlock = Arc<Mutex<CachedBlock>>;
+
+/// LRU of built blocks keyed by block hash, with an index from the hashes of
+/// their transactions so cached blocks are found without an RPC round-trip.
+struct BlockCache {
+    blocks: LruCache<B256, SharedBlock>,
+    tx_locations: HashMap<B256, (B256, u64)>,
+}
+
+impl BlockCache {
+    fn insert(&mut self, block_hash: B256, tx_hashes: Vec<B256>, block: SharedBlock) {
+        for (index, tx_hash) in tx_hashes.into_iter().enumerate() {
+            self.tx_locations
+                .insert(tx_hash, (block_hash, index as u64));
+        }
+        if let Some((evicted_hash, _)) = self.blocks.push(block_hash, block) {
+            if evicted_hash != block_hash {
+                self.drop_tx_locations(evicted_hash);
+            }
+        }
+    }
+
+    /// Drops a block that is no longer canonical.
+    fn remove(&mut self, block_hash: B256) {
+        self.blocks.pop(&block_hash);
+        self.drop_tx_locations(block_hash);
+    }
+
+    fn drop_tx_locations(&mut self, block_hash: B256) {
+        self.tx_locations
+            .retain(|_, (location_hash, _)| *location_hash != block_hash);
+    }
+}
+
+struct ProofServer {
+    rpc_url: Url,
+    cache: Mutex<BlockCache>,
+}
+
+#[derive(Debug, Deserialize)]
+struct JsonRpcRequest {
+    #[serde(default)]
+    id: Value,
+    method: String,
+    #[serde(default)]
+    params: Value,
+}
+
+#[derive(Debug)]
+struct JsonRpcError {
+    code: i64,
+    message: String,
+}
+
+impl JsonRpcError {
+    const PARSE_ERROR: i64 = -32700;
+    const METHOD_NOT_FOUND: i64 = -32601;
+    const INVALID_PARAMS: i64 = -32602;
+    const SERVER_ERROR: i64 = -32000;
+
+    fn invalid_params(message: impl Into<String>) -> Self {
+        Self {
+            code: Self::INVALID_PARAMS,
+            message: message.into(),
+        }
+    }
+}
+
+impl From<EthTrieError> for JsonRpcError {
+    fn from(err: EthTrieError) -> Self {
+        Self {
+            code: Self::SERVER_ERROR,
+            message: err.to_string(),
+        }
+    }
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase", bound = "")]
+struct ProofResponse<K> {
+    tx_hash: B256,
+    #[serde(flatten)]
+    proof: InclusionProof<K>,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct LogProofResponse {
+    #[serde(flatten)]
+    receipt_proof: ProofResponse<ReceiptTrie>,
+    log_index: u64,
+    log: Log,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(tag = "kind", content = "proof", rename_all = "lowercase")]
+enum VerifyParams {
+    Tx(TxInclusionProof),
+    Receipt(ReceiptInclusionProof),
+}
+
+impl ProofServer {
+    fn new(rpc_url: Url, cache_size: NonZeroUsize) -> Self {
+        Self {
+            rpc_url,
+            cache: Mutex::new(BlockCache {
+                blocks: LruCache::new(cache_size),
+                tx_locations: HashMap::new(),
+            }),
+        }
+    }
+
+    async fn handle_http(&self, request: Request<Body>) -> Response<Body> {
+        if request.method() != Method::POST {
+            return Response::builder()
+                .status(StatusCode::METHOD_NOT_ALLOWED)
+                .body(Body::empty())
+                .unwrap();
+        }
+
+        let response = match hyper::body::to_bytes(request.into_body()).await {
+            Ok(body) => match serde_json::from_slice::<JsonRpcRequest>(&body) {
+                Ok(request) => {
+                    let result = self.handle_request(&request.method, request.params).await;
+                    json_rpc_response(request.id, result)
+                }
+                Err(err) => json_rpc_response(
+                    Value::Null,
+                    Err(JsonRpcError {
+                        code: JsonRpcError::PARSE_ERROR,
+                        message: err.to_string(),
+                    }),
+                ),
+            },
+            Err(err) => json_rpc_response(
+                Value::Null,
+                Err(JsonRpcError {
+                    code: JsonRpcError::PARSE_ERROR,
+                    message: err.to_string(),
+                }),
+            ),
+        };
+
+        Response::builder()
+            .header(header::CONTENT_TYPE, "application/json")
+            .body(Body::from(response.to_string()))
+            .unwrap()
+    }
+
+    async fn handle_request(&self, method: &str, params: Value) -> Result<Value, JsonRpcError> {
+        let result = match method {
+            "getTxProof" => {
+                let tx_hash = tx_hash_param(&params)?;
+                serde_json::to_value(self.get_tx_proof(tx_hash).await?)
+            }
+            "getReceiptProof" => {
+                let tx_hash = tx_hash_param(&params)?;
+                serde_json::to_value(self.get_receipt_proof(tx_hash).await?)
+            }
+            "getLogProof" => {
+                let tx_hash = tx_hash_param(&params)?;
+                let log_index = param(&params, "logIndex", 1)
+                    .and_then(Value::as_u64)
+                    .ok_or_else(|| JsonRpcError::invalid_params("missing `logIndex`"))?;
+                serde_json::to_value(self.get_log_proof(tx_hash, log_index).await?)
+            }
+            "verify" => {
+                let params: VerifyParams = serde_json::from_value(params)
+                    .map_err(|err| JsonRpcError::invalid_params(err.to_string()))?;
+                let value = verify(params)?;
+                Ok(json!({ "value": value }))
+            }
+            _ => {
+                return Err(JsonRpcError {
+                    code: JsonRpcError::METHOD_NOT_FOUND,
+                    message: format!("method `{method}` not found"),
+                })
+            }
+        };
+        Ok(result.expect("responses serialize to JSON"))
+    }
+
+    async fn get_tx_proof(&self, tx_hash: B256) -> Result<ProofResponse<TxTrie>, EthTrieError> {
+        let (_, block, index) = self.get_block(tx_hash).await?;
+        let proof = block.lock().unwrap().txs.get_inclusion_proof(index)?;
+        Ok(ProofResponse { tx_hash, proof })
+    }
+
+    async fn get_receipt_proof(
+        &self,
+        tx_hash: B256,
+    ) -> Result<ProofResponse<ReceiptTrie>, EthTrieError> {
+        let (proof, _) = self.get_receipt_proof_with_block(tx_hash).await?;
+        Ok(proof)
+    }
+
+    /// Generates a receipt proof, and returns the cached block it was generated from.
+    async fn get_receipt_proof_with_block(
+        &self,
+        tx_hash: B256,
+    ) -> Result<(ProofResponse<ReceiptTrie>, SharedBlock), EthTrieError> {
+        let (_, shared_block, index) = self.get_block(tx_hash).await?;
+        self.build_receipts(&shared_block).await?;
+
+        let proof = shared_block
+            .lock()
+            .unwrap()
+            .receipts
+            .as_mut()
+            .ok_or(EthTrieError::TrieNotFound)?
+            .get_inclusion_proof(index)?;
+        Ok((ProofResponse { tx_hash, proof }, shared_block))
+    }
+
+    async fn get_log_proof(
+        &self,
+        tx_hash: B256,
+        log_index: u64,
+    ) -> Result<LogProofResponse, EthTrieError> {
+        let (receipt_proof, block) = self.get_receipt_proof_with_block(tx_hash).await?;
+
+        let block = block.lock().unwrap();
+        let receipts = block.receipts.as_ref().ok_or(EthTrieError::TrieNotFound)?;
+        let receipt = receipts.get_tx_receipt(receipt_proof.proof.index)?;
+        let log = receipt
+            .logs()
+            .get(log_index as usize)
+            .cloned()
+            .ok_or(EthTrieError::LogNotFound)?;
+        Ok(LogProofResponse {
+            receipt_proof,
+            log_index,
+            log,
+        })
+    }
+
+    /// Returns the cached block including `tx_hash`, building its transaction
+    /// trie on a cache miss, together with the block hash and transaction index.
+    ///
+    /// The trie is built by block number: if a reorg replaces the block in the meantime,
+    /// the block of the transaction is looked up again, up to [`REORG_RETRIES`] times.
+    async fn get_block(&self, tx_hash: B256) -> Result<(B256, SharedBlock, u64), EthTrieError> {
+        {
+            let mut cache = self.cache.lock().unwrap();
+            if let Some(&(block_hash, index)) = cache.tx_locations.get(&tx_hash) {
+                if let Some(block) = cache.blocks.get(&block_hash) {
+                    return Ok((block_hash, block.clone(), index));
+                }
+            }
+        }
+
+        let mut txs = TxsMptHandler::new(self.rpc_url.clone())?;
+        let mut attempt = 0;
+        let (block_number, block_hash) = loop {
+            let (block_number, block_hash) = txs.get_tx_block(tx_hash).await?;
+            if let Some(block) = self.cache.lock().unwrap().blocks.get(&block_hash) {
+                let index = block.lock().unwrap().txs.tx_hash_to_tx_index(tx_hash)?;
+                return Ok((block_hash, block.clone(), index));
+            }
+
+            txs.build_tx_tree_from_block(block_number).await?;
+            if txs.get_block()?.1 == block_hash {
+                break (block_number, block_hash);
+            }
+            if attempt == REORG_RETRIES {
+                return Err(EthTrieError::BlockReorged(block_hash));
+            }
+            attempt += 1;
+        };
+
+        let index = txs.tx_hash_to_tx_index(tx_hash)?;
+        let tx_hashes = txs.elements()?.iter().map(|tx| tx.0.tx_hash()).collect();
+        let block = Arc::new(Mutex::new(CachedBlock {
+            block_number,
+            block_hash,
+            txs,
+            receipts: None,
+        }));
+        self.cache
+            .lock()
+            .unwrap()
+            .insert(block_hash, tx_hashes, block.clone());
+        Ok((block_hash, block, index))
+    }
+
+    /// Builds the receipt trie of a cached block if it is not built yet.
+    ///
+    /// If a reorg replaced the block since its transaction trie was built, the block is
+    /// dropped from the cache, so the next request builds the canonical one.
+    async fn build_receipts(&self, block: &SharedBlock) -> Result<(), EthTrieError> {
+        let (block_number, block_hash) = {
+            let block = block.lock().unwrap();
+            if block.receipts.is_some() {
+                return Ok(());
+            }
+            (block.block_number, block.block_hash)
+        };
+
+        let mut receipts = TxReceiptsMptHandler::new(self.rpc_url.clone())?;
+        receipts
+            .build_tx_receipts_tree_from_block(block_number)
+            .await?;
+        if receipts.get_block()?.1 != block_hash {
+            self.cache.lock().unwrap().remove(block_hash);
+            return Err(EthTrieError::BlockReorged(block_hash));
+        }
+        block.lock().unwrap().receipts.get_or_insert(receipts);
+        Ok(())
+    }
+}
+
+fn verify(params: VerifyParams) -> Result<Bytes, JsonRpcError> {
+    let value = match params {
+        VerifyParams::Tx(proof) => {
+            proof.verify()?;
+            proof.value
+        }
+        VerifyParams::Receipt(proof) => {
+            proof.verify()?;
+            proof.value
+        }
+    };
+    Ok(value)
+}
+
+/// Looks up a parameter by name in an object, or by position in an array.
+fn param<'a>(params: &'a Value, name: &str, position: usize) -> Option<&'a Value> {
+    match params {
+        Value::Object(params) => params.get(name),
+        Value::Array(params) => params.get(position),
+        _ => None,
+    }
+}
+
+fn tx_hash_param(params: &Value) -> Result<B256, JsonRpcError> {
+    param(params, "txHash", 0)
+        .and_then(Value::as_str)
+        .and_then(|tx_hash| B256::from_hex(tx_hash).ok())
+        .ok_or_else(|| JsonRpcError::invalid_params("missing or invalid `txHash`"))
+}
+
+fn json_rpc_response(id: Value, result: Result<Value, JsonRpcError>) -> Value {
+    match result {
+        Ok(result) => json!({ "jsonrpc": "2.0", "id": id, "result": result }),
+        Err(err) => json!({
+            "jsonrpc": "2.0",
+            "id": id,
+            "error": { "code": err.code, "message": err.message },
+        }),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn server() -> ProofServer {
+        ProofServer::new(
+            Url::parse("http://127.0.0.1:1").unwrap(),
+            NonZeroUsize::new(2).unwrap(),
+        )
+    }
+
+    #[tokio::test]
+    async fn test_request_errors() {
+        let server = server();
+
+        let err = server
+            .handle_request("getBlockProof", Value::Null)
+            .await
+            .unwrap_err();
+        assert_eq!(err.code, JsonRpcError::METHOD_NOT_FOUND);
+
+        let err = server
+            .handle_request("getTxProof", json!({ "txHash": "0x01" }))
+            .await
+            .unwrap_err();
+        assert_eq!(err.code, JsonRpcError::INVALID_PARAMS);
+
+        let err = server
+            .handle_request("getLogProof", json!([B256::ZERO]))
+            .await
+            .unwrap_err();
+        assert_eq!(err.code, JsonRpcError::INVALID_PARAMS);
+
+        let err = server
+            .handle_request(
+                "verify",
+                json!({
+                    "kind": "tx",
+                    "proof": { "root": B256::ZERO, "proof": [], "index": 0 },
+                }),
+            )
+            .await
+            .unwrap_err();
+        assert_eq!(err.code, JsonRpcError::INVALID_PARAMS);
+
+        let proof = TxInclusionProof::new(1, B256::ZERO, B256::ZERO, 0, vec![], vec![]);
+        let err = server
+            .handle_request("verify", json!({ "kind": "tx", "proof": proof }))
+            .await
+            .unwrap_err();
+        assert_eq!(err.code, JsonRpcError::SERVER_ERROR);
+    }
+
+    #[test]
+    fn test_cache_eviction_drops_tx_locations() {
+        let server = server();
+        let mut cache = server.cache.lock().unwrap();
+        for block in 1..=3u8 {
+            let block_hash = B256::repeat_byte(block);
+            let tx_hash = B256::repeat_byte(block + 0x10);
+            let txs = TxsMptHandler::new(server.rpc_url.clone()).unwrap();
+            let cached = Arc::new(Mutex::new(CachedBlock {
+                block_number: block as u64,
+                block_hash,
+                txs,
+                receipts: None,
+            }));
+            cache.insert(block_hash, vec![tx_hash], cached);
+        }
+
+        assert_eq!(cache.blocks.len(), 2);
+        assert!(!cache.blocks.contains(&B256::repeat_byte(1)));
+        assert!(!cache.tx_locations.contains_key(&B256::repeat_byte(0x11)));
+        assert_eq!(
+            cache.tx_locations.get(&B256::repeat_byte(0x13)),
+            Some(&(B256::repeat_byte(3), 0))
+        );
+
+        // A reorged block is dropped with its transactions
+        cache.remove(B256::repeat_byte(3));
+        assert!(!cache.blocks.contains(&B256::repeat_byte(3)));
+        assert!(!cache.tx_locations.contains_key(&B256::repeat_byte(0x13)));
+        assert!(cache.tx_locations.contains_key(&B256::repeat_byte(0x12)));
+    }
+}
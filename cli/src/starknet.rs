@@ -0,0 +1,195 @@
+use clap::{Args, Subcommand, ValueEnum};
+use serde::{Deserialize, Serialize};
+use sn_trie_proofs::proof::TrieProof;
+use sn_trie_proofs::rpc::{GATEWAY_URL, MAINNET_GATEWAY_URL};
+use sn_trie_proofs::tx_receipt_trie::TxReceiptsMptHandler;
+use sn_trie_proofs::tx_trie::TxsMptHandler;
+use sn_trie_proofs::SnTrieError;
+use starknet_types_core::felt::Felt;
+
+#[derive(Debug, Subcommand)]
+pub(crate) enum StarknetCommands {
+    #[command(about = "Generate a Starknet proof for a transaction")]
+    Tx(StarknetArgs),
+    #[command(about = "Generate a Starknet proof for a transaction receipt")]
+    Receipt(StarknetArgs),
+}
+
+#[derive(Debug, Args)]
+pub(crate) struct StarknetArgs {
+    /// Target transaction hash
+    #[arg(required_unless_present = "block_number", value_parser = parse_felt)]
+    tx_hash: Option<Felt>,
+    /// Target block number, used with `--index` instead of a transaction hash
+    #[arg(long, conflicts_with = "tx_hash", requires = "index")]
+    block_number: Option<u64>,
+    /// Transaction index within the block
+    #[arg(long, requires = "block_number", conflicts_with = "tx_hash")]
+    index: Option<u64>,
+    /// Starknet node RPC URL
+    #[arg(long)]
+    rpc_url: Option<String>,
+    /// Starknet network
+    #[arg(long, value_enum, default_value_t = Network::Sepolia)]
+    network: Network,
+    /// Decode the printed proof and check it against the commitment it carries. This is
+    /// only a self-consistency round trip: pass `--commitment` to check the proof against
+    /// a trusted block commitment
+    #[arg(long)]
+    verify: bool,
+    /// Trusted transaction or receipt commitment of the block, e.g. from its header, to
+    /// verify the printed proof against
+    #[arg(long, value_parser = parse_felt)]
+    commitment: Option<Felt>,
+}
+
+#[derive(Debug, Clone, Copy, ValueEnum)]
+pub(crate) enum Network {
+    Mainnet,
+    Sepolia,
+}
+
+impl Network {
+    fn default_rpc_url(&self) -> &'static str {
+        match self {
+            Network::Mainnet => "https://starknet-mainnet.public.blastapi.io/rpc/v0_7",
+            Network::Sepolia => "https://pathfinder.sepolia.iosis.tech/",
+        }
+    }
+
+    fn gateway_url(&self) -> &'static str {
+        match self {
+            Network::Mainnet => MAINNET_GATEWAY_URL,
+            Network::Sepolia => GATEWAY_URL,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub(crate) struct SnProof {
+    #[serde(flatten)]
+    pub(crate) proof: TrieProof,
+    pub(crate) protocol_version: String,
+}
+
+pub(crate) async fn run(command: StarknetCommands) -> Result<(), SnTrieError> {
+    let (args, sn_proof) = match command {
+        StarknetCommands::Tx(args) => {
+            let sn_proof = generate_tx_proof(&args).await?;
+            (args, sn_proof)
+        }
+        StarknetCommands::Receipt(args) => {
+            let sn_proof = generate_receipt_proof(&args).await?;
+            (args, sn_proof)
+        }
+    };
+
+    let json = serde_json::to_string(&sn_proof).unwrap();
+    print!("{}", json);
+
+    if let Some(commitment) = args.commitment {
+        verify_proof_json(&json, Some(commitment))?;
+        eprintln!("proof verified against commitment {:#x}", commitment);
+    } else if args.verify {
+        verify_proof_json(&json, None)?;
+        eprintln!("proof consistent with its own commitment");
+    }
+    Ok(())
+}
+
+async fn generate_tx_proof(args: &StarknetArgs) -> Result<SnProof, SnTrieError> {
+    let rpc_url = args
+        .rpc_url
+        .as_deref()
+        .unwrap_or(args.network.default_rpc_url());
+    let mut handler = TxsMptHandler::new_with_gateway(rpc_url, args.network.gateway_url())?;
+
+    let index = match (args.tx_hash, args.block_number) {
+        (Some(tx_hash), _) => {
+            handler.build_tx_tree_from_tx_hash(tx_hash).await?;
+            handler.tx_hash_to_tx_index(tx_hash)?
+        }
+        (None, Some(block_number)) => {
+            handler.build_tx_tree_from_block(block_number).await?;
+            args.index.unwrap_or_default()
+        }
+        (None, None) => unreachable!("enforced by clap"),
+    };
+
+    Ok(SnProof {
+        proof: handler.get_trie_proof(index)?,
+        protocol_version: handler.get_protocol_version()?,
+    })
+}
+
+async fn generate_receipt_proof(args: &StarknetArgs) -> Result<SnProof, SnTrieError> {
+    let rpc_url = args
+        .rpc_url
+        .as_deref()
+        .unwrap_or(args.network.default_rpc_url());
+    let mut handler = TxReceiptsMptHandler::new_with_gateway(rpc_url, args.network.gateway_url())?;
+
+    let index = match (args.tx_hash, args.block_number) {
+        (Some(tx_hash), _) => {
+            handler.build_tx_receipt_tree_from_tx_hash(tx_hash).await?;
+            handler.tx_hash_to_tx_index(tx_hash)?
+        }
+        (None, Some(block_number)) => {
+            handler
+                .build_tx_receipts_tree_from_block(block_number)
+                .await?;
+            args.index.unwrap_or_default()
+        }
+        (None, None) => unreachable!("enforced by clap"),
+    };
+
+    Ok(SnProof {
+        proof: handler.get_trie_proof(index)?,
+        protocol_version: handler.get_protocol_version()?,
+    })
+}
+
+fn parse_felt(value: &str) -> Result<Felt, String> {
+    Felt::from_hex(value).map_err(|_| format!("invalid hex felt: {value}"))
+}
+
+/// Decodes a printed proof and verifies it, as a consumer of the JSON output would.
+///
+/// Without a trusted `commitment`, the proof is only checked against the commitment it
+/// carries, which shows it is consistent but not that it belongs to a block.
+pub(crate) fn verify_proof_json(json: &str, commitment: Option<Felt>) -> Result<(), SnTrieError> {
+    let sn_proof: SnProof =
+        serde_json::from_str(json).map_err(|_| SnTrieError::InvalidProofEncoding)?;
+    let proof = &sn_proof.proof;
+    let membership = match commitment {
+        Some(commitment) => proof.verify(commitment, proof.hash_function)?,
+        None => proof.check_consistency()?,
+    };
+    if !membership.is_member() {
+        return Err(SnTrieError::VerificationError);
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use clap::Parser;
+
+    use super::*;
+
+    #[derive(Debug, Parser)]
+    struct Cli {
+        #[command(subcommand)]
+        command: StarknetCommands,
+    }
+
+    #[test]
+    fn test_index_requires_block_number() {
+        let hash = "0x1";
+        assert!(Cli::try_parse_from(["cli", "tx", hash]).is_ok());
+        assert!(Cli::try_parse_from(["cli", "tx", "--block-number", "5", "--index", "2"]).is_ok());
+        assert!(Cli::try_parse_from(["cli", "tx", hash, "--index", "5"]).is_err());
+        assert!(Cli::try_parse_from(["cli", "receipt", hash, "--index", "5"]).is_err());
+        assert!(Cli::try_parse_from(["cli", "tx", "--block-number", "5"]).is_err());
+    }
+}
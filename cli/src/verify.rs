@@ -0,0 +1,71 @@
+use std::io::Read;
+use std::path::PathBuf;
+
+use alloy_primitives::hex::FromHex;
+use alloy_primitives::B256;
+use clap::Subcommand;
+use eth_trie_proofs::proof::{ReceiptInclusionProof, TxInclusionProof};
+use serde::de::DeserializeOwned;
+
+#[derive(Debug, Subcommand)]
+pub(crate) enum VerifyCommands {
+    #[command(about = "Verify a transaction MPT proof")]
+    Tx {
+        /// Proof JSON file, read from stdin if omitted or `-`
+        #[arg(long)]
+        proof: Option<PathBuf>,
+        /// Expected transaction hash of the proven transaction
+        #[arg(long, value_parser = parse_tx_hash)]
+        tx_hash: Option<B256>,
+    },
+    #[command(about = "Verify a transaction receipt MPT proof")]
+    Receipt {
+        /// Proof JSON file, read from stdin if omitted or `-`
+        #[arg(long)]
+        proof: Option<PathBuf>,
+    },
+}
+
+pub(crate) fn run(command: VerifyCommands) -> Result<(), Box<dyn std::error::Error>> {
+    match command {
+        VerifyCommands::Tx { proof, tx_hash } => {
+            let proof: TxInclusionProof = read_proof(proof)?;
+            let tx = proof.verify()?;
+
+            // Nothing is printed for a proof of another transaction.
+            if let Some(expected) = tx_hash {
+                let actual = tx.0.tx_hash();
+                if actual != expected {
+                    return Err(format!(
+                        "transaction hash mismatch: expected {expected}, got {actual}"
+                    )
+                    .into());
+                }
+            }
+            println!("{:#?}", tx.0);
+        }
+        VerifyCommands::Receipt { proof } => {
+            let proof: ReceiptInclusionProof = read_proof(proof)?;
+            let receipt = proof.verify()?;
+            println!("{:#?}", receipt.0);
+        }
+    }
+
+    Ok(())
+}
+
+fn parse_tx_hash(value: &str) -> Result<B256, String> {
+    B256::from_hex(value).map_err(|_| format!("invalid transaction hash: {value}"))
+}
+
+fn read_proof<P: DeserializeOwned>(path: Option<PathBuf>) -> Result<P, Box<dyn std::error::Error>> {
+    let json = match path {
+        Some(path) if path.as_os_str() != "-" => std::fs::read_to_string(path)?,
+        _ => {
+            let mut json = String::new();
+            std::io::stdin().read_to_string(&mut json)?;
+            json
+        }
+    };
+    Ok(serde_json::from_str(&json)?)
+}
@@ -0,0 +1,79 @@
+//! Compares building transactions tries with `TxsMptHandler::build_trie`, which inserts every
+//! transaction into an in-memory `EthTrie`, and with `IndexedTrieBuilder`.
+//!
+//! Run with `cargo bench -p eth-trie-proofs`.
+
+use std::time::{Duration, Instant};
+
+use alloy::consensus::{SignableTransaction, TxLegacy};
+use alloy::primitives::{Address, Bytes, Signature, TxKind, U256};
+use eth_trie_proofs::trie_builder::IndexedTrieBuilder;
+use eth_trie_proofs::tx::ConsensusTx;
+use eth_trie_proofs::tx_trie::TxsMptHandler;
+use url::Url;
+
+const BLOCK_SIZES: [usize; 3] = [1_000, 5_000, 20_000];
+const PROOF_INDICES: [u64; 4] = [0, 1, 500, 999];
+const ITERATIONS: u32 = 10;
+
+fn block(size: usize) -> Vec<ConsensusTx> {
+    let signature = Signature::from_rs_and_parity(U256::from(1), U256::from(1), 37).unwrap();
+    (0..size)
+        .map(|i| {
+            let tx = TxLegacy {
+                chain_id: Some(1),
+                nonce: i as u64,
+                gas_price: 1_000_000_000,
+                gas_limit: 21_000,
+                to: TxKind::Call(Address::repeat_byte(i as u8)),
+                value: U256::from(i),
+                input: Bytes::from(vec![i as u8; 100]),
+            };
+            ConsensusTx(tx.into_signed(signature).into())
+        })
+        .collect()
+}
+
+/// Times `run` on the output of `setup`, leaving out the setup and dropping the result.
+fn time<I, T>(mut setup: impl FnMut() -> I, mut run: impl FnMut(I) -> T) -> Duration {
+    let mut elapsed = Duration::ZERO;
+    for _ in 0..ITERATIONS {
+        let input = setup();
+        let start = Instant::now();
+        let output = std::hint::black_box(run(input));
+        elapsed += start.elapsed();
+        drop(output);
+    }
+    elapsed / ITERATIONS
+}
+
+fn main() {
+    let url = Url::parse("http://localhost:8545").unwrap();
+    for size in BLOCK_SIZES {
+        let txs = block(size);
+        let builder = IndexedTrieBuilder::new().with_proofs(PROOF_INDICES);
+        let root = builder.build_txs(&txs).root;
+
+        // Both sides are timed from the transactions to the proofs, the handler and the copy
+        // of the transactions it takes are set up beforehand.
+        let eth_trie = time(
+            || (TxsMptHandler::new(url.clone()).unwrap(), txs.clone()),
+            |(mut handler, txs)| {
+                handler.build_trie(txs, root).unwrap();
+                let proofs = PROOF_INDICES
+                    .iter()
+                    .map(|index| handler.get_proof(*index).unwrap())
+                    .collect::<Vec<_>>();
+                (handler, proofs)
+            },
+        );
+        let hash_builder = time(|| (), |()| builder.build_txs(&txs));
+
+        println!(
+            "{size:>6} txs: EthTrie {:>10.2?}, IndexedTrieBuilder {:>10.2?} ({:.1}x)",
+            eth_trie,
+            hash_builder,
+            eth_trie.as_secs_f64() / hash_builder.as_secs_f64()
+        );
+    }
+}
@@ -0,0 +1,371 @@
+//! Arbitrum (Nitro) specific transaction types.
+//!
+//! Arbitrum blocks mix Ethereum transaction types with transactions created by the
+//! sequencer and the retryable ticket mechanism. They are all EIP-2718 typed transactions,
+//! whose payload is the RLP list of the fields of the type.
+//!
+//! Nitro's `ArbitrumLegacyTxType` ([`ARBITRUM_LEGACY_TX_TYPE`]), which wraps the
+//! transactions of the blocks executed before the Nitro upgrade together with their
+//! original hash, is not supported: it is rejected like any unknown type.
+
+use alloy::primitives::{address, keccak256, Address, Bytes, TxKind, B256, U256};
+use alloy_rlp::{BufMut, Decodable, Encodable, RlpDecodable, RlpEncodable};
+
+pub const ARBITRUM_DEPOSIT_TX_TYPE: u8 = 0x64;
+pub const ARBITRUM_UNSIGNED_TX_TYPE: u8 = 0x65;
+pub const ARBITRUM_CONTRACT_TX_TYPE: u8 = 0x66;
+pub const ARBITRUM_RETRY_TX_TYPE: u8 = 0x68;
+pub const ARBITRUM_SUBMIT_RETRYABLE_TX_TYPE: u8 = 0x69;
+pub const ARBITRUM_INTERNAL_TX_TYPE: u8 = 0x6A;
+/// Type of the pre-Nitro transactions, not supported.
+pub const ARBITRUM_LEGACY_TX_TYPE: u8 = 0x78;
+
+/// Deposit of ETH from L1.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Default, RlpEncodable, RlpDecodable)]
+pub struct ArbitrumDepositTx {
+    pub chain_id: U256,
+    pub l1_request_id: B256,
+    pub from: Address,
+    pub to: Address,
+    pub value: U256,
+}
+
+/// Transaction sent from L1 by a contract on behalf of an L2 account, with a nonce.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Default, RlpEncodable, RlpDecodable)]
+pub struct ArbitrumUnsignedTx {
+    pub chain_id: U256,
+    pub from: Address,
+    pub nonce: u64,
+    pub gas_fee_cap: U256,
+    pub gas: u64,
+    pub to: TxKind,
+    pub value: U256,
+    pub data: Bytes,
+}
+
+/// Transaction sent from L1 by a contract on behalf of an L2 account, without nonce.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Default, RlpEncodable, RlpDecodable)]
+pub struct ArbitrumContractTx {
+    pub chain_id: U256,
+    pub request_id: B256,
+    pub from: Address,
+    pub gas_fee_cap: U256,
+    pub gas: u64,
+    pub to: TxKind,
+    pub value: U256,
+    pub data: Bytes,
+}
+
+/// Execution of a retryable ticket, automatic or manual.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Default, RlpEncodable, RlpDecodable)]
+pub struct ArbitrumRetryTx {
+    pub chain_id: U256,
+    /// Number of times the ticket was tried.
+    pub nonce: u64,
+    pub from: Address,
+    pub gas_fee_cap: U256,
+    pub gas: u64,
+    pub to: TxKind,
+    pub value: U256,
+    pub data: Bytes,
+    /// Hash of the submit retryable transaction that created the ticket.
+    pub ticket_id: B256,
+    pub refund_to: Address,
+    pub max_refund: U256,
+    pub submission_fee_refund: U256,
+}
+
+/// Creation of a retryable ticket from L1.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Default, RlpEncodable, RlpDecodable)]
+pub struct ArbitrumSubmitRetryableTx {
+    pub chain_id: U256,
+    pub request_id: B256,
+    pub from: Address,
+    pub l1_base_fee: U256,
+    pub deposit_value: U256,
+    pub gas_fee_cap: U256,
+    pub gas: u64,
+    pub retry_to: TxKind,
+    pub retry_value: U256,
+    pub beneficiary: Address,
+    pub max_submission_fee: U256,
+    pub fee_refund_addr: Address,
+    pub retry_data: Bytes,
+}
+
+/// Transaction of ArbOS itself, e.g. the start of block transaction.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Default, RlpEncodable, RlpDecodable)]
+pub struct ArbitrumInternalTx {
+    pub chain_id: U256,
+    pub data: Bytes,
+}
+
+/// Arbitrum specific transaction.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum ArbitrumTx {
+    Deposit(ArbitrumDepositTx),
+    Unsigned(ArbitrumUnsignedTx),
+    Contract(ArbitrumContractTx),
+    Retry(ArbitrumRetryTx),
+    SubmitRetryable(ArbitrumSubmitRetryableTx),
+    Internal(ArbitrumInternalTx),
+}
+
+impl ArbitrumTx {
+    /// Returns whether `ty` is the EIP-2718 type of an Arbitrum specific transaction.
+    pub fn is_arbitrum_tx_type(ty: u8) -> bool {
+        matches!(
+            ty,
+            ARBITRUM_DEPOSIT_TX_TYPE
+                | ARBITRUM_UNSIGNED_TX_TYPE
+                | ARBITRUM_CONTRACT_TX_TYPE
+                | ARBITRUM_RETRY_TX_TYPE
+                | ARBITRUM_SUBMIT_RETRYABLE_TX_TYPE
+                | ARBITRUM_INTERNAL_TX_TYPE
+        )
+    }
+
+    pub fn tx_type(&self) -> u8 {
+        match self {
+            ArbitrumTx::Deposit(_) => ARBITRUM_DEPOSIT_TX_TYPE,
+            ArbitrumTx::Unsigned(_) => ARBITRUM_UNSIGNED_TX_TYPE,
+            ArbitrumTx::Contract(_) => ARBITRUM_CONTRACT_TX_TYPE,
+            ArbitrumTx::Retry(_) => ARBITRUM_RETRY_TX_TYPE,
+            ArbitrumTx::SubmitRetryable(_) => ARBITRUM_SUBMIT_RETRYABLE_TX_TYPE,
+            ArbitrumTx::Internal(_) => ARBITRUM_INTERNAL_TX_TYPE,
+        }
+    }
+
+    /// Hash of the transaction, i.e. the hash of its 2718 encoding.
+    pub fn tx_hash(&self) -> B256 {
+        let mut out = vec![];
+        self.encode_2718(&mut out);
+        keccak256(out)
+    }
+
+    fn fields(&self) -> &dyn Encodable {
+        match self {
+            ArbitrumTx::Deposit(tx) => tx,
+            ArbitrumTx::Unsigned(tx) => tx,
+            ArbitrumTx::Contract(tx) => tx,
+            ArbitrumTx::Retry(tx) => tx,
+            ArbitrumTx::SubmitRetryable(tx) => tx,
+            ArbitrumTx::Internal(tx) => tx,
+        }
+    }
+
+    /// Length of the 2718 encoding, type byte included.
+    pub fn encode_2718_len(&self) -> usize {
+        1 + self.fields().length()
+    }
+
+    /// Encodes the transaction as its type byte followed by the RLP list of its fields.
+    pub fn encode_2718(&self, out: &mut dyn BufMut) {
+        out.put_u8(self.tx_type());
+        self.fields().encode(out);
+    }
+
+    /// Decodes the RLP list following the type byte `ty`.
+    pub fn typed_decode(ty: u8, buf: &mut &[u8]) -> alloy_rlp::Result<Self> {
+        Ok(match ty {
+            ARBITRUM_DEPOSIT_TX_TYPE => ArbitrumTx::Deposit(Decodable::decode(buf)?),
+            ARBITRUM_UNSIGNED_TX_TYPE => ArbitrumTx::Unsigned(Decodable::decode(buf)?),
+            ARBITRUM_CONTRACT_TX_TYPE => ArbitrumTx::Contract(Decodable::decode(buf)?),
+            ARBITRUM_RETRY_TX_TYPE => ArbitrumTx::Retry(Decodable::decode(buf)?),
+            ARBITRUM_SUBMIT_RETRYABLE_TX_TYPE => {
+                ArbitrumTx::SubmitRetryable(Decodable::decode(buf)?)
+            }
+            ARBITRUM_INTERNAL_TX_TYPE => ArbitrumTx::Internal(Decodable::decode(buf)?),
+            _ => return Err(alloy_rlp::Error::Custom("unexpected arbitrum tx type")),
+        })
+    }
+
+    pub fn chain_id(&self) -> U256 {
+        match self {
+            ArbitrumTx::Deposit(tx) => tx.chain_id,
+            ArbitrumTx::Unsigned(tx) => tx.chain_id,
+            ArbitrumTx::Contract(tx) => tx.chain_id,
+            ArbitrumTx::Retry(tx) => tx.chain_id,
+            ArbitrumTx::SubmitRetryable(tx) => tx.chain_id,
+            ArbitrumTx::Internal(tx) => tx.chain_id,
+        }
+    }
+
+    /// Nonce of unsigned transactions, and number of tries of retries. Other transactions
+    /// have no nonce.
+    pub fn nonce(&self) -> u64 {
+        match self {
+            ArbitrumTx::Unsigned(tx) => tx.nonce,
+            ArbitrumTx::Retry(tx) => tx.nonce,
+            _ => 0,
+        }
+    }
+
+    pub fn gas_limit(&self) -> u64 {
+        match self {
+            ArbitrumTx::Unsigned(tx) => tx.gas,
+            ArbitrumTx::Contract(tx) => tx.gas,
+            ArbitrumTx::Retry(tx) => tx.gas,
+            ArbitrumTx::SubmitRetryable(tx) => tx.gas,
+            ArbitrumTx::Deposit(_) | ArbitrumTx::Internal(_) => 0,
+        }
+    }
+
+    pub fn gas_fee_cap(&self) -> Option<U256> {
+        match self {
+            ArbitrumTx::Unsigned(tx) => Some(tx.gas_fee_cap),
+            ArbitrumTx::Contract(tx) => Some(tx.gas_fee_cap),
+            ArbitrumTx::Retry(tx) => Some(tx.gas_fee_cap),
+            ArbitrumTx::SubmitRetryable(tx) => Some(tx.gas_fee_cap),
+            ArbitrumTx::Deposit(_) | ArbitrumTx::Internal(_) => None,
+        }
+    }
+
+    /// Recipient of the transaction. For submit retryable transactions, this is the
+    /// recipient of the retryable ticket.
+    pub fn to(&self) -> TxKind {
+        match self {
+            ArbitrumTx::Deposit(tx) => TxKind::Call(tx.to),
+            ArbitrumTx::Unsigned(tx) => tx.to,
+            ArbitrumTx::Contract(tx) => tx.to,
+            ArbitrumTx::Retry(tx) => tx.to,
+            ArbitrumTx::SubmitRetryable(tx) => tx.retry_to,
+            ArbitrumTx::Internal(_) => TxKind::Call(ARBOS_ADDRESS),
+        }
+    }
+
+    /// Value of the transaction. For submit retryable transactions, this is the value of
+    /// the retryable ticket.
+    pub fn value(&self) -> U256 {
+        match self {
+            ArbitrumTx::Deposit(tx) => tx.value,
+            ArbitrumTx::Unsigned(tx) => tx.value,
+            ArbitrumTx::Contract(tx) => tx.value,
+            ArbitrumTx::Retry(tx) => tx.value,
+            ArbitrumTx::SubmitRetryable(tx) => tx.retry_value,
+            ArbitrumTx::Internal(_) => U256::ZERO,
+        }
+    }
+
+    /// Input of the transaction. For submit retryable transactions, this is the input of
+    /// the retryable ticket.
+    pub fn input(&self) -> &[u8] {
+        match self {
+            ArbitrumTx::Deposit(_) => &[],
+            ArbitrumTx::Unsigned(tx) => &tx.data,
+            ArbitrumTx::Contract(tx) => &tx.data,
+            ArbitrumTx::Retry(tx) => &tx.data,
+            ArbitrumTx::SubmitRetryable(tx) => &tx.retry_data,
+            ArbitrumTx::Internal(tx) => &tx.data,
+        }
+    }
+
+    /// Sender of the transaction. Internal transactions are sent by ArbOS.
+    pub fn from(&self) -> Address {
+        match self {
+            ArbitrumTx::Deposit(tx) => tx.from,
+            ArbitrumTx::Unsigned(tx) => tx.from,
+            ArbitrumTx::Contract(tx) => tx.from,
+            ArbitrumTx::Retry(tx) => tx.from,
+            ArbitrumTx::SubmitRetryable(tx) => tx.from,
+            ArbitrumTx::Internal(_) => ARBOS_ADDRESS,
+        }
+    }
+}
+
+/// Address sending the internal transactions of ArbOS.
+pub const ARBOS_ADDRESS: Address = address!("00000000000000000000000000000000000a4b05");
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn txs() -> Vec<ArbitrumTx> {
+        let chain_id = U256::from(42161);
+        vec![
+            ArbitrumTx::Deposit(ArbitrumDepositTx {
+                chain_id,
+                l1_request_id: B256::repeat_byte(0x01),
+                from: Address::repeat_byte(0x02),
+                to: Address::repeat_byte(0x03),
+                value: U256::from(10).pow(U256::from(18)),
+            }),
+            ArbitrumTx::Unsigned(ArbitrumUnsignedTx {
+                chain_id,
+                from: Address::repeat_byte(0x02),
+                nonce: 7,
+                gas_fee_cap: U256::from(100_000_000),
+                gas: 1_000_000,
+                to: TxKind::Create,
+                value: U256::ZERO,
+                data: Bytes::from_static(&[0x60, 0x80]),
+            }),
+            ArbitrumTx::Contract(ArbitrumContractTx {
+                chain_id,
+                request_id: B256::repeat_byte(0x04),
+                from: Address::repeat_byte(0x02),
+                gas_fee_cap: U256::from(100_000_000),
+                gas: 1_000_000,
+                to: TxKind::Call(Address::repeat_byte(0x05)),
+                value: U256::from(1),
+                data: Bytes::new(),
+            }),
+            ArbitrumTx::Retry(ArbitrumRetryTx {
+                chain_id,
+                nonce: 0,
+                from: Address::repeat_byte(0x02),
+                gas_fee_cap: U256::from(100_000_000),
+                gas: 500_000,
+                to: TxKind::Call(Address::repeat_byte(0x05)),
+                value: U256::from(5),
+                data: Bytes::from_static(&[0xab, 0xcd]),
+                ticket_id: B256::repeat_byte(0x06),
+                refund_to: Address::repeat_byte(0x07),
+                max_refund: U256::from(1_000),
+                submission_fee_refund: U256::from(10),
+            }),
+            ArbitrumTx::SubmitRetryable(ArbitrumSubmitRetryableTx {
+                chain_id,
+                request_id: B256::repeat_byte(0x08),
+                from: Address::repeat_byte(0x02),
+                l1_base_fee: U256::from(30_000_000_000u64),
+                deposit_value: U256::from(1_000_000),
+                gas_fee_cap: U256::from(100_000_000),
+                gas: 500_000,
+                retry_to: TxKind::Call(Address::repeat_byte(0x05)),
+                retry_value: U256::from(5),
+                beneficiary: Address::repeat_byte(0x09),
+                max_submission_fee: U256::from(2_000),
+                fee_refund_addr: Address::repeat_byte(0x07),
+                retry_data: Bytes::from_static(&[0xab, 0xcd]),
+            }),
+            ArbitrumTx::Internal(ArbitrumInternalTx {
+                chain_id,
+                data: Bytes::from_static(&[0x6b, 0xf6, 0xa4, 0x2d]),
+            }),
+        ]
+    }
+
+    #[test]
+    fn test_arbitrum_tx_round_trip() {
+        for tx in txs() {
+            let mut encoded = vec![];
+            tx.encode_2718(&mut encoded);
+            assert_eq!(encoded[0], tx.tx_type());
+            assert!(ArbitrumTx::is_arbitrum_tx_type(encoded[0]));
+            assert_eq!(encoded.len(), tx.encode_2718_len());
+            assert_eq!(tx.tx_hash(), keccak256(&encoded));
+
+            let decoded = ArbitrumTx::typed_decode(encoded[0], &mut &encoded[1..]).unwrap();
+            assert_eq!(decoded, tx);
+            assert!(
+                ArbitrumTx::typed_decode(encoded[0], &mut &encoded[1..encoded.len() - 1]).is_err()
+            );
+        }
+        assert!(!ArbitrumTx::is_arbitrum_tx_type(0x67));
+
+        // Pre-Nitro transactions are not supported
+        assert!(!ArbitrumTx::is_arbitrum_tx_type(ARBITRUM_LEGACY_TX_TYPE));
+        assert!(ArbitrumTx::typed_decode(ARBITRUM_LEGACY_TX_TYPE, &mut &[0xc0][..]).is_err());
+    }
+}
@@ -0,0 +1,231 @@
+use std::collections::{BTreeMap, BTreeSet};
+use std::future::Future;
+
+use futures::{future::try_join, stream, StreamExt};
+use url::Url;
+
+use crate::{
+    chain::{ChainSpec, ChainSpecRegistry},
+    error::EthTrieError,
+    rpc::RpcProvider,
+    tx_receipt_trie::TxReceiptsMptHandler,
+    tx_trie::TxsMptHandler,
+};
+
+/// Number of blocks fetched at the same time by default.
+const DEFAULT_CONCURRENCY: usize = 8;
+
+/// Results of a batch by block number. A failed block does not abort the others.
+pub type BlockResults<T> = BTreeMap<u64, Result<T, EthTrieError>>;
+
+/// Transactions and receipts tries of a block.
+pub struct BlockTries {
+    pub txs: TxsMptHandler,
+    pub receipts: TxReceiptsMptHandler,
+}
+
+/// Builds the tries of many blocks, fetching up to a given number of blocks at the same time.
+///
+/// Each block gets its own handler, whose trie is built and ready to generate proofs. All the
+/// handlers share the HTTP client of the builder.
+///
+/// The concurrency limit counts blocks, not requests: a block has one request in flight when
+/// building one trie, and two with [`MultiBlockBuilder::build_tries`], which fetches its
+/// transactions and receipts at the same time.
+pub struct MultiBlockBuilder {
+    provider: RpcProvider,
+    /// Profile of the chain.
+    /// If `None`, it is resolved once per batch from the chain id of the provider.
+    chain_spec: Option<ChainSpec>,
+    concurrency: usize,
+}
+
+impl MultiBlockBuilder {
+    /// Creates a new [`MultiBlockBuilder`] with a given RPC provider URL.
+    pub fn new(url: Url) -> Self {
+        Self {
+            provider: RpcProvider::new(url),
+            chain_spec: None,
+            concurrency: DEFAULT_CONCURRENCY,
+        }
+    }
+
+    /// Sets the maximum number of blocks fetched at the same time, at least 1.
+    ///
+    /// With [`MultiBlockBuilder::build_tries`], up to twice as many requests are in flight.
+    pub fn with_concurrency(mut self, limit: usize) -> Self {
+        self.concurrency = limit.max(1);
+        self
+    }
+
+    /// Sets the profile of the chain, instead of resolving it from the chain id.
+    pub fn with_chain_spec(mut self, chain_spec: ChainSpec) -> Self {
+        self.chain_spec = Some(chain_spec);
+        self
+    }
+
+    /// Builds the transactions trie of each block.
+    pub async fn build_tx_tries(
+        &self,
+        block_numbers: impl IntoIterator<Item = u64>,
+    ) -> BlockResults<TxsMptHandler> {
+        let chain_spec = self.resolve_chain_spec().await;
+        self.run(block_numbers, |block_number| {
+            self.build_txs(chain_spec.clone(), block_number)
+        })
+        .await
+    }
+
+    /// Builds the receipts trie of each block.
+    pub async fn build_receipt_tries(
+        &self,
+        block_numbers: impl IntoIterator<Item = u64>,
+    ) -> BlockResults<TxReceiptsMptHandler> {
+        let chain_spec = self.resolve_chain_spec().await;
+        self.run(block_numbers, |block_number| {
+            self.build_receipts(chain_spec.clone(), block_number)
+        })
+        .await
+    }
+
+    /// Builds the transactions and receipts tries of each block, fetching both at the same time.
+    ///
+    /// The header of each block is fetched once, along its transactions, and used for both
+    /// tries. Receipts of another block, after a reorg, do not match its receipts root.
+    pub async fn build_tries(
+        &self,
+        block_numbers: impl IntoIterator<Item = u64>,
+    ) -> BlockResults<BlockTries> {
+        let chain_spec = self.resolve_chain_spec().await;
+        self.run(block_numbers, |block_number| {
+            self.build_both(chain_spec.clone(), block_number)
+        })
+        .await
+    }
+
+    /// Runs `build` once for each block, with at most `concurrency` blocks in flight.
+    async fn run<T, F, Fut>(
+        &self,
+        block_numbers: impl IntoIterator<Item = u64>,
+        build: F,
+    ) -> BlockResults<T>
+    where
+        F: Fn(u64) -> Fut,
+        Fut: Future<Output = Result<T, EthTrieError>>,
+    {
+        let block_numbers: BTreeSet<u64> = block_numbers.into_iter().collect();
+        stream::iter(block_numbers)
+            .map(|block_number| {
+                let result = build(block_number);
+                async move { (block_number, result.await) }
+            })
+            .buffer_unordered(self.concurrency)
+            .collect()
+            .await
+    }
+
+    async fn build_txs(
+        &self,
+        chain_spec: Option<ChainSpec>,
+        block_number: u64,
+    ) -> Result<TxsMptHandler, EthTrieError> {
+        let mut handler = TxsMptHandler::with_provider(self.provider.clone(), chain_spec);
+        handler.build_tx_tree_from_block(block_number).await?;
+        Ok(handler)
+    }
+
+    async fn build_receipts(
+        &self,
+        chain_spec: Option<ChainSpec>,
+        block_number: u64,
+    ) -> Result<TxReceiptsMptHandler, EthTrieError> {
+        let mut handler = TxReceiptsMptHandler::with_provider(self.provider.clone(), chain_spec);
+        handler
+            .build_tx_receipts_tree_from_block(block_number)
+            .await?;
+        Ok(handler)
+    }
+
+    async fn build_both(
+        &self,
+        chain_spec: Option<ChainSpec>,
+        block_number: u64,
+    ) -> Result<BlockTries, EthTrieError> {
+        let ((rpc_txs, header), rpc_receipts) = try_join(
+            self.provider.get_block_transactions(block_number),
+            self.provider.get_block_receipts(block_number),
+        )
+        .await?;
+
+        let mut txs = TxsMptHandler::with_provider(self.provider.clone(), chain_spec.clone());
+        txs.build_tx_tree_from_rpc(block_number, rpc_txs, header.clone())
+            .await?;
+        let mut receipts = TxReceiptsMptHandler::with_provider(self.provider.clone(), chain_spec);
+        receipts
+            .build_tx_receipts_tree_from_rpc(block_number, rpc_receipts, header)
+            .await?;
+        Ok(BlockTries { txs, receipts })
+    }
+
+    /// Resolves the profile of the chain once for the whole batch.
+    ///
+    /// If the chain id cannot be fetched, each handler tries again, and reports the error
+    /// for its block.
+    async fn resolve_chain_spec(&self) -> Option<ChainSpec> {
+        if let Some(chain_spec) = &self.chain_spec {
+            return Some(chain_spec.clone());
+        }
+        let chain_id = self.provider.get_chain_id().await.ok()?;
+        Some(ChainSpecRegistry::new().resolve(chain_id))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const MAINNET_RPC_URL: &str = "https://ethereum-rpc.publicnode.com";
+
+    #[tokio::test]
+    async fn test_multi_block_errors_per_block() {
+        // Nothing listens on port 1, every block fails on its own.
+        let builder = MultiBlockBuilder::new(Url::parse("http://127.0.0.1:1").unwrap())
+            .with_chain_spec(ChainSpec::MAINNET)
+            .with_concurrency(0);
+        let results = builder.build_tx_tries([30, 10, 20, 10]).await;
+
+        assert_eq!(
+            results.keys().copied().collect::<Vec<_>>(),
+            vec![10, 20, 30]
+        );
+        assert!(results
+            .values()
+            .all(|result| matches!(result, Err(EthTrieError::RPC(_)))));
+
+        let results = builder.build_tries([10, 20]).await;
+        assert_eq!(results.keys().copied().collect::<Vec<_>>(), vec![10, 20]);
+        assert!(results
+            .values()
+            .all(|result| matches!(result, Err(EthTrieError::RPC(_)))));
+    }
+
+    #[tokio::test]
+    async fn test_multi_block_tries() {
+        let blocks = [46147, 4370000, 12244000, 12965000, 19426589];
+        let builder =
+            MultiBlockBuilder::new(Url::parse(MAINNET_RPC_URL).unwrap()).with_concurrency(2);
+        let results = builder.build_tries(blocks).await;
+
+        assert_eq!(results.keys().copied().collect::<Vec<_>>(), blocks);
+        for (block_number, result) in results {
+            let BlockTries { mut txs, receipts } = result.unwrap();
+            let proof = txs.get_inclusion_proof(0).unwrap();
+            assert_eq!(proof.block_number, block_number);
+            proof.verify().unwrap();
+            assert_eq!(
+                receipts.elements().unwrap().len(),
+                txs.elements().unwrap().len()
+            );
+        }
+    }
+}
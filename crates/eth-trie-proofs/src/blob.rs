@@ -0,0 +1,358 @@
+use std::{collections::HashMap, path::PathBuf};
+
+use alloy::eips::eip4844::{env_settings::EnvKzgSettings, Blob, BlobTransactionSidecar, Bytes48};
+use alloy::primitives::B256;
+use serde::{de, Deserialize, Deserializer, Serialize};
+use url::Url;
+
+use crate::{error::EthTrieError, proof::TxInclusionProof, tx::ConsensusTx};
+
+/// Proof that blobs belong to a type-3 transaction included in a block.
+///
+/// The transactions trie only commits to the versioned hashes of the blobs. The sidecar links
+/// them to the blob data: each versioned hash is the versioned SHA-256 of a KZG commitment, and
+/// the KZG proofs check the blobs against their commitments.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BlobInclusionProof {
+    pub tx: TxInclusionProof,
+    /// Blobs of the transaction, in the order of its versioned hashes.
+    pub sidecar: BlobTransactionSidecar,
+}
+
+impl BlobInclusionProof {
+    /// Attaches a sidecar to a transaction proof, failing if the proof does not verify.
+    pub fn new(
+        tx: TxInclusionProof,
+        sidecar: BlobTransactionSidecar,
+    ) -> Result<Self, EthTrieError> {
+        let proof = Self { tx, sidecar };
+        proof.verify()?;
+        Ok(proof)
+    }
+
+    /// Verifies the transaction proof and the sidecar against the versioned hashes of the
+    /// transaction, and returns the decoded transaction.
+    pub fn verify(&self) -> Result<ConsensusTx, EthTrieError> {
+        let tx = self.tx.verify()?;
+        let versioned_hashes = tx
+            .blob_versioned_hashes()
+            .ok_or(EthTrieError::InvalidTxVersion)?;
+        verify_blob_sidecar(&self.sidecar, &versioned_hashes)?;
+        Ok(tx)
+    }
+}
+
+/// Checks that the sidecar holds the blobs with the given versioned hashes, in order.
+pub fn verify_blob_sidecar(
+    sidecar: &BlobTransactionSidecar,
+    versioned_hashes: &[B256],
+) -> Result<(), EthTrieError> {
+    sidecar
+        .validate(versioned_hashes, EnvKzgSettings::Default.get())
+        .map_err(EthTrieError::InvalidBlobSidecar)
+}
+
+/// Where to fetch the blobs of a block from.
+#[derive(Debug, Clone)]
+pub enum BlobSidecarSource {
+    /// JSON file holding a [`BlobTransactionSidecar`], which may contain blobs of other
+    /// transactions of the block.
+    File(PathBuf),
+    /// Beacon node API. Nodes prune blobs after about 18 days, older blobs need an archive node.
+    BeaconApi(Url),
+}
+
+impl BlobSidecarSource {
+    /// Fetches the blobs with the given versioned hashes, in order, for the block produced at
+    /// `block_timestamp`.
+    pub async fn fetch(
+        &self,
+        block_timestamp: u64,
+        versioned_hashes: &[B256],
+    ) -> Result<BlobTransactionSidecar, EthTrieError> {
+        let sidecar = match self {
+            BlobSidecarSource::File(path) => {
+                let data = tokio::fs::read(path).await.map_err(EthTrieError::Io)?;
+                serde_json::from_slice(&data).map_err(EthTrieError::Json)?
+            }
+            BlobSidecarSource::BeaconApi(url) => fetch_beacon_sidecar(url, block_timestamp)
+                .await
+                .map_err(EthTrieError::BeaconApi)?,
+        };
+        select_blobs(sidecar, versioned_hashes)
+    }
+}
+
+/// Picks the blobs with the given versioned hashes out of a sidecar.
+///
+/// Fails with [`EthTrieError::MalformedBlobSidecar`] if the sidecar does not have as many
+/// blobs, commitments and proofs.
+fn select_blobs(
+    sidecar: BlobTransactionSidecar,
+    versioned_hashes: &[B256],
+) -> Result<BlobTransactionSidecar, EthTrieError> {
+    // Positions come from the commitments, and index the blobs and proofs too.
+    if sidecar.blobs.len() != sidecar.commitments.len()
+        || sidecar.proofs.len() != sidecar.commitments.len()
+    {
+        return Err(EthTrieError::MalformedBlobSidecar);
+    }
+    let positions: HashMap<B256, usize> = sidecar
+        .versioned_hashes()
+        .enumerate()
+        .map(|(index, hash)| (hash, index))
+        .collect();
+    let indices = versioned_hashes
+        .iter()
+        .map(|hash| {
+            positions
+                .get(hash)
+                .copied()
+                .ok_or(EthTrieError::BlobNotFound(*hash))
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+
+    // Blobs are 128 KiB each, move them out of the sidecar rather than copying them. Only a
+    // versioned hash listed twice needs a copy, of the blob already taken.
+    let mut blobs: Vec<Option<Blob>> = sidecar.blobs.into_iter().map(Some).collect();
+    let mut selected: Vec<Blob> = Vec::with_capacity(indices.len());
+    for (position, &i) in indices.iter().enumerate() {
+        let blob = match blobs[i].take() {
+            Some(blob) => blob,
+            None => selected[indices[..position].iter().position(|&j| j == i).unwrap()],
+        };
+        selected.push(blob);
+    }
+    Ok(BlobTransactionSidecar {
+        blobs: selected,
+        commitments: indices.iter().map(|&i| sidecar.commitments[i]).collect(),
+        proofs: indices.iter().map(|&i| sidecar.proofs[i]).collect(),
+    })
+}
+
+#[derive(Deserialize)]
+struct BeaconResponse<T> {
+    data: T,
+}
+
+#[derive(Deserialize)]
+struct BeaconGenesis {
+    #[serde(deserialize_with = "deserialize_decimal")]
+    genesis_time: u64,
+}
+
+#[derive(Deserialize)]
+struct BeaconSpec {
+    #[serde(rename = "SECONDS_PER_SLOT", deserialize_with = "deserialize_decimal")]
+    seconds_per_slot: u64,
+}
+
+#[derive(Deserialize)]
+struct BeaconBlobSidecar {
+    blob: Box<Blob>,
+    kzg_commitment: Bytes48,
+    kzg_proof: Bytes48,
+}
+
+/// The beacon API encodes numbers as decimal strings.
+fn deserialize_decimal<'de, D: Deserializer<'de>>(deserializer: D) -> Result<u64, D::Error> {
+    String::deserialize(deserializer)?
+        .parse()
+        .map_err(de::Error::custom)
+}
+
+/// Fetches the blob sidecars of the slot of the block produced at `block_timestamp`.
+async fn fetch_beacon_sidecar(
+    url: &Url,
+    block_timestamp: u64,
+) -> Result<BlobTransactionSidecar, reqwest::Error> {
+    let client = reqwest::Client::new();
+    let base = url.as_str().trim_end_matches('/');
+    let get = |path: String| client.get(format!("{base}/eth/v1/{path}")).send();
+
+    let genesis: BeaconResponse<BeaconGenesis> = get("beacon/genesis".to_string())
+        .await?
+        .error_for_status()?
+        .json()
+        .await?;
+    let spec: BeaconResponse<BeaconSpec> = get("config/spec".to_string())
+        .await?
+        .error_for_status()?
+        .json()
+        .await?;
+    let slot = block_timestamp.saturating_sub(genesis.data.genesis_time)
+        / spec.data.seconds_per_slot.max(1);
+
+    let sidecars: BeaconResponse<Vec<BeaconBlobSidecar>> =
+        get(format!("beacon/blob_sidecars/{slot}"))
+            .await?
+            .error_for_status()?
+            .json()
+            .await?;
+
+    let mut sidecar = BlobTransactionSidecar::default();
+    for item in sidecars.data {
+        sidecar.blobs.push(*item.blob);
+        sidecar.commitments.push(item.kzg_commitment);
+        sidecar.proofs.push(item.kzg_proof);
+    }
+    Ok(sidecar)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tx_trie::TxsMptHandler;
+    use alloy::consensus::{
+        SidecarBuilder, SignableTransaction, SimpleCoder, TxEip1559, TxEip4844, TxEip4844Variant,
+        TxEip4844WithSidecar,
+    };
+    use alloy::network::eip2718::Encodable2718;
+    use alloy::primitives::{Signature, U256};
+    use eth_trie::{EthTrie, MemoryDB, Trie};
+    use std::sync::Arc;
+
+    fn sidecar(data: &[u8]) -> BlobTransactionSidecar {
+        SidecarBuilder::<SimpleCoder>::from_slice(data)
+            .build()
+            .unwrap()
+    }
+
+    fn signature() -> Signature {
+        Signature::from_rs_and_parity(U256::from(1), U256::from(1), false).unwrap()
+    }
+
+    fn blob_tx(sidecar: BlobTransactionSidecar) -> ConsensusTx {
+        let tx = TxEip4844WithSidecar {
+            tx: TxEip4844 {
+                chain_id: 1,
+                blob_versioned_hashes: sidecar.versioned_hashes().collect(),
+                max_fee_per_blob_gas: 1,
+                ..Default::default()
+            },
+            sidecar,
+        };
+        ConsensusTx(TxEip4844Variant::from(tx).into_signed(signature()).into())
+    }
+
+    /// Proves the single transaction of a one-transaction block.
+    fn tx_proof(tx: ConsensusTx) -> TxInclusionProof {
+        let tx = tx.without_blob_sidecar();
+        let mut trie = EthTrie::new(Arc::new(MemoryDB::new(true)));
+        let key = alloy_rlp::encode(U256::ZERO);
+        trie.insert(&key, &tx.0.encoded_2718()).unwrap();
+        let root = B256::from_slice(trie.root_hash().unwrap().as_bytes());
+
+        let mut handler = TxsMptHandler::new(Url::parse("http://localhost").unwrap()).unwrap();
+        handler.build_trie(vec![tx.clone()], root).unwrap();
+        let nodes = handler.get_proof(0).unwrap();
+        TxInclusionProof::new(1, B256::ZERO, root, 0, nodes, tx.0.encoded_2718())
+    }
+
+    #[test]
+    fn test_blob_inclusion_proof() {
+        let sidecar = sidecar(b"blob data");
+        let tx = blob_tx(sidecar.clone());
+        assert_eq!(tx.blob_sidecar(), Some(&sidecar));
+        assert_eq!(tx.clone().without_blob_sidecar().blob_sidecar(), None);
+
+        let proof = BlobInclusionProof::new(tx_proof(tx.clone()), sidecar.clone()).unwrap();
+        let verified = proof.verify().unwrap();
+        assert_eq!(verified.0.tx_hash(), tx.0.tx_hash());
+
+        let json = serde_json::to_string(&proof).unwrap();
+        let decoded: BlobInclusionProof = serde_json::from_str(&json).unwrap();
+        assert_eq!(decoded, proof);
+    }
+
+    #[test]
+    fn test_blob_inclusion_proof_tampered() {
+        let sidecar = sidecar(b"blob data");
+        let tx_proof = tx_proof(blob_tx(sidecar.clone()));
+
+        // The blob does not match the commitment
+        let mut tampered = sidecar.clone();
+        tampered.blobs[0].0[0] = 1;
+        assert!(matches!(
+            BlobInclusionProof::new(tx_proof.clone(), tampered),
+            Err(EthTrieError::InvalidBlobSidecar(_))
+        ));
+
+        // The commitment does not match the versioned hash
+        let other = self::sidecar(b"other blob data");
+        assert!(matches!(
+            BlobInclusionProof::new(tx_proof.clone(), other),
+            Err(EthTrieError::InvalidBlobSidecar(_))
+        ));
+
+        // Blobs only belong to type-3 transactions
+        let tx = TxEip1559::default().into_signed(signature());
+        assert!(matches!(
+            BlobInclusionProof::new(self::tx_proof(ConsensusTx(tx.into())), sidecar),
+            Err(EthTrieError::InvalidTxVersion)
+        ));
+    }
+
+    #[test]
+    fn test_select_blobs_malformed_sidecar() {
+        let sidecar = sidecar(b"blob data");
+        let hashes: Vec<B256> = sidecar.versioned_hashes().collect();
+        assert_eq!(select_blobs(sidecar.clone(), &hashes).unwrap(), sidecar);
+
+        // A commitment without blob nor proof
+        let mut malformed = sidecar.clone();
+        malformed
+            .commitments
+            .extend(self::sidecar(b"other").commitments);
+        let hashes: Vec<B256> = malformed.versioned_hashes().collect();
+        assert!(matches!(
+            select_blobs(malformed, &hashes),
+            Err(EthTrieError::MalformedBlobSidecar)
+        ));
+
+        // A blob without proof
+        let mut malformed = sidecar.clone();
+        malformed.proofs.clear();
+        assert!(matches!(
+            select_blobs(malformed, &hashes[..1]),
+            Err(EthTrieError::MalformedBlobSidecar)
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_blob_sidecar_file_source() {
+        let first = sidecar(b"first blob");
+        let second = sidecar(b"second blob");
+        let mut block_sidecar = first.clone();
+        block_sidecar.blobs.extend(second.blobs.clone());
+        block_sidecar.commitments.extend(second.commitments.clone());
+        block_sidecar.proofs.extend(second.proofs.clone());
+
+        let path = std::env::temp_dir().join(format!(
+            "eth-trie-proofs-blob-sidecar-{}.json",
+            std::process::id()
+        ));
+        std::fs::write(&path, serde_json::to_vec(&block_sidecar).unwrap()).unwrap();
+        let source = BlobSidecarSource::File(path.clone());
+
+        let hashes: Vec<B256> = second.versioned_hashes().collect();
+        let fetched = source.fetch(0, &hashes).await.unwrap();
+        assert_eq!(fetched, second);
+        verify_blob_sidecar(&fetched, &hashes).unwrap();
+
+        // A blob listed twice is selected twice
+        let mut hashes: Vec<B256> = block_sidecar.versioned_hashes().collect();
+        hashes.push(hashes[0]);
+        let fetched = source.fetch(0, &hashes).await.unwrap();
+        assert_eq!(fetched.blobs[2], first.blobs[0]);
+        verify_blob_sidecar(&fetched, &hashes).unwrap();
+
+        let missing = B256::repeat_byte(1);
+        assert!(matches!(
+            source.fetch(0, &[missing]).await,
+            Err(EthTrieError::BlobNotFound(hash)) if hash == missing
+        ));
+        std::fs::remove_file(path).unwrap();
+    }
+}
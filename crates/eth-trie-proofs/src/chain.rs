@@ -0,0 +1,451 @@
+//! Chain profiles describing the per-chain rules followed to rebuild tries.
+//!
+//! Which transaction types exist, when receipts switch from an intermediate state root to a
+//! status, and which fields the block header carries all differ between EVM chains. A
+//! [`ChainSpec`] gathers these rules, and a [`ChainSpecRegistry`] maps chain ids to them.
+
+use std::borrow::Cow;
+use std::collections::HashMap;
+
+use alloy::consensus::TxType;
+use alloy::rpc::types::Header;
+
+use crate::arbitrum::{
+    ArbitrumTx, ARBITRUM_CONTRACT_TX_TYPE, ARBITRUM_DEPOSIT_TX_TYPE, ARBITRUM_INTERNAL_TX_TYPE,
+    ARBITRUM_RETRY_TX_TYPE, ARBITRUM_SUBMIT_RETRYABLE_TX_TYPE, ARBITRUM_UNSIGNED_TX_TYPE,
+};
+use crate::error::{EthTrieError, Field};
+use crate::op::{OpHardforks, DEPOSIT_TX_TYPE};
+
+const ETHEREUM_TX_TYPES: &[u8] = &[1, 2, 3];
+const OP_STACK_TX_TYPES: &[u8] = &[1, 2, DEPOSIT_TX_TYPE];
+const ARBITRUM_TX_TYPES: &[u8] = &[
+    1,
+    2,
+    ARBITRUM_DEPOSIT_TX_TYPE,
+    ARBITRUM_UNSIGNED_TX_TYPE,
+    ARBITRUM_CONTRACT_TX_TYPE,
+    ARBITRUM_RETRY_TX_TYPE,
+    ARBITRUM_SUBMIT_RETRYABLE_TX_TYPE,
+    ARBITRUM_INTERNAL_TX_TYPE,
+];
+const POLYGON_TX_TYPES: &[u8] = &[1, 2];
+const ALL_TX_TYPES: &[u8] = &[
+    1,
+    2,
+    3,
+    DEPOSIT_TX_TYPE,
+    ARBITRUM_DEPOSIT_TX_TYPE,
+    ARBITRUM_UNSIGNED_TX_TYPE,
+    ARBITRUM_CONTRACT_TX_TYPE,
+    ARBITRUM_RETRY_TX_TYPE,
+    ARBITRUM_SUBMIT_RETRYABLE_TX_TYPE,
+    ARBITRUM_INTERNAL_TX_TYPE,
+];
+
+/// Activation condition of a fork.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ForkCondition {
+    /// Active from a block number.
+    Block(u64),
+    /// Active from a block timestamp.
+    Timestamp(u64),
+    /// Never active.
+    Never,
+    /// Activation is not known, so whatever the RPC returns is accepted.
+    Unknown,
+}
+
+impl ForkCondition {
+    /// Returns whether the fork is active at a block, or `None` if the activation is unknown.
+    pub fn is_active_at(&self, block_number: u64, timestamp: u64) -> Option<bool> {
+        match self {
+            ForkCondition::Block(block) => Some(block_number >= *block),
+            ForkCondition::Timestamp(time) => Some(timestamp >= *time),
+            ForkCondition::Never => Some(false),
+            ForkCondition::Unknown => None,
+        }
+    }
+}
+
+/// Transaction type, classified by the envelope it decodes to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ChainTxType {
+    Ethereum(TxType),
+    /// OP Stack deposit transaction.
+    OpDeposit,
+    /// Arbitrum specific transaction, with its type.
+    Arbitrum(u8),
+}
+
+impl TryFrom<u8> for ChainTxType {
+    type Error = EthTrieError;
+
+    fn try_from(ty: u8) -> Result<Self, EthTrieError> {
+        match ty {
+            DEPOSIT_TX_TYPE => Ok(ChainTxType::OpDeposit),
+            ty if ArbitrumTx::is_arbitrum_tx_type(ty) => Ok(ChainTxType::Arbitrum(ty)),
+            ty => TxType::try_from(ty)
+                .map(ChainTxType::Ethereum)
+                .map_err(|_| EthTrieError::InvalidTxVersion),
+        }
+    }
+}
+
+/// Rules of an EVM chain, followed to convert RPC data to consensus types and validate it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ChainSpec {
+    pub name: Cow<'static, str>,
+    pub chain_id: u64,
+    /// EIP-2718 transaction types of the chain. Legacy transactions are always accepted.
+    pub tx_types: Cow<'static, [u8]>,
+    /// Byzantium replaces the intermediate state root of receipts with a status (EIP-658).
+    pub byzantium: ForkCondition,
+    /// London adds `base_fee_per_gas` to headers.
+    pub london: ForkCondition,
+    /// Shanghai adds `withdrawals_root` to headers.
+    pub shanghai: ForkCondition,
+    /// Cancun adds `blob_gas_used`, `excess_blob_gas` and `parent_beacon_block_root` to headers.
+    pub cancun: ForkCondition,
+    /// Hardforks deciding the fields of deposit receipts, for OP Stack chains.
+    pub op_hardforks: Option<OpHardforks>,
+    /// Whether blocks may include a Bor state sync transaction, left out of the tries.
+    pub bor_state_sync: bool,
+}
+
+impl ChainSpec {
+    pub const MAINNET: Self = Self {
+        name: Cow::Borrowed("mainnet"),
+        chain_id: 1,
+        tx_types: Cow::Borrowed(ETHEREUM_TX_TYPES),
+        byzantium: ForkCondition::Block(4370000),
+        london: ForkCondition::Block(12965000),
+        shanghai: ForkCondition::Timestamp(1681338455),
+        cancun: ForkCondition::Timestamp(1710338135),
+        op_hardforks: None,
+        bor_state_sync: false,
+    };
+    pub const SEPOLIA: Self = Self {
+        name: Cow::Borrowed("sepolia"),
+        chain_id: 11155111,
+        tx_types: Cow::Borrowed(ETHEREUM_TX_TYPES),
+        byzantium: ForkCondition::Block(0),
+        london: ForkCondition::Block(0),
+        shanghai: ForkCondition::Timestamp(1677557088),
+        cancun: ForkCondition::Timestamp(1706655072),
+        op_hardforks: None,
+        bor_state_sync: false,
+    };
+    pub const HOLESKY: Self = Self {
+        name: Cow::Borrowed("holesky"),
+        chain_id: 17000,
+        tx_types: Cow::Borrowed(ETHEREUM_TX_TYPES),
+        byzantium: ForkCondition::Block(0),
+        london: ForkCondition::Block(0),
+        shanghai: ForkCondition::Timestamp(1696000704),
+        cancun: ForkCondition::Timestamp(1707305664),
+        op_hardforks: None,
+        bor_state_sync: false,
+    };
+    /// OP Mainnet, whose history before the Bedrock block is legacy.
+    pub const OP_MAINNET: Self = Self {
+        name: Cow::Borrowed("optimism"),
+        chain_id: 10,
+        tx_types: Cow::Borrowed(OP_STACK_TX_TYPES),
+        byzantium: ForkCondition::Block(0),
+        london: ForkCondition::Block(105235063),
+        // Canyon and Ecotone
+        shanghai: ForkCondition::Timestamp(1704992401),
+        cancun: ForkCondition::Timestamp(1710374401),
+        op_hardforks: Some(OpHardforks::OP_MAINNET),
+        bor_state_sync: false,
+    };
+    pub const OP_SEPOLIA: Self = Self {
+        name: Cow::Borrowed("optimism-sepolia"),
+        chain_id: 11155420,
+        tx_types: Cow::Borrowed(OP_STACK_TX_TYPES),
+        byzantium: ForkCondition::Block(0),
+        london: ForkCondition::Block(0),
+        shanghai: ForkCondition::Timestamp(1699981200),
+        cancun: ForkCondition::Timestamp(1708534800),
+        op_hardforks: Some(OpHardforks::OP_SEPOLIA),
+        bor_state_sync: false,
+    };
+    pub const BASE_MAINNET: Self = Self {
+        name: Cow::Borrowed("base"),
+        chain_id: 8453,
+        tx_types: Cow::Borrowed(OP_STACK_TX_TYPES),
+        byzantium: ForkCondition::Block(0),
+        london: ForkCondition::Block(0),
+        shanghai: ForkCondition::Timestamp(1704992401),
+        cancun: ForkCondition::Timestamp(1710374401),
+        op_hardforks: Some(OpHardforks::BASE_MAINNET),
+        bor_state_sync: false,
+    };
+    pub const BASE_SEPOLIA: Self = Self {
+        name: Cow::Borrowed("base-sepolia"),
+        chain_id: 84532,
+        tx_types: Cow::Borrowed(OP_STACK_TX_TYPES),
+        byzantium: ForkCondition::Block(0),
+        london: ForkCondition::Block(0),
+        shanghai: ForkCondition::Timestamp(1699981200),
+        cancun: ForkCondition::Timestamp(1708534800),
+        op_hardforks: Some(OpHardforks::BASE_SEPOLIA),
+        bor_state_sync: false,
+    };
+    pub const ARBITRUM_ONE: Self = Self {
+        name: Cow::Borrowed("arbitrum"),
+        chain_id: 42161,
+        tx_types: Cow::Borrowed(ARBITRUM_TX_TYPES),
+        byzantium: ForkCondition::Block(0),
+        london: ForkCondition::Unknown,
+        shanghai: ForkCondition::Unknown,
+        cancun: ForkCondition::Unknown,
+        op_hardforks: None,
+        bor_state_sync: false,
+    };
+    pub const POLYGON: Self = Self {
+        name: Cow::Borrowed("polygon"),
+        chain_id: 137,
+        tx_types: Cow::Borrowed(POLYGON_TX_TYPES),
+        byzantium: ForkCondition::Block(0),
+        london: ForkCondition::Block(23850000),
+        shanghai: ForkCondition::Unknown,
+        cancun: ForkCondition::Unknown,
+        op_hardforks: None,
+        bor_state_sync: true,
+    };
+
+    /// Returns a permissive profile for a chain without a known profile.
+    ///
+    /// All transaction types are accepted, and receipts and headers are taken as returned by
+    /// the RPC.
+    pub const fn generic(chain_id: u64) -> Self {
+        Self {
+            name: Cow::Borrowed("generic"),
+            chain_id,
+            tx_types: Cow::Borrowed(ALL_TX_TYPES),
+            byzantium: ForkCondition::Unknown,
+            london: ForkCondition::Unknown,
+            shanghai: ForkCondition::Unknown,
+            cancun: ForkCondition::Unknown,
+            op_hardforks: None,
+            bor_state_sync: true,
+        }
+    }
+
+    /// Returns the built-in profiles.
+    pub fn builtins() -> [Self; 9] {
+        [
+            Self::MAINNET,
+            Self::SEPOLIA,
+            Self::HOLESKY,
+            Self::OP_MAINNET,
+            Self::OP_SEPOLIA,
+            Self::BASE_MAINNET,
+            Self::BASE_SEPOLIA,
+            Self::ARBITRUM_ONE,
+            Self::POLYGON,
+        ]
+    }
+
+    /// Classifies a transaction type, failing if the chain does not have it.
+    pub fn tx_type(&self, ty: u8) -> Result<ChainTxType, EthTrieError> {
+        if ty != 0 && !self.tx_types.contains(&ty) {
+            return Err(EthTrieError::InvalidTxVersion);
+        }
+        ChainTxType::try_from(ty)
+    }
+
+    /// Checks that the header carries exactly the fields of the forks active at its block.
+    pub fn validate_header(&self, header: &Header) -> Result<(), EthTrieError> {
+        let (number, timestamp) = (header.number, header.timestamp);
+        let check = |fork: ForkCondition, present: bool, field: Field| match fork
+            .is_active_at(number, timestamp)
+        {
+            Some(active) if active != present => Err(EthTrieError::InvalidHeaderField(field)),
+            _ => Ok(()),
+        };
+
+        check(
+            self.london,
+            header.base_fee_per_gas.is_some(),
+            Field::BaseFeePerGas,
+        )?;
+        check(
+            self.shanghai,
+            header.withdrawals_root.is_some(),
+            Field::WithdrawalsRoot,
+        )?;
+        check(
+            self.cancun,
+            header.blob_gas_used.is_some(),
+            Field::BlobGasUsed,
+        )?;
+        check(
+            self.cancun,
+            header.excess_blob_gas.is_some(),
+            Field::ExcessBlobGas,
+        )?;
+        check(
+            self.cancun,
+            header.parent_beacon_block_root.is_some(),
+            Field::ParentBeaconBlockRoot,
+        )?;
+        Ok(())
+    }
+}
+
+/// Chain profiles by chain id, starting with the built-in ones.
+#[derive(Debug, Clone)]
+pub struct ChainSpecRegistry {
+    specs: HashMap<u64, ChainSpec>,
+}
+
+impl Default for ChainSpecRegistry {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ChainSpecRegistry {
+    /// Creates a registry with the built-in profiles.
+    pub fn new() -> Self {
+        let specs = ChainSpec::builtins()
+            .into_iter()
+            .map(|spec| (spec.chain_id, spec))
+            .collect();
+        Self { specs }
+    }
+
+    /// Registers a profile, replacing and returning the one of the same chain id, if any.
+    pub fn register(&mut self, spec: ChainSpec) -> Option<ChainSpec> {
+        self.specs.insert(spec.chain_id, spec)
+    }
+
+    pub fn get(&self, chain_id: u64) -> Option<&ChainSpec> {
+        self.specs.get(&chain_id)
+    }
+
+    /// Returns the profile of a chain, or [`ChainSpec::generic`] if it has none.
+    pub fn resolve(&self, chain_id: u64) -> ChainSpec {
+        self.get(chain_id)
+            .cloned()
+            .unwrap_or_else(|| ChainSpec::generic(chain_id))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloy::primitives::B256;
+
+    use super::*;
+    use crate::arbitrum::ARBITRUM_LEGACY_TX_TYPE;
+
+    #[test]
+    fn test_registry() {
+        let mut registry = ChainSpecRegistry::new();
+        assert_eq!(registry.get(1), Some(&ChainSpec::MAINNET));
+        assert_eq!(registry.get(8453), Some(&ChainSpec::BASE_MAINNET));
+        assert_eq!(
+            registry.resolve(10).op_hardforks,
+            Some(OpHardforks::OP_MAINNET)
+        );
+        assert_eq!(registry.resolve(1).op_hardforks, None);
+        assert_eq!(registry.resolve(424242), ChainSpec::generic(424242));
+
+        let spec = ChainSpec {
+            name: "devnet".into(),
+            chain_id: 424242,
+            tx_types: vec![1, 2].into(),
+            ..ChainSpec::SEPOLIA
+        };
+        assert_eq!(registry.register(spec.clone()), None);
+        assert_eq!(registry.resolve(424242), spec);
+    }
+
+    #[test]
+    fn test_tx_type() {
+        assert_eq!(
+            ChainSpec::MAINNET.tx_type(3).unwrap(),
+            ChainTxType::Ethereum(TxType::Eip4844)
+        );
+        assert_eq!(
+            ChainSpec::MAINNET.tx_type(0).unwrap(),
+            ChainTxType::Ethereum(TxType::Legacy)
+        );
+        assert!(ChainSpec::MAINNET.tx_type(DEPOSIT_TX_TYPE).is_err());
+        assert_eq!(
+            ChainSpec::OP_MAINNET.tx_type(DEPOSIT_TX_TYPE).unwrap(),
+            ChainTxType::OpDeposit
+        );
+        // OP Stack chains have no blob transactions
+        assert!(ChainSpec::OP_MAINNET.tx_type(3).is_err());
+        assert_eq!(
+            ChainSpec::ARBITRUM_ONE
+                .tx_type(ARBITRUM_INTERNAL_TX_TYPE)
+                .unwrap(),
+            ChainTxType::Arbitrum(ARBITRUM_INTERNAL_TX_TYPE)
+        );
+        assert!(ChainSpec::generic(1).tx_type(4).is_err());
+        // Pre-Nitro transactions are not supported, even by the permissive profile
+        for spec in [ChainSpec::ARBITRUM_ONE, ChainSpec::generic(42161)] {
+            assert!(matches!(
+                spec.tx_type(ARBITRUM_LEGACY_TX_TYPE),
+                Err(EthTrieError::InvalidTxVersion)
+            ));
+        }
+    }
+
+    #[test]
+    fn test_validate_header() {
+        let spec = ChainSpec::MAINNET;
+        let mut header = Header {
+            number: 12964999,
+            ..Default::default()
+        };
+        spec.validate_header(&header).unwrap();
+
+        header.number = 12965000;
+        assert!(matches!(
+            spec.validate_header(&header),
+            Err(EthTrieError::InvalidHeaderField(Field::BaseFeePerGas))
+        ));
+        header.base_fee_per_gas = Some(1000000000);
+        spec.validate_header(&header).unwrap();
+
+        header.number = 19426589;
+        header.timestamp = 1710338135;
+        header.withdrawals_root = Some(B256::ZERO);
+        assert!(matches!(
+            spec.validate_header(&header),
+            Err(EthTrieError::InvalidHeaderField(Field::BlobGasUsed))
+        ));
+        header.blob_gas_used = Some(0);
+        header.excess_blob_gas = Some(0);
+        header.parent_beacon_block_root = Some(B256::ZERO);
+        spec.validate_header(&header).unwrap();
+
+        // Holesky launched without withdrawals, Shanghai activated at its epoch 256
+        let spec = ChainSpec::HOLESKY;
+        let mut header = Header {
+            number: 1,
+            timestamp: 1695902424,
+            base_fee_per_gas: Some(1000000000),
+            ..Default::default()
+        };
+        spec.validate_header(&header).unwrap();
+        header.withdrawals_root = Some(B256::ZERO);
+        assert!(matches!(
+            spec.validate_header(&header),
+            Err(EthTrieError::InvalidHeaderField(Field::WithdrawalsRoot))
+        ));
+        header.timestamp = 1696000704;
+        spec.validate_header(&header).unwrap();
+
+        // Nothing is checked for unknown chains
+        ChainSpec::generic(1)
+            .validate_header(&Header::default())
+            .unwrap();
+    }
+}
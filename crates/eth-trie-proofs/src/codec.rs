@@ -0,0 +1,368 @@
+//! Compact, versioned binary encoding of [`TxInclusionProof`] and [`ReceiptInclusionProof`].
+//!
+//! Every encoding starts with the header of `trie-proof-header`, shared with the Starknet
+//! proofs of `sn-trie-proofs`:
+//!
+//! ```text
+//! magic "TRPF" (4) | version (1) | chain (1) | trie (1) | hash (1) | flags (1)
+//! ```
+//!
+//! `chain` is [`CHAIN_ETHEREUM`], `trie` is [`TRIE_TX`] or [`TRIE_RECEIPT`], `hash` is
+//! [`HASH_KECCAK`], and `flags` is a combination of [`FLAG_BATCH`] and [`FLAG_DEDUP`].
+//! Integers are big-endian and byte strings are prefixed by their length as a `u32`.
+//! A single proof follows as:
+//!
+//! ```text
+//! block_number (8) | block_hash (32) | root (32) | index (8) | key | value | node_count (4) | nodes
+//! ```
+//!
+//! A batch holds `proof_count (4)` proofs of the same trie. With [`FLAG_DEDUP`], the
+//! distinct nodes of the batch are stored once in a table (`node_count (4) | nodes`)
+//! placed before the proofs, and proofs refer to them by their `u32` index in the table.
+//! The reader, writer and batch layout are those of [`trie_proof_header::codec`], shared
+//! with `sn-trie-proofs`.
+//!
+//! Encoding fails on empty proof nodes. Decoding is strict: unknown versions, kinds, hash
+//! functions and flags, truncated or trailing bytes, empty nodes, keys not matching the
+//! index, and non-canonical node tables are rejected.
+
+use std::marker::PhantomData;
+
+use alloy::primitives::{Bytes, B256, U256};
+
+use crate::{
+    error::EthTrieError,
+    proof::{InclusionProof, ReceiptInclusionProof, TxInclusionProof},
+};
+use sealed::{ProofFields, Sealed};
+use trie_proof_header::codec::{self, InvalidEncoding, Reader, Writer};
+use trie_proof_header::Header;
+
+pub use trie_proof_header::{
+    CHAIN_ETHEREUM, FLAG_BATCH, FLAG_DEDUP, HASH_KECCAK, MAGIC, TRIE_RECEIPT, TRIE_TX, VERSION,
+};
+
+/// Inclusion proofs with a compact binary encoding.
+pub trait CompactProof: sealed::Sealed {
+    /// Trie kind written in the header.
+    const TRIE: u8;
+
+    /// Encodes the proof alone.
+    ///
+    /// Fails with [`EthTrieError::InvalidProofEncoding`] if a node is empty.
+    fn to_bytes(&self) -> Result<Vec<u8>, EthTrieError> {
+        let fields = self.fields();
+        check_nodes(&fields)?;
+        let mut writer = writer(Self::TRIE, 0);
+        write_fields(&mut writer, &fields);
+        writer.nodes(&fields.nodes, write_node);
+        Ok(writer.into_bytes())
+    }
+
+    /// Decodes a proof encoded by [`CompactProof::to_bytes`].
+    fn from_bytes(bytes: &[u8]) -> Result<Self, EthTrieError> {
+        let (flags, mut reader) = reader(bytes, Self::TRIE)?;
+        if flags != 0 {
+            return Err(EthTrieError::InvalidProofEncoding);
+        }
+        let fields = read_fields(&mut reader)?;
+        let nodes = reader.nodes(read_node)?;
+        reader.finish()?;
+        Ok(Self::from_fields(ProofFields { nodes, ..fields }))
+    }
+}
+
+mod sealed {
+    use alloy::primitives::{Bytes, B256};
+
+    /// Fields shared by the Ethereum proof bundles.
+    pub struct ProofFields {
+        pub block_number: u64,
+        pub block_hash: B256,
+        pub root: B256,
+        pub index: u64,
+        pub key: Bytes,
+        pub value: Bytes,
+        pub nodes: Vec<Bytes>,
+    }
+
+    pub trait Sealed: Sized {
+        fn fields(&self) -> ProofFields;
+        fn from_fields(fields: ProofFields) -> Self;
+    }
+}
+
+impl CompactProof for TxInclusionProof {
+    const TRIE: u8 = TRIE_TX;
+}
+
+impl CompactProof for ReceiptInclusionProof {
+    const TRIE: u8 = TRIE_RECEIPT;
+}
+
+impl<K> Sealed for InclusionProof<K> {
+    fn fields(&self) -> ProofFields {
+        ProofFields {
+            block_number: self.block_number,
+            block_hash: self.block_hash,
+            root: self.root,
+            index: self.index,
+            key: self.key.clone(),
+            value: self.value.clone(),
+            nodes: self.nodes.clone(),
+        }
+    }
+
+    fn from_fields(fields: ProofFields) -> Self {
+        Self {
+            block_number: fields.block_number,
+            block_hash: fields.block_hash,
+            root: fields.root,
+            index: fields.index,
+            key: fields.key,
+            nodes: fields.nodes,
+            value: fields.value,
+            kind: PhantomData,
+        }
+    }
+}
+
+impl From<InvalidEncoding> for EthTrieError {
+    fn from(_: InvalidEncoding) -> Self {
+        Self::InvalidProofEncoding
+    }
+}
+
+/// Encodes a batch of proofs of the same trie kind.
+///
+/// With `dedup`, nodes shared between proofs, e.g. the top of the trie for proofs of
+/// the same block, are stored once. Fails if a node is empty.
+pub fn encode_batch<P: CompactProof>(proofs: &[P], dedup: bool) -> Result<Vec<u8>, EthTrieError> {
+    let fields: Vec<ProofFields> = proofs.iter().map(Sealed::fields).collect();
+    for proof in &fields {
+        check_nodes(proof)?;
+    }
+    let mut writer = writer(P::TRIE, codec::batch_flags(dedup));
+    codec::write_batch(
+        &mut writer,
+        &fields,
+        dedup,
+        |proof| proof.nodes.as_slice(),
+        write_fields,
+        write_node,
+    );
+    Ok(writer.into_bytes())
+}
+
+/// Empty nodes are rejected by the decoder, so they are never encoded.
+fn check_nodes(proof: &ProofFields) -> Result<(), EthTrieError> {
+    if proof.nodes.iter().any(|node| node.is_empty()) {
+        return Err(EthTrieError::InvalidProofEncoding);
+    }
+    Ok(())
+}
+
+/// Decodes a batch encoded by [`encode_batch`], with or without dedup.
+pub fn decode_batch<P: CompactProof>(bytes: &[u8]) -> Result<Vec<P>, EthTrieError> {
+    let (flags, mut reader) = reader(bytes, P::TRIE)?;
+    let dedup = codec::batch_dedup(flags)?;
+    let proofs = codec::read_batch(&mut reader, dedup, read_fields, read_node)?;
+    reader.finish()?;
+
+    Ok(proofs
+        .into_iter()
+        .map(|(fields, nodes)| P::from_fields(ProofFields { nodes, ..fields }))
+        .collect())
+}
+
+fn writer(trie: u8, flags: u8) -> Writer {
+    Writer::new(Header {
+        chain: CHAIN_ETHEREUM,
+        trie,
+        hash: HASH_KECCAK,
+        flags,
+    })
+}
+
+/// Checks the header and returns its flags.
+fn reader(bytes: &[u8], trie: u8) -> Result<(u8, Reader<'_>), EthTrieError> {
+    let (header, reader) = Reader::new(bytes)?;
+    if header.chain != CHAIN_ETHEREUM || header.trie != trie || header.hash != HASH_KECCAK {
+        return Err(EthTrieError::InvalidProofEncoding);
+    }
+    Ok((header.flags, reader))
+}
+
+fn write_fields(writer: &mut Writer, proof: &ProofFields) {
+    writer.u64(proof.block_number);
+    writer.raw(proof.block_hash.as_slice());
+    writer.raw(proof.root.as_slice());
+    writer.u64(proof.index);
+    writer.bytes(&proof.key);
+    writer.bytes(&proof.value);
+}
+
+fn write_node(writer: &mut Writer, node: &Bytes) {
+    writer.bytes(node);
+}
+
+/// Reads the fields of a proof, without its nodes.
+fn read_fields(reader: &mut Reader<'_>) -> Result<ProofFields, EthTrieError> {
+    let block_number = reader.u64()?;
+    let block_hash = B256::from(reader.array()?);
+    let root = B256::from(reader.array()?);
+    let index = reader.u64()?;
+    let key = Bytes::copy_from_slice(reader.bytes()?);
+    if key != alloy_rlp::encode(U256::from(index)) {
+        return Err(EthTrieError::InvalidProofEncoding);
+    }
+    let value = Bytes::copy_from_slice(reader.bytes()?);
+    Ok(ProofFields {
+        block_number,
+        block_hash,
+        root,
+        index,
+        key,
+        value,
+        nodes: vec![],
+    })
+}
+
+fn read_node(reader: &mut Reader<'_>) -> Result<Bytes, EthTrieError> {
+    let node = reader.bytes()?;
+    if node.is_empty() {
+        return Err(EthTrieError::InvalidProofEncoding);
+    }
+    Ok(Bytes::copy_from_slice(node))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn proof(index: u64, shared: u8) -> ReceiptInclusionProof {
+        let nodes = vec![
+            vec![0xf8; 100],
+            vec![shared; 60],
+            vec![index as u8 + 0x10; 40],
+        ];
+        ReceiptInclusionProof::new(
+            42,
+            B256::repeat_byte(0xbb),
+            B256::repeat_byte(0xcc),
+            index,
+            nodes,
+            vec![0x02, index as u8, 0xc0],
+        )
+    }
+
+    #[test]
+    fn test_round_trip() {
+        let receipt = proof(300, 0x01);
+        let bytes = receipt.to_bytes().unwrap();
+        assert_eq!(&bytes[..9], &[b'T', b'R', b'P', b'F', 2, 1, 2, 1, 0]);
+        assert_eq!(ReceiptInclusionProof::from_bytes(&bytes).unwrap(), receipt);
+
+        let tx = TxInclusionProof::new(1, B256::ZERO, B256::ZERO, 0, vec![vec![0xc0]], vec![1]);
+        assert_eq!(
+            TxInclusionProof::from_bytes(&tx.to_bytes().unwrap()).unwrap(),
+            tx
+        );
+
+        // Smaller than its JSON encoding
+        assert!(bytes.len() * 2 < serde_json::to_string(&receipt).unwrap().len());
+    }
+
+    #[test]
+    fn test_batch_round_trip() {
+        let proofs: Vec<_> = (0..10).map(|i| proof(i, (i % 3) as u8)).collect();
+
+        let plain = encode_batch(&proofs, false).unwrap();
+        let deduped = encode_batch(&proofs, true).unwrap();
+        assert!(deduped.len() < plain.len());
+
+        assert_eq!(
+            decode_batch::<ReceiptInclusionProof>(&plain).unwrap(),
+            proofs
+        );
+        assert_eq!(
+            decode_batch::<ReceiptInclusionProof>(&deduped).unwrap(),
+            proofs
+        );
+        assert!(decode_batch::<ReceiptInclusionProof>(
+            &encode_batch::<ReceiptInclusionProof>(&[], true).unwrap()
+        )
+        .unwrap()
+        .is_empty());
+    }
+
+    #[test]
+    fn test_strict_decoding() {
+        let receipt = proof(5, 0x01);
+        let bytes = receipt.to_bytes().unwrap();
+
+        // Wrong trie kind, batch decoding of a single proof
+        assert!(TxInclusionProof::from_bytes(&bytes).is_err());
+        assert!(decode_batch::<ReceiptInclusionProof>(&bytes).is_err());
+
+        // Truncated and trailing bytes
+        for len in 0..bytes.len() {
+            assert!(ReceiptInclusionProof::from_bytes(&bytes[..len]).is_err());
+        }
+        let mut trailing = bytes.clone();
+        trailing.push(0);
+        assert!(ReceiptInclusionProof::from_bytes(&trailing).is_err());
+
+        // Magic, version, chain, hash function and flags
+        for position in [0, 4, 5, 7, 8] {
+            let mut invalid = bytes.clone();
+            invalid[position] ^= 0x80;
+            assert!(ReceiptInclusionProof::from_bytes(&invalid).is_err());
+        }
+
+        // Key not matching the index
+        let mut invalid = receipt.clone();
+        invalid.key = alloy_rlp::encode(U256::from(6)).into();
+        assert!(ReceiptInclusionProof::from_bytes(&invalid.to_bytes().unwrap()).is_err());
+
+        // Empty nodes are rejected when encoding, and when decoding
+        let mut invalid = receipt.clone();
+        invalid.nodes[1] = Bytes::new();
+        assert!(matches!(
+            invalid.to_bytes(),
+            Err(EthTrieError::InvalidProofEncoding)
+        ));
+        assert!(encode_batch(&[invalid.clone()], false).is_err());
+        assert!(encode_batch(&[invalid], true).is_err());
+        let mut empty_node = bytes.clone();
+        let node_start = bytes.len() - (4 + 40) - (4 + 60);
+        empty_node.splice(node_start..node_start + 4 + 60, 0u32.to_be_bytes());
+        assert!(ReceiptInclusionProof::from_bytes(&empty_node).is_err());
+
+        // Unused and out of range table nodes
+        let proofs = vec![proof(1, 0x01), proof(2, 0x01)];
+        let deduped = encode_batch(&proofs, true).unwrap();
+        let last_reference = deduped.len() - 1;
+        let mut invalid = deduped.clone();
+        invalid[last_reference] = 0;
+        assert!(decode_batch::<ReceiptInclusionProof>(&invalid).is_err());
+        let mut invalid = deduped;
+        invalid[last_reference] = 9;
+        assert!(decode_batch::<ReceiptInclusionProof>(&invalid).is_err());
+
+        // Duplicated table nodes
+        let single = encode_batch(&[proof(1, 0x01)], true).unwrap();
+        let table_start = 13;
+        let mut duplicated = single[..9].to_vec();
+        duplicated.extend(4u32.to_be_bytes());
+        duplicated.extend(&single[table_start..table_start + 4 + 100]);
+        duplicated.extend(&single[table_start..]);
+        assert!(decode_batch::<ReceiptInclusionProof>(&duplicated).is_err());
+
+        // Counts larger than the input
+        let mut huge = bytes[..9].to_vec();
+        huge[8] = FLAG_BATCH;
+        huge.extend(u32::MAX.to_be_bytes());
+        assert!(decode_batch::<ReceiptInclusionProof>(&huge).is_err());
+    }
+}
@@ -0,0 +1,30 @@
+//! Solidity code generation for verifying proofs on chain.
+
+const RECEIPT_PROVER_TEMPLATE: &str = include_str!("templates/ReceiptProver.sol");
+
+/// Renders a `ReceiptProver` contract, which proves a receipt with `MerkleTrie.get`,
+/// decodes it (stripping the EIP-2718 type byte of typed receipts) and extracts the
+/// address, topics and data of one of its logs.
+///
+/// `lib_path` is the import directory of the `MerkleTrie`, `RLPReader` and `BytesUtils`
+/// libraries, e.g. `./lib` for a contract in `examples/eth/src`.
+pub fn render_receipt_prover(contract_name: &str, lib_path: &str) -> String {
+    RECEIPT_PROVER_TEMPLATE
+        .replace("{{contract_name}}", contract_name)
+        .replace("{{lib_path}}", lib_path.trim_end_matches('/'))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_render_receipt_prover() {
+        let contract = render_receipt_prover("LogProver", "./lib/");
+
+        assert!(!contract.contains("{{"));
+        assert!(contract.contains("contract LogProver {"));
+        assert!(contract.contains("import {MerkleTrie} from \"./lib/MerkleTrie.sol\";"));
+        assert!(contract.contains("function verifyLog("));
+    }
+}
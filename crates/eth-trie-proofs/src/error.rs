@@ -1,5 +1,7 @@
 use core::fmt;
 
+use alloy::eips::eip4844::BlobTransactionValidationError;
+use alloy::primitives::{B256, U256};
 use alloy::transports::{RpcError, TransportErrorKind};
 use eth_trie::TrieError;
 use thiserror::Error;
@@ -18,10 +20,14 @@ pub enum EthTrieError {
     TxNotFound,
     #[error("Block not found")]
     BlockNotFound,
+    #[error("Block reorged: {0}")]
+    BlockReorged(B256),
     #[error("Invalid transaction version")]
     InvalidTxVersion,
     #[error("Error converting field: {0}")]
     ConversionError(Field),
+    #[error("Unsupported signature v: {0}")]
+    UnsupportedSignatureV(U256),
     #[error("Unexpected root")]
     UnexpectedRoot,
     #[error("Invalid mpt proof")]
@@ -30,6 +36,26 @@ pub enum EthTrieError {
     TrieNotFound,
     #[error("Field not found")]
     FieldNotFound,
+    #[error("Log not found")]
+    LogNotFound,
+    #[error("Invalid proof encoding")]
+    InvalidProofEncoding,
+    #[error("Invalid Bor receipt")]
+    InvalidBorReceipt,
+    #[error("Invalid header field: {0}")]
+    InvalidHeaderField(Field),
+    #[error("Invalid blob sidecar: {0}")]
+    InvalidBlobSidecar(BlobTransactionValidationError),
+    #[error("Malformed blob sidecar")]
+    MalformedBlobSidecar,
+    #[error("Blob not found: {0}")]
+    BlobNotFound(B256),
+    #[error("Beacon API error: {0}")]
+    BeaconApi(reqwest::Error),
+    #[error("IO error: {0}")]
+    Io(std::io::Error),
+    #[error("JSON error: {0}")]
+    Json(serde_json::Error),
 }
 
 #[derive(Debug)]
@@ -44,6 +70,29 @@ pub enum Field {
     MaxPriorityFeePerGas,
     MaxFeePerBlobGas,
     Signature,
+    SourceHash,
+    Mint,
+    IsSystemTx,
+    DepositNonce,
+    DepositReceiptVersion,
+    RequestId,
+    TicketId,
+    RefundTo,
+    MaxRefund,
+    SubmissionFeeRefund,
+    L1BaseFee,
+    DepositValue,
+    RetryValue,
+    RetryData,
+    Beneficiary,
+    MaxSubmissionFee,
+    To,
+    BaseFeePerGas,
+    WithdrawalsRoot,
+    BlobGasUsed,
+    ExcessBlobGas,
+    ParentBeaconBlockRoot,
+    Status,
 }
 
 impl fmt::Display for Field {
@@ -59,6 +108,29 @@ impl fmt::Display for Field {
             Field::MaxPriorityFeePerGas => write!(f, "max_priority_fee_per_gas"),
             Field::MaxFeePerBlobGas => write!(f, "max_fee_per_blob_gas"),
             Field::Signature => write!(f, "signature"),
+            Field::SourceHash => write!(f, "source_hash"),
+            Field::Mint => write!(f, "mint"),
+            Field::IsSystemTx => write!(f, "is_system_tx"),
+            Field::DepositNonce => write!(f, "deposit_nonce"),
+            Field::DepositReceiptVersion => write!(f, "deposit_receipt_version"),
+            Field::RequestId => write!(f, "request_id"),
+            Field::TicketId => write!(f, "ticket_id"),
+            Field::RefundTo => write!(f, "refund_to"),
+            Field::MaxRefund => write!(f, "max_refund"),
+            Field::SubmissionFeeRefund => write!(f, "submission_fee_refund"),
+            Field::L1BaseFee => write!(f, "l1_base_fee"),
+            Field::DepositValue => write!(f, "deposit_value"),
+            Field::RetryValue => write!(f, "retry_value"),
+            Field::RetryData => write!(f, "retry_data"),
+            Field::Beneficiary => write!(f, "beneficiary"),
+            Field::MaxSubmissionFee => write!(f, "max_submission_fee"),
+            Field::To => write!(f, "to"),
+            Field::BaseFeePerGas => write!(f, "base_fee_per_gas"),
+            Field::WithdrawalsRoot => write!(f, "withdrawals_root"),
+            Field::BlobGasUsed => write!(f, "blob_gas_used"),
+            Field::ExcessBlobGas => write!(f, "excess_blob_gas"),
+            Field::ParentBeaconBlockRoot => write!(f, "parent_beacon_block_root"),
+            Field::Status => write!(f, "status"),
         }
     }
 }
@@ -0,0 +1,534 @@
+use std::fmt::Write;
+
+use alloy::primitives::{hex, Bytes, Log, B256};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    error::EthTrieError,
+    proof::{ReceiptInclusionProof, TxInclusionProof},
+};
+
+/// Proof carried by a [`ProofFixture`].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "lowercase")]
+pub enum FixtureProof {
+    Tx(TxInclusionProof),
+    Receipt(ReceiptInclusionProof),
+    /// A receipt proof, along with one of the logs of the receipt.
+    Log {
+        #[serde(flatten)]
+        receipt: ReceiptInclusionProof,
+        #[serde(rename = "logIndex")]
+        log_index: u64,
+        log: Log,
+    },
+}
+
+/// An [`InclusionProof`](crate::proof::InclusionProof), with everything a Solidity MPT
+/// verifier such as `MerkleTrie.get` needs to check it offline.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ProofFixture {
+    pub name: String,
+    #[serde(flatten)]
+    pub proof: FixtureProof,
+    /// RLP list of the proof nodes, as `MerkleTrie.get` expects.
+    pub rlp_proof: Bytes,
+}
+
+impl ProofFixture {
+    /// Builds a transaction fixture.
+    pub fn tx(name: impl Into<String>, proof: TxInclusionProof) -> Self {
+        let rlp_proof = rlp_proof(&proof.nodes);
+        Self {
+            name: name.into(),
+            proof: FixtureProof::Tx(proof),
+            rlp_proof,
+        }
+    }
+
+    /// Builds a receipt fixture.
+    pub fn receipt(name: impl Into<String>, proof: ReceiptInclusionProof) -> Self {
+        let rlp_proof = rlp_proof(&proof.nodes);
+        Self {
+            name: name.into(),
+            proof: FixtureProof::Receipt(proof),
+            rlp_proof,
+        }
+    }
+
+    /// Builds a fixture for the log at `log_index` within the proven receipt.
+    pub fn log(
+        name: impl Into<String>,
+        proof: ReceiptInclusionProof,
+        log_index: u64,
+    ) -> Result<Self, EthTrieError> {
+        let log = proof
+            .verify()?
+            .logs()
+            .get(log_index as usize)
+            .cloned()
+            .ok_or(EthTrieError::LogNotFound)?;
+        let rlp_proof = rlp_proof(&proof.nodes);
+        Ok(Self {
+            name: name.into(),
+            proof: FixtureProof::Log {
+                receipt: proof,
+                log_index,
+                log,
+            },
+            rlp_proof,
+        })
+    }
+
+    pub fn root(&self) -> B256 {
+        match &self.proof {
+            FixtureProof::Tx(proof) => proof.root,
+            FixtureProof::Receipt(proof) | FixtureProof::Log { receipt: proof, .. } => proof.root,
+        }
+    }
+
+    /// RLP-encoded trie key, i.e. the RLP-encoded transaction index.
+    pub fn key(&self) -> &Bytes {
+        match &self.proof {
+            FixtureProof::Tx(proof) => &proof.key,
+            FixtureProof::Receipt(proof) | FixtureProof::Log { receipt: proof, .. } => &proof.key,
+        }
+    }
+
+    /// The 2718-encoded transaction or receipt stored at the key.
+    pub fn value(&self) -> &Bytes {
+        match &self.proof {
+            FixtureProof::Tx(proof) => &proof.value,
+            FixtureProof::Receipt(proof) | FixtureProof::Log { receipt: proof, .. } => &proof.value,
+        }
+    }
+
+    /// Index and content of the log of a log fixture.
+    pub fn log_entry(&self) -> Option<(u64, &Log)> {
+        match &self.proof {
+            FixtureProof::Log { log_index, log, .. } => Some((*log_index, log)),
+            _ => None,
+        }
+    }
+
+    /// Checks that the proof resolves to the expected value, that `rlp_proof` holds its nodes
+    /// and, for a log fixture, that the receipt holds the log.
+    pub fn verify(&self) -> Result<(), EthTrieError> {
+        let nodes = match &self.proof {
+            FixtureProof::Tx(proof) => {
+                proof.verify()?;
+                &proof.nodes
+            }
+            FixtureProof::Receipt(proof) => {
+                proof.verify()?;
+                &proof.nodes
+            }
+            FixtureProof::Log {
+                receipt,
+                log_index,
+                log,
+            } => {
+                if receipt.verify()?.logs().get(*log_index as usize) != Some(log) {
+                    return Err(EthTrieError::LogNotFound);
+                }
+                &receipt.nodes
+            }
+        };
+        if rlp_proof(nodes) != self.rlp_proof {
+            return Err(EthTrieError::InvalidMPTProof);
+        }
+        Ok(())
+    }
+}
+
+fn rlp_proof(nodes: &[Bytes]) -> Bytes {
+    let mut rlp_proof = vec![];
+    alloy_rlp::encode_list::<_, [u8]>(nodes, &mut rlp_proof);
+    rlp_proof.into()
+}
+
+/// Renders fixtures as a pretty-printed JSON array.
+pub fn render_json_fixtures(fixtures: &[ProofFixture]) -> String {
+    serde_json::to_string_pretty(fixtures).expect("fixtures serialize to JSON")
+}
+
+/// Renders fixtures as a Foundry test contract, with one test per fixture
+/// checking the proof with `MerkleTrie.get`.
+///
+/// `merkle_trie_path` is the import path of the `MerkleTrie` library, e.g.
+/// `../src/lib/MerkleTrie.sol` for the `examples/eth` project.
+pub fn render_foundry_test(
+    contract_name: &str,
+    merkle_trie_path: &str,
+    fixtures: &[ProofFixture],
+) -> String {
+    let mut out = String::new();
+    writeln!(out, "// SPDX-License-Identifier: MIT").unwrap();
+    writeln!(out, "pragma solidity 0.8.25;").unwrap();
+    writeln!(out).unwrap();
+    writeln!(out, "// Generated by eth-trie-proofs, do not edit.").unwrap();
+    writeln!(out).unwrap();
+    writeln!(out, "import {{Test}} from \"forge-std/Test.sol\";").unwrap();
+    writeln!(out, "import {{MerkleTrie}} from \"{merkle_trie_path}\";").unwrap();
+    writeln!(out).unwrap();
+    writeln!(out, "contract {contract_name} is Test {{").unwrap();
+
+    for (idx, fixture) in fixtures.iter().enumerate() {
+        if idx > 0 {
+            writeln!(out).unwrap();
+        }
+        if let Some((log_index, log)) = fixture.log_entry() {
+            writeln!(
+                out,
+                "    // Receipt including log {log_index}, emitted by {}",
+                log.address
+            )
+            .unwrap();
+        }
+        writeln!(
+            out,
+            "    function test_{}() public pure {{",
+            solidity_identifier(&fixture.name)
+        )
+        .unwrap();
+        writeln!(
+            out,
+            "        bytes memory key = {};",
+            hex_literal(fixture.key())
+        )
+        .unwrap();
+        writeln!(
+            out,
+            "        bytes memory proof = {};",
+            hex_literal(&fixture.rlp_proof)
+        )
+        .unwrap();
+        writeln!(out, "        bytes32 root = {};", fixture.root()).unwrap();
+        writeln!(out).unwrap();
+        writeln!(
+            out,
+            "        (bool exists, bytes memory value) = MerkleTrie.get(key, proof, root);"
+        )
+        .unwrap();
+        writeln!(out, "        assertTrue(exists);").unwrap();
+        writeln!(
+            out,
+            "        assertEq(value, {});",
+            hex_literal(fixture.value())
+        )
+        .unwrap();
+        writeln!(out, "    }}").unwrap();
+    }
+
+    writeln!(out, "}}").unwrap();
+    out
+}
+
+/// Renders the log fixtures as a Foundry test contract, with one test per log
+/// checking it with the `verifyLog` function of a contract generated by
+/// [`crate::codegen::render_receipt_prover`]. Other fixtures are skipped.
+///
+/// `prover_path` is the import path of the prover contract named `prover_name`.
+pub fn render_receipt_prover_test(
+    contract_name: &str,
+    prover_name: &str,
+    prover_path: &str,
+    fixtures: &[ProofFixture],
+) -> String {
+    let mut out = String::new();
+    writeln!(out, "// SPDX-License-Identifier: MIT").unwrap();
+    writeln!(out, "pragma solidity 0.8.25;").unwrap();
+    writeln!(out).unwrap();
+    writeln!(out, "// Generated by eth-trie-proofs, do not edit.").unwrap();
+    writeln!(out).unwrap();
+    writeln!(out, "import {{Test}} from \"forge-std/Test.sol\";").unwrap();
+    writeln!(out, "import {{{prover_name}}} from \"{prover_path}\";").unwrap();
+    writeln!(out).unwrap();
+    writeln!(out, "contract {contract_name} is Test {{").unwrap();
+    writeln!(out, "    {prover_name} prover;").unwrap();
+    writeln!(out).unwrap();
+    writeln!(out, "    function setUp() public {{").unwrap();
+    writeln!(out, "        prover = new {prover_name}();").unwrap();
+    writeln!(out, "    }}").unwrap();
+
+    for fixture in fixtures {
+        let Some((log_index, log)) = fixture.log_entry() else {
+            continue;
+        };
+        writeln!(out).unwrap();
+        writeln!(
+            out,
+            "    function test_{}() public view {{",
+            solidity_identifier(&fixture.name)
+        )
+        .unwrap();
+        writeln!(
+            out,
+            "        bytes32[] memory topics = new bytes32[]({});",
+            log.topics().len()
+        )
+        .unwrap();
+        for (idx, topic) in log.topics().iter().enumerate() {
+            writeln!(out, "        topics[{idx}] = {topic};").unwrap();
+        }
+        writeln!(out).unwrap();
+        writeln!(out, "        bool valid = prover.verifyLog(").unwrap();
+        writeln!(out, "            {},", hex_literal(fixture.key())).unwrap();
+        writeln!(out, "            {},", hex_literal(&fixture.rlp_proof)).unwrap();
+        writeln!(out, "            {},", fixture.root()).unwrap();
+        writeln!(out, "            {log_index},").unwrap();
+        writeln!(out, "            {},", log.address).unwrap();
+        writeln!(out, "            topics,").unwrap();
+        writeln!(out, "            {}", hex_literal(&log.data.data)).unwrap();
+        writeln!(out, "        );").unwrap();
+        writeln!(out, "        assertTrue(valid);").unwrap();
+        writeln!(out, "    }}").unwrap();
+    }
+
+    writeln!(out, "}}").unwrap();
+    out
+}
+
+fn hex_literal(bytes: &[u8]) -> String {
+    format!("hex\"{}\"", hex::encode(bytes))
+}
+
+fn solidity_identifier(name: &str) -> String {
+    name.chars()
+        .map(|c| if c.is_ascii_alphanumeric() { c } else { '_' })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use alloy::consensus::{Eip658Value, Receipt, ReceiptEnvelope, ReceiptWithBloom};
+    use alloy::primitives::{Address, LogData, U256};
+    use eth_trie::{EthTrie, MemoryDB, Trie};
+
+    use super::*;
+    use crate::codegen::render_receipt_prover;
+    use crate::op::OpDepositReceipt;
+    use crate::proof::InclusionProof;
+    use crate::tx_receipt::{ChainReceiptEnvelope, ConsensusTxReceipt};
+    use crate::tx_receipt_trie::TxReceiptsMptHandler;
+
+    /// Foundry example checking the generated `ReceiptProver` against [`example_receipts`].
+    const EXAMPLE_DIR: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/../../examples/eth");
+
+    fn receipt(seed: u8) -> ConsensusTxReceipt {
+        let logs = (0..2)
+            .map(|i| Log {
+                address: Address::repeat_byte(seed + i),
+                data: LogData::new_unchecked(vec![B256::repeat_byte(seed)], vec![seed; 40].into()),
+            })
+            .collect();
+        let receipt = Receipt {
+            status: true.into(),
+            cumulative_gas_used: 21_000 * (seed as u128 + 1),
+            logs,
+        };
+        ConsensusTxReceipt(ReceiptEnvelope::Eip1559(ReceiptWithBloom::from(receipt)).into())
+    }
+
+    /// Receipts of every envelope, holding logs with 0 to 4 topics and empty, short or long data.
+    fn example_receipts() -> Vec<ConsensusTxReceipt> {
+        const DATA_LENGTHS: [usize; 6] = [0, 1, 31, 32, 56, 200];
+        (0..64u8)
+            .map(|seed| {
+                let logs = (0..seed % 3 + 1)
+                    .map(|i| {
+                        let topics = (0..(seed + i) % 5)
+                            .map(|topic| B256::repeat_byte(seed ^ (topic + 1)))
+                            .collect();
+                        let data = (0..DATA_LENGTHS[(seed + i) as usize % 6])
+                            .map(|byte| seed.wrapping_mul(31).wrapping_add(byte as u8))
+                            .collect::<Vec<_>>();
+                        Log {
+                            address: Address::repeat_byte(seed.wrapping_add(i + 1)),
+                            data: LogData::new_unchecked(topics, data.into()),
+                        }
+                    })
+                    .collect();
+                let status = if seed == 5 {
+                    Eip658Value::PostState(B256::repeat_byte(seed))
+                } else {
+                    (seed % 7 != 0).into()
+                };
+                let receipt = ReceiptWithBloom::from(Receipt {
+                    status,
+                    cumulative_gas_used: 21_000 * (seed as u128 + 1),
+                    logs,
+                });
+                let envelope = match seed % 5 {
+                    0 => ReceiptEnvelope::Legacy(receipt).into(),
+                    1 => ReceiptEnvelope::Eip2930(receipt).into(),
+                    2 => ReceiptEnvelope::Eip1559(receipt).into(),
+                    3 => ReceiptEnvelope::Eip4844(receipt).into(),
+                    _ => ChainReceiptEnvelope::OpDeposit(OpDepositReceipt {
+                        inner: receipt,
+                        deposit_nonce: Some(seed as u64),
+                        deposit_receipt_version: Some(1),
+                    }),
+                };
+                ConsensusTxReceipt(envelope)
+            })
+            .collect()
+    }
+
+    fn handler(receipts: Vec<ConsensusTxReceipt>) -> TxReceiptsMptHandler {
+        let mut trie = EthTrie::new(Arc::new(MemoryDB::new(true)));
+        for (idx, receipt) in receipts.iter().enumerate() {
+            let key = alloy_rlp::encode(U256::from(idx));
+            trie.insert(&key, &receipt.rlp_encode()).unwrap();
+        }
+        let root = B256::from_slice(trie.root_hash().unwrap().as_bytes());
+
+        let mut handler = TxReceiptsMptHandler::new("http://localhost".parse().unwrap()).unwrap();
+        handler.build_trie(receipts, root).unwrap();
+        handler
+    }
+
+    fn proof(handler: &mut TxReceiptsMptHandler, index: u64) -> ReceiptInclusionProof {
+        let value = handler.get_tx_receipt(index).unwrap().rlp_encode();
+        let nodes = handler.get_proof(index).unwrap();
+        InclusionProof::new(
+            1,
+            B256::ZERO,
+            handler.get_root().unwrap(),
+            index,
+            nodes,
+            value,
+        )
+    }
+
+    #[test]
+    fn test_fixtures() {
+        let receipts: Vec<_> = (0..20).map(receipt).collect();
+        let mut handler = handler(receipts.clone());
+
+        let receipt = ProofFixture::receipt("receipt_3", proof(&mut handler, 3));
+        receipt.verify().unwrap();
+        assert_eq!(receipt.value().to_vec(), receipts[3].rlp_encode());
+        assert_eq!(receipt.log_entry(), None);
+
+        let fixture = ProofFixture::log("log_3_1", proof(&mut handler, 3), 1).unwrap();
+        fixture.verify().unwrap();
+        assert_eq!(fixture.log_entry(), Some((1, &receipts[3].logs()[1])));
+        assert!(matches!(
+            ProofFixture::log("log_3_2", proof(&mut handler, 3), 2),
+            Err(EthTrieError::LogNotFound)
+        ));
+
+        let fixtures = vec![receipt, fixture.clone()];
+        let json = render_json_fixtures(&fixtures);
+        let decoded: Vec<ProofFixture> = serde_json::from_str(&json).unwrap();
+        assert_eq!(decoded, fixtures);
+
+        let mut invalid = fixture.clone();
+        if let FixtureProof::Log { receipt, .. } = &mut invalid.proof {
+            receipt.value = Bytes::from_static(&[0x01]);
+        }
+        assert!(invalid.verify().is_err());
+
+        let mut invalid = fixture.clone();
+        if let FixtureProof::Log { log_index, .. } = &mut invalid.proof {
+            *log_index = 0;
+        }
+        assert!(matches!(invalid.verify(), Err(EthTrieError::LogNotFound)));
+
+        let mut invalid = fixture;
+        invalid.rlp_proof = Bytes::from_static(&[0xc0]);
+        assert!(invalid.verify().is_err());
+    }
+
+    #[test]
+    fn test_render_foundry_test() {
+        let mut handler = handler((0..3).map(receipt).collect());
+        let fixture = ProofFixture::log("log 1-0", proof(&mut handler, 1), 0).unwrap();
+        let test = render_foundry_test(
+            "ReceiptFixturesTest",
+            "../src/lib/MerkleTrie.sol",
+            std::slice::from_ref(&fixture),
+        );
+
+        assert!(test.contains("contract ReceiptFixturesTest is Test {"));
+        assert!(test.contains("import {MerkleTrie} from \"../src/lib/MerkleTrie.sol\";"));
+        assert!(test.contains("function test_log_1_0() public pure {"));
+        assert!(test.contains(&format!("bytes32 root = {};", fixture.root())));
+        assert!(test.contains(&hex::encode(&fixture.rlp_proof)));
+        assert!(test.contains(&format!(
+            "assertEq(value, hex\"{}\");",
+            hex::encode(fixture.value())
+        )));
+    }
+
+    #[test]
+    fn test_render_receipt_prover_test() {
+        let mut handler = handler((0..3).map(receipt).collect());
+        let fixtures = vec![
+            ProofFixture::receipt("receipt_0", proof(&mut handler, 0)),
+            ProofFixture::log("log_2_1", proof(&mut handler, 2), 1).unwrap(),
+        ];
+        let test = render_receipt_prover_test(
+            "ReceiptProverTest",
+            "ReceiptProver",
+            "../src/ReceiptProver.sol",
+            &fixtures,
+        );
+
+        let (_, log) = fixtures[1].log_entry().unwrap();
+        assert!(!test.contains("test_receipt_0"));
+        assert!(test.contains("function test_log_2_1() public view {"));
+        assert!(test.contains(&format!("topics[0] = {};", log.topics()[0])));
+        assert!(test.contains(&format!("            {},", log.address)));
+        assert!(test.contains(&hex::encode(&log.data.data)));
+    }
+
+    /// The Foundry example runs the rendered prover against these fixtures offline.
+    /// Run with `UPDATE_FIXTURES=1` to write them again after changing the renderers.
+    #[test]
+    fn test_receipt_prover_example_up_to_date() {
+        let receipts = example_receipts();
+        let mut handler = handler(receipts.clone());
+        let mut fixtures = vec![];
+        for tx_index in [0, 1, 2, 3, 4, 5, 6, 7, 63] {
+            for log_index in 0..receipts[tx_index].logs().len() as u64 {
+                let name = format!("log_{tx_index}_{log_index}");
+                let proof = proof(&mut handler, tx_index as u64);
+                let fixture = ProofFixture::log(name, proof, log_index).unwrap();
+                fixture.verify().unwrap();
+                fixtures.push(fixture);
+            }
+        }
+
+        let files = [
+            (
+                "src/ReceiptProver.sol",
+                render_receipt_prover("ReceiptProver", "./lib"),
+            ),
+            (
+                "test/ReceiptProver.t.sol",
+                render_receipt_prover_test(
+                    "ReceiptProverTest",
+                    "ReceiptProver",
+                    "../src/ReceiptProver.sol",
+                    &fixtures,
+                ),
+            ),
+        ];
+        for (file, rendered) in files {
+            let path = format!("{EXAMPLE_DIR}/{file}");
+            if std::env::var_os("UPDATE_FIXTURES").is_some() {
+                std::fs::write(&path, &rendered).unwrap();
+            }
+            assert_eq!(
+                std::fs::read_to_string(&path).unwrap(),
+                rendered,
+                "{file} is outdated, run the tests with UPDATE_FIXTURES=1"
+            );
+        }
+    }
+}
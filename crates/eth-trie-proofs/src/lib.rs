@@ -1,8 +1,20 @@
+pub mod arbitrum;
+pub mod batch;
+pub mod blob;
+pub mod chain;
+pub mod codec;
+pub mod codegen;
 pub mod error;
+pub mod fixture;
+pub mod op;
+pub mod polygon;
+pub mod proof;
 mod rpc;
+pub mod trie_builder;
 pub mod tx;
 pub mod tx_receipt;
 pub mod tx_receipt_trie;
 pub mod tx_trie;
+pub mod verify;
 
 pub use error::EthTrieError;
@@ -0,0 +1,353 @@
+//! OP Stack (Optimism, Base, ...) specific transaction and receipt types.
+
+use alloy::consensus::{Eip658Value, Receipt, ReceiptWithBloom};
+use alloy::primitives::{keccak256, Address, Bloom, Bytes, Log, TxKind, B256, U256};
+use alloy_rlp::{BufMut, Decodable, Encodable, Header};
+
+/// EIP-2718 type of OP Stack deposit transactions.
+pub const DEPOSIT_TX_TYPE: u8 = 0x7E;
+
+/// Deposit transaction of an OP Stack chain, derived from L1 and included first in L2 blocks.
+///
+/// Deposits are not signed and have no nonce: `from` is set by the derivation, and
+/// `source_hash` uniquely identifies the deposit.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Default)]
+pub struct TxDeposit {
+    /// Hash identifying the origin of the deposit.
+    pub source_hash: B256,
+    pub from: Address,
+    pub to: TxKind,
+    /// ETH minted on L2, if any.
+    pub mint: Option<u128>,
+    pub value: U256,
+    pub gas_limit: u64,
+    /// Whether the transaction is exempt from the L2 gas limit, before Regolith.
+    pub is_system_transaction: bool,
+    pub input: Bytes,
+}
+
+impl TxDeposit {
+    /// Hash of the transaction, i.e. the hash of its 2718 encoding.
+    pub fn tx_hash(&self) -> B256 {
+        let mut out = vec![];
+        self.encode_2718(&mut out);
+        keccak256(out)
+    }
+
+    fn fields_len(&self) -> usize {
+        self.source_hash.length()
+            + self.from.length()
+            + self.to.length()
+            + self.mint.unwrap_or_default().length()
+            + self.value.length()
+            + self.gas_limit.length()
+            + self.is_system_transaction.length()
+            + self.input.length()
+    }
+
+    fn rlp_header(&self) -> Header {
+        Header {
+            list: true,
+            payload_length: self.fields_len(),
+        }
+    }
+
+    /// Length of the 2718 encoding, type byte included.
+    pub fn encode_2718_len(&self) -> usize {
+        1 + self.rlp_header().length() + self.fields_len()
+    }
+
+    /// Encodes the transaction as `0x7E || rlp([source_hash, from, to, mint, value, gas_limit,
+    /// is_system_transaction, input])`.
+    pub fn encode_2718(&self, out: &mut dyn BufMut) {
+        out.put_u8(DEPOSIT_TX_TYPE);
+        self.rlp_header().encode(out);
+        self.source_hash.encode(out);
+        self.from.encode(out);
+        self.to.encode(out);
+        // No mint and a zero mint share the same encoding.
+        self.mint.unwrap_or_default().encode(out);
+        self.value.encode(out);
+        self.gas_limit.encode(out);
+        self.is_system_transaction.encode(out);
+        self.input.encode(out);
+    }
+
+    /// Decodes the RLP list following the type byte.
+    pub fn rlp_decode(buf: &mut &[u8]) -> alloy_rlp::Result<Self> {
+        let header = Header::decode(buf)?;
+        if !header.list {
+            return Err(alloy_rlp::Error::UnexpectedString);
+        }
+        if buf.len() < header.payload_length {
+            return Err(alloy_rlp::Error::InputTooShort);
+        }
+        let remaining = buf.len();
+
+        let tx = Self {
+            source_hash: Decodable::decode(buf)?,
+            from: Decodable::decode(buf)?,
+            to: Decodable::decode(buf)?,
+            mint: match u128::decode(buf)? {
+                0 => None,
+                mint => Some(mint),
+            },
+            value: Decodable::decode(buf)?,
+            gas_limit: Decodable::decode(buf)?,
+            is_system_transaction: Decodable::decode(buf)?,
+            input: Decodable::decode(buf)?,
+        };
+
+        if remaining - buf.len() != header.payload_length {
+            return Err(alloy_rlp::Error::ListLengthMismatch {
+                expected: header.payload_length,
+                got: remaining - buf.len(),
+            });
+        }
+        Ok(tx)
+    }
+}
+
+/// Activation timestamps of the OP Stack hardforks changing deposit receipts.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct OpHardforks {
+    /// Regolith adds the nonce of the sender to deposit receipts.
+    pub regolith_time: u64,
+    /// Canyon adds the receipt version to deposit receipts.
+    pub canyon_time: u64,
+}
+
+impl OpHardforks {
+    pub const OP_MAINNET: Self = Self {
+        regolith_time: 0,
+        canyon_time: 1704992401,
+    };
+    pub const OP_SEPOLIA: Self = Self {
+        regolith_time: 0,
+        canyon_time: 1699981200,
+    };
+    pub const BASE_MAINNET: Self = Self {
+        regolith_time: 0,
+        canyon_time: 1704992401,
+    };
+    pub const BASE_SEPOLIA: Self = Self {
+        regolith_time: 0,
+        canyon_time: 1699981200,
+    };
+
+    pub fn is_regolith_active_at(&self, timestamp: u64) -> bool {
+        timestamp >= self.regolith_time
+    }
+
+    pub fn is_canyon_active_at(&self, timestamp: u64) -> bool {
+        timestamp >= self.canyon_time
+    }
+}
+
+/// Receipt of an OP Stack deposit transaction.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct OpDepositReceipt {
+    pub inner: ReceiptWithBloom<Log>,
+    /// Nonce of the sender when the deposit was executed, since Regolith.
+    pub deposit_nonce: Option<u64>,
+    /// Version of the receipt, since Canyon.
+    pub deposit_receipt_version: Option<u64>,
+}
+
+impl OpDepositReceipt {
+    fn fields_len(&self) -> usize {
+        let receipt = &self.inner.receipt;
+        receipt.status.length()
+            + receipt.cumulative_gas_used.length()
+            + self.inner.logs_bloom.length()
+            + receipt.logs.length()
+            + self.deposit_nonce.map_or(0, |nonce| nonce.length())
+            + self
+                .deposit_receipt_version
+                .map_or(0, |version| version.length())
+    }
+
+    fn rlp_header(&self) -> Header {
+        Header {
+            list: true,
+            payload_length: self.fields_len(),
+        }
+    }
+
+    /// Length of the 2718 encoding, type byte included.
+    pub fn encode_2718_len(&self) -> usize {
+        1 + self.rlp_header().length() + self.fields_len()
+    }
+
+    /// Encodes the receipt as `0x7E || rlp([status, cumulative_gas_used, logs_bloom, logs,
+    /// deposit_nonce?, deposit_receipt_version?])`.
+    pub fn encode_2718(&self, out: &mut dyn BufMut) {
+        let receipt = &self.inner.receipt;
+        out.put_u8(DEPOSIT_TX_TYPE);
+        self.rlp_header().encode(out);
+        receipt.status.encode(out);
+        receipt.cumulative_gas_used.encode(out);
+        self.inner.logs_bloom.encode(out);
+        receipt.logs.encode(out);
+        if let Some(nonce) = self.deposit_nonce {
+            nonce.encode(out);
+        }
+        if let Some(version) = self.deposit_receipt_version {
+            version.encode(out);
+        }
+    }
+
+    /// Decodes the RLP list following the type byte.
+    pub fn rlp_decode(buf: &mut &[u8]) -> alloy_rlp::Result<Self> {
+        let header = Header::decode(buf)?;
+        if !header.list {
+            return Err(alloy_rlp::Error::UnexpectedString);
+        }
+        if buf.len() < header.payload_length {
+            return Err(alloy_rlp::Error::InputTooShort);
+        }
+        let remaining = buf.len();
+        let consumed = |buf: &&[u8]| remaining - buf.len();
+
+        let status: Eip658Value = Decodable::decode(buf)?;
+        let cumulative_gas_used = Decodable::decode(buf)?;
+        let logs_bloom: Bloom = Decodable::decode(buf)?;
+        let logs = Decodable::decode(buf)?;
+        let deposit_nonce = if consumed(buf) < header.payload_length {
+            Some(Decodable::decode(buf)?)
+        } else {
+            None
+        };
+        let deposit_receipt_version = if consumed(buf) < header.payload_length {
+            Some(Decodable::decode(buf)?)
+        } else {
+            None
+        };
+
+        if consumed(buf) != header.payload_length {
+            return Err(alloy_rlp::Error::ListLengthMismatch {
+                expected: header.payload_length,
+                got: consumed(buf),
+            });
+        }
+        Ok(Self {
+            inner: ReceiptWithBloom {
+                receipt: Receipt {
+                    status,
+                    cumulative_gas_used,
+                    logs,
+                },
+                logs_bloom,
+            },
+            deposit_nonce,
+            deposit_receipt_version,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloy::primitives::{address, hex};
+
+    use super::*;
+
+    #[test]
+    fn test_deposit_round_trip() {
+        let tx = TxDeposit {
+            source_hash: B256::repeat_byte(0x01),
+            from: Address::repeat_byte(0x02),
+            to: TxKind::Call(Address::repeat_byte(0x03)),
+            mint: Some(1_000),
+            value: U256::from(1_000),
+            gas_limit: 100_000,
+            is_system_transaction: false,
+            input: Bytes::from_static(&[0xde, 0xad]),
+        };
+        let mut encoded = vec![];
+        tx.encode_2718(&mut encoded);
+        assert_eq!(encoded[0], DEPOSIT_TX_TYPE);
+        assert_eq!(encoded.len(), tx.encode_2718_len());
+
+        let decoded = TxDeposit::rlp_decode(&mut &encoded[1..]).unwrap();
+        assert_eq!(decoded, tx);
+
+        let create = TxDeposit {
+            to: TxKind::Create,
+            mint: None,
+            ..tx
+        };
+        let mut encoded = vec![];
+        create.encode_2718(&mut encoded);
+        assert_eq!(TxDeposit::rlp_decode(&mut &encoded[1..]).unwrap(), create);
+        assert!(TxDeposit::rlp_decode(&mut &encoded[1..encoded.len() - 1]).is_err());
+    }
+
+    #[test]
+    fn test_deposit_decode() {
+        // Pre-Regolith L1 attributes deposit, a system transaction
+        let raw = hex!("7ef9015aa044bae9d41b8380d781187b426c6fe43df5fb2fb57bd4466ef6a701e1f01e015694deaddeaddeaddeaddeaddeaddeaddeaddead000194420000000000000000000000000000000000001580808408f0d18001b90104015d8eb900000000000000000000000000000000000000000000000000000000008057650000000000000000000000000000000000000000000000000000000063d96d10000000000000000000000000000000000000000000000000000000000009f35273d89754a1e0387b89520d989d3be9c37c1f32495a88faf1ea05c61121ab0d1900000000000000000000000000000000000000000000000000000000000000010000000000000000000000002d679b567db6187c0c8323fa982cfb88b74dbcc7000000000000000000000000000000000000000000000000000000000000083400000000000000000000000000000000000000000000000000000000000f4240");
+        let tx = TxDeposit::rlp_decode(&mut &raw[1..]).unwrap();
+        assert_eq!(
+            tx.from,
+            address!("deaddeaddeaddeaddeaddeaddeaddeaddead0001")
+        );
+        assert_eq!(
+            tx.to,
+            TxKind::Call(address!("4200000000000000000000000000000000000015"))
+        );
+        assert_eq!(tx.mint, None);
+        assert_eq!(tx.gas_limit, 150_000_000);
+        assert!(tx.is_system_transaction);
+
+        let mut encoded = vec![];
+        tx.encode_2718(&mut encoded);
+        assert_eq!(encoded, raw);
+        assert_eq!(tx.tx_hash(), keccak256(raw));
+    }
+
+    fn deposit_receipt(
+        deposit_nonce: Option<u64>,
+        deposit_receipt_version: Option<u64>,
+    ) -> OpDepositReceipt {
+        let log = Log::new_unchecked(
+            Address::repeat_byte(0x42),
+            vec![B256::repeat_byte(0x01)],
+            Bytes::from_static(&[0xca, 0xfe]),
+        );
+        let receipt = Receipt {
+            status: true.into(),
+            cumulative_gas_used: 46_913,
+            logs: vec![log],
+        };
+        OpDepositReceipt {
+            inner: receipt.with_bloom(),
+            deposit_nonce,
+            deposit_receipt_version,
+        }
+    }
+
+    #[test]
+    fn test_deposit_receipt_round_trip() {
+        // Bedrock, Regolith and Canyon receipts
+        for (nonce, version) in [(None, None), (Some(1_234), None), (Some(0), Some(1))] {
+            let receipt = deposit_receipt(nonce, version);
+            let mut encoded = vec![];
+            receipt.encode_2718(&mut encoded);
+            assert_eq!(encoded[0], DEPOSIT_TX_TYPE);
+            assert_eq!(encoded.len(), receipt.encode_2718_len());
+            assert_eq!(
+                OpDepositReceipt::rlp_decode(&mut &encoded[1..]).unwrap(),
+                receipt
+            );
+            assert!(OpDepositReceipt::rlp_decode(&mut &encoded[1..encoded.len() - 1]).is_err());
+        }
+    }
+
+    #[test]
+    fn test_op_hardforks() {
+        let hardforks = OpHardforks::OP_MAINNET;
+        assert!(hardforks.is_regolith_active_at(1686068903));
+        assert!(!hardforks.is_canyon_active_at(1704992400));
+        assert!(hardforks.is_canyon_active_at(1704992401));
+    }
+}
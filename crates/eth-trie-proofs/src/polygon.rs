@@ -0,0 +1,163 @@
+//! Polygon PoS (Bor) specific handling.
+//!
+//! At the end of some sprints, Bor executes state syncs from L1 in a synthetic transaction.
+//! Bor nodes return this transaction and its receipt along the block transactions and
+//! receipts, but neither is part of `transactions_root` or `receipts_root`. The receipt is
+//! stored separately, as the Bor receipt of the block, under a transaction hash derived from
+//! the block.
+
+use alloy::primitives::{keccak256, Bloom, B256};
+use url::Url;
+
+use crate::{
+    chain::ChainSpec,
+    error::EthTrieError,
+    rpc::RpcProvider,
+    tx_receipt::{ConsensusTxReceipt, RpcTxReceipt},
+};
+
+/// Prefix of the key of Bor receipts, also hashed into the state sync transaction hash.
+const BOR_RECEIPT_PREFIX: &[u8] = b"matic-bor-receipt-";
+
+/// Returns the hash Bor derives for the state sync transaction of a block.
+pub fn bor_state_sync_tx_hash(block_number: u64, block_hash: B256) -> B256 {
+    let mut key = BOR_RECEIPT_PREFIX.to_vec();
+    key.extend(block_number.to_be_bytes());
+    key.extend(block_hash.as_slice());
+    keccak256(key)
+}
+
+/// Receipt of the state sync transaction of a Polygon PoS block.
+///
+/// Bor receipts are not committed to by the block header, so they cannot be proven against it:
+/// a `BorReceipt` is only as trustworthy as the node it was fetched from.
+/// [`BorReceipt::check_consistency`] catches malformed responses, it is not a proof.
+#[derive(Debug, Clone)]
+pub struct BorReceipt {
+    pub block_number: u64,
+    pub block_hash: B256,
+    pub tx_hash: B256,
+    pub receipt: ConsensusTxReceipt,
+}
+
+impl BorReceipt {
+    /// Checks that the transaction hash is the one Bor derives for the block and that the bloom
+    /// matches the logs.
+    ///
+    /// Both are computed from the fields of the receipt itself, so this does not show that the
+    /// receipt is the one of the block.
+    pub fn check_consistency(&self) -> Result<(), EthTrieError> {
+        if self.tx_hash != bor_state_sync_tx_hash(self.block_number, self.block_hash) {
+            return Err(EthTrieError::InvalidBorReceipt);
+        }
+        let logs = self.receipt.logs();
+        let bloom = Bloom::from_iter(logs.iter());
+        if bloom != self.receipt.bloom() {
+            return Err(EthTrieError::InvalidBorReceipt);
+        }
+        Ok(())
+    }
+}
+
+/// Fetches Bor receipts, which are not part of the receipts trie of Polygon PoS blocks.
+pub struct BorReceiptHandler {
+    provider: RpcProvider,
+}
+
+impl BorReceiptHandler {
+    /// Creates a new [`BorReceiptHandler`] with a given Bor RPC provider URL.
+    pub fn new(url: Url) -> Result<Self, EthTrieError> {
+        Ok(Self {
+            provider: RpcProvider::new(url),
+        })
+    }
+
+    /// Fetches the Bor receipt of a block, trusting the node for its content.
+    ///
+    /// Returns `None` if the block executed no state sync.
+    pub async fn get_bor_receipt(
+        &self,
+        block_number: u64,
+    ) -> Result<Option<BorReceipt>, EthTrieError> {
+        let header = self.provider.get_block_header(block_number).await?;
+        let block_hash = header.hash;
+        let Some(receipt) = self.provider.get_bor_block_receipt(block_hash).await? else {
+            return Ok(None);
+        };
+        let tx_hash = receipt.transaction_hash;
+        let receipt = RpcTxReceipt {
+            receipt,
+            chain_spec: &ChainSpec::POLYGON,
+            timestamp: header.timestamp,
+        }
+        .try_into()?;
+
+        let bor_receipt = BorReceipt {
+            block_number,
+            block_hash,
+            tx_hash,
+            receipt,
+        };
+        bor_receipt.check_consistency()?;
+        Ok(Some(bor_receipt))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloy::primitives::hex;
+
+    use super::*;
+    use crate::tx_receipt_trie::TxReceiptsMptHandler;
+    use crate::tx_trie::TxsMptHandler;
+
+    const POLYGON_RPC_URL: &str = "https://polygon-rpc.com";
+
+    #[test]
+    fn test_bor_state_sync_tx_hash() {
+        let block_hash = B256::repeat_byte(0xab);
+        let mut key = b"matic-bor-receipt-".to_vec();
+        key.extend(hex!("0000000000000010"));
+        key.extend([0xab; 32]);
+        assert_eq!(bor_state_sync_tx_hash(16, block_hash), keccak256(key));
+        assert_ne!(
+            bor_state_sync_tx_hash(16, block_hash),
+            bor_state_sync_tx_hash(17, block_hash)
+        );
+    }
+
+    #[tokio::test]
+    async fn test_bor_receipt() {
+        let url = Url::parse(POLYGON_RPC_URL).unwrap();
+        let handler = BorReceiptHandler::new(url).unwrap();
+
+        // State syncs are only executed at the start of sprints
+        let mut found = false;
+        for block_number in (60000000..60000000 + 16 * 8).step_by(16) {
+            if let Some(receipt) = handler.get_bor_receipt(block_number).await.unwrap() {
+                receipt.check_consistency().unwrap();
+                assert!(!receipt.receipt.logs().is_empty());
+
+                // The state sync transaction and receipt are left out of the tries
+                let url = Url::parse(POLYGON_RPC_URL).unwrap();
+                let mut txs_mpt_handler = TxsMptHandler::new(url.clone()).unwrap();
+                txs_mpt_handler
+                    .build_tx_tree_from_block(block_number)
+                    .await
+                    .unwrap();
+                assert!(txs_mpt_handler
+                    .tx_hash_to_tx_index(receipt.tx_hash)
+                    .is_err());
+                let mut tx_receipts_mpt_handler = TxReceiptsMptHandler::new(url).unwrap();
+                tx_receipts_mpt_handler
+                    .build_tx_receipts_tree_from_block(block_number)
+                    .await
+                    .unwrap();
+
+                found = true;
+                break;
+            }
+        }
+        assert!(found);
+    }
+}
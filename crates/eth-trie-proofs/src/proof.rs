@@ -0,0 +1,196 @@
+use std::marker::PhantomData;
+
+use alloy::primitives::{Bytes, B256, U256};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    error::EthTrieError, tx::ConsensusTx, tx_receipt::ConsensusTxReceipt, verify::verify_proof,
+};
+
+/// Kind of trie an [`InclusionProof`] belongs to, and how its values decode.
+pub trait TrieKind {
+    /// Element stored in the trie.
+    type Element;
+
+    fn decode(value: &[u8]) -> Result<Self::Element, EthTrieError>;
+}
+
+/// Marker of the transactions trie.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TxTrie;
+
+/// Marker of the receipts trie.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ReceiptTrie;
+
+impl TrieKind for TxTrie {
+    type Element = ConsensusTx;
+
+    fn decode(value: &[u8]) -> Result<ConsensusTx, EthTrieError> {
+        ConsensusTx::rlp_decode(value)
+    }
+}
+
+impl TrieKind for ReceiptTrie {
+    type Element = ConsensusTxReceipt;
+
+    fn decode(value: &[u8]) -> Result<ConsensusTxReceipt, EthTrieError> {
+        ConsensusTxReceipt::rlp_decode(value)
+    }
+}
+
+/// Proof of inclusion of an element in the trie of kind `K` of a block.
+///
+/// Note: [`InclusionProof::verify`] checks the proof against `root`. Tying `root`
+/// to `block_hash` requires the block header and is up to the consumer.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", bound = "")]
+pub struct InclusionProof<K> {
+    pub block_number: u64,
+    pub block_hash: B256,
+    /// Transactions or receipts root of the block.
+    pub root: B256,
+    pub index: u64,
+    /// RLP-encoded trie key, i.e. the RLP-encoded index.
+    pub key: Bytes,
+    pub nodes: Vec<Bytes>,
+    /// The 2718-encoded transaction or receipt.
+    pub value: Bytes,
+    #[serde(skip)]
+    pub(crate) kind: PhantomData<K>,
+}
+
+/// Proof of inclusion of a transaction in the transactions trie of a block.
+pub type TxInclusionProof = InclusionProof<TxTrie>;
+
+/// Proof of inclusion of a receipt in the receipts trie of a block.
+pub type ReceiptInclusionProof = InclusionProof<ReceiptTrie>;
+
+impl<K: TrieKind> InclusionProof<K> {
+    pub fn new(
+        block_number: u64,
+        block_hash: B256,
+        root: B256,
+        index: u64,
+        nodes: Vec<Vec<u8>>,
+        value: Vec<u8>,
+    ) -> Self {
+        Self {
+            block_number,
+            block_hash,
+            root,
+            index,
+            key: alloy_rlp::encode(U256::from(index)).into(),
+            nodes: nodes.into_iter().map(Bytes::from).collect(),
+            value: value.into(),
+            kind: PhantomData,
+        }
+    }
+
+    /// Verifies the proof against `root` and returns the decoded transaction or receipt.
+    pub fn verify(&self) -> Result<K::Element, EthTrieError> {
+        verify_inclusion(self.root, self.index, &self.key, &self.nodes, &self.value)?;
+        K::decode(&self.value)
+    }
+}
+
+fn verify_inclusion(
+    root: B256,
+    index: u64,
+    key: &[u8],
+    nodes: &[Bytes],
+    value: &[u8],
+) -> Result<(), EthTrieError> {
+    if key != alloy_rlp::encode(U256::from(index)) {
+        return Err(EthTrieError::InvalidMPTProof);
+    }
+    let nodes = nodes.iter().map(|node| node.to_vec()).collect();
+    if verify_proof(root, index, nodes)? != value {
+        return Err(EthTrieError::InvalidMPTProof);
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use alloy::consensus::{SignableTransaction, TxLegacy};
+    use alloy::network::eip2718::Encodable2718;
+    use alloy::primitives::{Address, Parity, Signature, TxKind};
+    use eth_trie::{EthTrie, MemoryDB, Trie};
+
+    use super::*;
+
+    fn tx(nonce: u64) -> ConsensusTx {
+        let tx = TxLegacy {
+            chain_id: Some(1),
+            nonce,
+            gas_price: 10,
+            gas_limit: 21_000,
+            to: TxKind::Call(Address::repeat_byte(0x11)),
+            value: U256::from(5),
+            input: Default::default(),
+        };
+        let signature =
+            Signature::from_rs_and_parity(U256::from(1234), U256::from(5678), Parity::Eip155(37))
+                .unwrap();
+        ConsensusTx(tx.into_signed(signature).into())
+    }
+
+    fn tx_proof(index: u64) -> (TxInclusionProof, ConsensusTx) {
+        let txs: Vec<_> = (0..10).map(tx).collect();
+        let mut trie = EthTrie::new(Arc::new(MemoryDB::new(true)));
+        for (idx, tx) in txs.iter().enumerate() {
+            let key = alloy_rlp::encode(U256::from(idx));
+            trie.insert(&key, &tx.0.encoded_2718()).unwrap();
+        }
+        let root = B256::from_slice(trie.root_hash().unwrap().as_bytes());
+        let nodes = trie
+            .get_proof(&alloy_rlp::encode(U256::from(index)))
+            .unwrap();
+        let tx = txs[index as usize].clone();
+        let proof = TxInclusionProof::new(
+            100,
+            B256::repeat_byte(0xbb),
+            root,
+            index,
+            nodes,
+            tx.0.encoded_2718(),
+        );
+        (proof, tx)
+    }
+
+    #[test]
+    fn test_tx_inclusion_proof() {
+        let (proof, tx) = tx_proof(7);
+        assert_eq!(proof.key.to_vec(), vec![0x07]);
+        assert_eq!(proof.verify().unwrap().0, tx.0);
+
+        let json = serde_json::to_string(&proof).unwrap();
+        assert!(json.contains("\"blockHash\":\"0xbbbb"));
+        let decoded: TxInclusionProof = serde_json::from_str(&json).unwrap();
+        assert_eq!(decoded, proof);
+    }
+
+    #[test]
+    fn test_tx_inclusion_proof_invalid() {
+        let (proof, _) = tx_proof(3);
+
+        let mut invalid = proof.clone();
+        invalid.key = alloy_rlp::encode(U256::from(4)).into();
+        assert!(invalid.verify().is_err());
+
+        let mut invalid = proof.clone();
+        invalid.index = 4;
+        assert!(invalid.verify().is_err());
+
+        let mut invalid = proof.clone();
+        invalid.value = tx(42).0.encoded_2718().into();
+        assert!(invalid.verify().is_err());
+
+        let mut invalid = proof;
+        invalid.nodes.pop();
+        assert!(invalid.verify().is_err());
+    }
+}
@@ -3,10 +3,17 @@ use alloy::network::Ethereum;
 use alloy::primitives::B256;
 use alloy::providers::{Provider, RootProvider};
 
-use alloy::rpc::types::{BlockTransactions, Transaction, TransactionReceipt};
+use alloy::rpc::types::serde_helpers::WithOtherFields;
+use alloy::rpc::types::{
+    AnyNetworkBlock, AnyTransactionReceipt, BlockNumberOrTag, BlockTransactions, Header,
+    Transaction,
+};
 use alloy::transports::http::{Client, Http};
 use alloy::transports::{RpcError, TransportErrorKind};
+use serde::Deserialize;
 
+/// Client of the RPC node. Clones share the same HTTP client and its connections.
+#[derive(Clone)]
 pub(crate) struct RpcProvider {
     provider: RootProvider<Http<Client>, Ethereum>,
 }
@@ -17,47 +24,82 @@ impl RpcProvider {
         Self { provider }
     }
 
+    /// Fetches the transactions and header of a block, keeping the fields of chain specific
+    /// transaction types, e.g. OP Stack deposits, which the Ethereum transaction type drops.
     pub(crate) async fn get_block_transactions(
         &self,
         block_number: u64,
-    ) -> Result<(Vec<Transaction>, B256), EthTrieError> {
-        let block = self
+    ) -> Result<(Vec<WithOtherFields<Transaction>>, Header), EthTrieError> {
+        let block: AnyNetworkBlock = self
             .provider
-            .get_block(
-                block_number.into(),
-                alloy::rpc::types::BlockTransactionsKind::Full,
+            .raw_request::<_, Option<AnyNetworkBlock>>(
+                "eth_getBlockByNumber".into(),
+                (BlockNumberOrTag::Number(block_number), true),
             )
             .await?
             .ok_or_else(|| EthTrieError::BlockNotFound)?;
 
-        let txs = match block.transactions {
+        let txs = match block.inner.transactions {
             BlockTransactions::Full(txs) => txs,
             _ => return Err(EthTrieError::TxNotFound),
         };
 
-        Ok((txs, block.header.transactions_root))
+        Ok((txs, block.inner.header))
     }
 
+    /// Fetches the receipts of a block, keeping the fields of chain specific receipt types,
+    /// e.g. OP Stack deposits. Also returns the header of the block.
     pub(crate) async fn get_block_transaction_receipts(
         &self,
         block_number: u64,
-    ) -> Result<(Vec<TransactionReceipt>, B256), EthTrieError> {
-        let block = self
-            .provider
-            .get_block(
-                block_number.into(),
-                alloy::rpc::types::BlockTransactionsKind::Full,
+    ) -> Result<(Vec<AnyTransactionReceipt>, Header), EthTrieError> {
+        let header = self.get_block_header(block_number).await?;
+        let tx_receipts = self.get_block_receipts(block_number).await?;
+        Ok((tx_receipts, header))
+    }
+
+    /// Fetches the receipts of a block, keeping the fields of chain specific receipt types.
+    pub(crate) async fn get_block_receipts(
+        &self,
+        block_number: u64,
+    ) -> Result<Vec<AnyTransactionReceipt>, EthTrieError> {
+        self.provider
+            .raw_request::<_, Option<Vec<serde_json::Value>>>(
+                "eth_getBlockReceipts".into(),
+                (BlockNumberOrTag::Number(block_number),),
             )
             .await?
-            .ok_or_else(|| EthTrieError::BlockNotFound)?;
+            .ok_or_else(|| EthTrieError::BlockNotFound)?
+            .into_iter()
+            .map(deserialize_receipt)
+            .collect()
+    }
 
-        let tx_receipts = self
+    pub(crate) async fn get_block_header(&self, block_number: u64) -> Result<Header, EthTrieError> {
+        let block: AnyNetworkBlock = self
             .provider
-            .get_block_receipts(block_number.into())
+            .raw_request::<_, Option<AnyNetworkBlock>>(
+                "eth_getBlockByNumber".into(),
+                (BlockNumberOrTag::Number(block_number), false),
+            )
             .await?
             .ok_or_else(|| EthTrieError::BlockNotFound)?;
+        Ok(block.inner.header)
+    }
 
-        Ok((tx_receipts, block.header.receipts_root))
+    /// Fetches the receipt of the state sync transaction of a Polygon PoS block, if any.
+    pub(crate) async fn get_bor_block_receipt(
+        &self,
+        block_hash: B256,
+    ) -> Result<Option<AnyTransactionReceipt>, EthTrieError> {
+        Ok(self
+            .provider
+            .raw_request("eth_getBorBlockReceipt".into(), (block_hash,))
+            .await?)
+    }
+
+    pub(crate) async fn get_chain_id(&self) -> Result<u64, EthTrieError> {
+        Ok(self.provider.get_chain_id().await?)
     }
 
     pub(crate) async fn get_tx_index_by_hash(&self, tx_hash: B256) -> Result<u64, EthTrieError> {
@@ -89,6 +131,37 @@ impl RpcProvider {
 
         Ok(height)
     }
+
+    pub(crate) async fn get_tx_block(&self, tx_hash: B256) -> Result<(u64, B256), EthTrieError> {
+        let tx = self
+            .provider
+            .get_transaction_by_hash(tx_hash)
+            .await?
+            .ok_or(EthTrieError::TxNotFound)?;
+
+        match (tx.block_number, tx.block_hash) {
+            (Some(height), Some(hash)) => Ok((height, hash)),
+            _ => Err(EthTrieError::TxNotFound),
+        }
+    }
+}
+
+/// Deserializes an RPC receipt.
+///
+/// Receipts before Byzantium carry their intermediate state root in a `root` field instead of a
+/// `status`, which the receipt type expects in `status`.
+pub(crate) fn deserialize_receipt(
+    mut receipt: serde_json::Value,
+) -> Result<AnyTransactionReceipt, EthTrieError> {
+    if let Some(fields) = receipt.as_object_mut() {
+        if fields.get("status").is_none_or(|status| status.is_null()) {
+            if let Some(root) = fields.get("root").cloned() {
+                fields.insert("status".into(), root);
+            }
+        }
+    }
+    AnyTransactionReceipt::deserialize(&receipt)
+        .map_err(|err| RpcError::deser_err(err, receipt.to_string()).into())
 }
 
 impl From<RpcError<TransportErrorKind>> for EthTrieError {
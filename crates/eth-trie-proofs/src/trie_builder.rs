@@ -0,0 +1,437 @@
+use std::collections::BTreeMap;
+use std::num::NonZeroUsize;
+
+use alloy::consensus::EMPTY_ROOT_HASH;
+use alloy::network::eip2718::Encodable2718;
+use alloy::primitives::{keccak256, B256};
+use alloy_rlp::{Encodable, Header};
+
+use crate::{tx::ConsensusTx, tx_receipt::ConsensusTxReceipt};
+
+/// Below this many elements per thread, encoding in parallel is not worth spawning threads.
+const MIN_ENCODE_CHUNK: usize = 256;
+
+/// Computes the root of a Merkle Patricia Trie from leaves added in increasing key order.
+///
+/// Only the stack of nodes along the path of the last added key is kept, so the trie is never
+/// held in memory. Nodes are recorded only when they lie on the path of one of the proof
+/// targets, to build their proofs.
+///
+/// Keys must be prefix-free, as branch nodes are built without values. RLP-encoded indices,
+/// which key the transactions and receipts tries, are.
+#[derive(Debug, Default)]
+pub struct HashBuilder {
+    /// Nibbles of the pending leaf.
+    key: Vec<u8>,
+    /// Value of the pending leaf.
+    value: Vec<u8>,
+    /// References to the nodes built so far: their RLP if shorter than 32 bytes, their
+    /// RLP-encoded hash otherwise.
+    stack: Vec<Vec<u8>>,
+    /// Children seen so far by the branch at each depth, one bit per nibble.
+    groups: Vec<u16>,
+    /// Sorted nibbles of the keys to record proofs for.
+    targets: Vec<Vec<u8>>,
+    /// RLP of the recorded nodes, by path.
+    recorded: BTreeMap<Vec<u8>, Vec<u8>>,
+}
+
+impl HashBuilder {
+    /// Creates a builder recording the proofs of the given keys.
+    pub fn new(proof_keys: impl IntoIterator<Item = Vec<u8>>) -> Self {
+        let mut targets: Vec<Vec<u8>> = proof_keys.into_iter().map(|k| to_nibbles(&k)).collect();
+        targets.sort();
+        targets.dedup();
+        Self {
+            targets,
+            ..Default::default()
+        }
+    }
+
+    /// Adds a leaf. Keys must be added in strictly increasing order.
+    pub fn add_leaf(&mut self, key: &[u8], value: &[u8]) {
+        let key = to_nibbles(key);
+        assert!(key > self.key, "keys must be added in increasing order");
+        if !self.key.is_empty() {
+            self.update(&key);
+        }
+        self.key = key;
+        self.value = value.to_vec();
+    }
+
+    /// Builds the nodes left on the stack and returns the root hash.
+    pub fn root(&mut self) -> B256 {
+        if !self.key.is_empty() {
+            self.update(&[]);
+            self.key.clear();
+            self.value.clear();
+        }
+        match self.stack.last() {
+            None => EMPTY_ROOT_HASH,
+            Some(node) if node.len() == B256::len_bytes() + 1 => B256::from_slice(&node[1..]),
+            Some(node) => keccak256(node),
+        }
+    }
+
+    /// Returns the proof of a key, from the root down, in the format of
+    /// [`eth_trie::Trie::get_proof`]. Only meaningful once [`HashBuilder::root`] is called.
+    pub fn proof(&self, key: &[u8]) -> Vec<Vec<u8>> {
+        let key = to_nibbles(key);
+        (0..=key.len())
+            .filter_map(|len| self.recorded.get(&key[..len]).cloned())
+            .collect()
+    }
+
+    /// Builds the nodes of the pending leaf that do not lie on the path of `succeeding`, the
+    /// next key, or all of them if it is empty.
+    fn update(&mut self, succeeding: &[u8]) {
+        let mut build_extensions = false;
+        let mut current = std::mem::take(&mut self.key);
+
+        loop {
+            let preceding_exists = !self.groups.is_empty();
+            let preceding_len = self.groups.len().saturating_sub(1);
+            let common_prefix_len = common_prefix_len(succeeding, &current);
+            let len = preceding_len.max(common_prefix_len);
+
+            // The nibble following the shared prefix is a child of the branch at `len`.
+            let extra_digit = current[len];
+            if self.groups.len() <= len {
+                self.groups.resize(len + 1, 0);
+            }
+            self.groups[len] |= 1 << extra_digit;
+
+            let mut len_from = len;
+            if !succeeding.is_empty() || preceding_exists {
+                len_from += 1;
+            }
+            let short_key = &current[len_from..];
+
+            if !build_extensions {
+                let rlp = leaf_rlp(short_key, &self.value);
+                self.push_node(&current[..len_from], rlp);
+            } else if !short_key.is_empty() {
+                let child = self.stack.pop().expect("extension child");
+                let rlp = extension_rlp(short_key, &child);
+                self.push_node(&current[..len_from], rlp);
+            }
+
+            if preceding_len <= common_prefix_len && !succeeding.is_empty() {
+                return;
+            }
+
+            if !succeeding.is_empty() || preceding_exists {
+                let mask = self.groups[len];
+                let children = self
+                    .stack
+                    .split_off(self.stack.len() - mask.count_ones() as usize);
+                let rlp = branch_rlp(mask, &children);
+                self.push_node(&current[..len], rlp);
+            }
+
+            self.groups.truncate(len);
+            if preceding_len == 0 {
+                return;
+            }
+
+            current.truncate(preceding_len);
+            while self.groups.last() == Some(&0) {
+                self.groups.pop();
+            }
+            build_extensions = true;
+        }
+    }
+
+    /// Pushes the reference of a node, recording it if it belongs to a proof.
+    fn push_node(&mut self, path: &[u8], rlp: Vec<u8>) {
+        let node_ref = if rlp.len() < B256::len_bytes() {
+            rlp.clone()
+        } else {
+            alloy_rlp::encode(keccak256(&rlp))
+        };
+        // Inlined nodes are part of their parent, apart from the root.
+        if (rlp.len() >= B256::len_bytes() || path.is_empty()) && self.is_on_target_path(path) {
+            self.recorded.insert(path.to_vec(), rlp);
+        }
+        self.stack.push(node_ref);
+    }
+
+    fn is_on_target_path(&self, path: &[u8]) -> bool {
+        let first = self
+            .targets
+            .partition_point(|target| target.as_slice() < path);
+        self.targets
+            .get(first)
+            .is_some_and(|target| target.starts_with(path))
+    }
+}
+
+/// Root and proofs of a trie keyed by RLP-encoded indices, such as the transactions or
+/// receipts trie of a block.
+#[derive(Debug, Clone)]
+pub struct IndexedTrie {
+    pub root: B256,
+    proofs: BTreeMap<u64, Vec<Vec<u8>>>,
+}
+
+impl IndexedTrie {
+    /// Returns the proof of the element at `index`, if requested when building the trie.
+    pub fn proof(&self, index: u64) -> Option<&[Vec<u8>]> {
+        self.proofs.get(&index).map(Vec::as_slice)
+    }
+}
+
+/// Builds [`IndexedTrie`]s without a node database, recording only the proofs of the requested
+/// indices.
+///
+/// This is an alternative to building an `EthTrie` in memory, as [`crate::tx_trie::TxsMptHandler`]
+/// does, for large blocks or when only the root and a few proofs are needed.
+#[derive(Debug, Clone, Default)]
+pub struct IndexedTrieBuilder {
+    proof_indices: Vec<u64>,
+}
+
+impl IndexedTrieBuilder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Records the proofs of the elements at the given indices.
+    pub fn with_proofs(mut self, indices: impl IntoIterator<Item = u64>) -> Self {
+        self.proof_indices.extend(indices);
+        self
+    }
+
+    /// Builds the trie of already encoded values, the value at position `i` being keyed by
+    /// the RLP encoding of `i`.
+    pub fn build<T: AsRef<[u8]>>(&self, values: &[T]) -> IndexedTrie {
+        let proof_indices: Vec<u64> = self
+            .proof_indices
+            .iter()
+            .copied()
+            .filter(|index| *index < values.len() as u64)
+            .collect();
+        let mut builder = HashBuilder::new(proof_indices.iter().map(alloy_rlp::encode));
+
+        for index in sorted_indices(values.len()) {
+            builder.add_leaf(&alloy_rlp::encode(index), values[index].as_ref());
+        }
+
+        let root = builder.root();
+        let proofs = proof_indices
+            .into_iter()
+            .map(|index| (index, builder.proof(&alloy_rlp::encode(index))))
+            .collect();
+        IndexedTrie { root, proofs }
+    }
+
+    /// Builds the transactions trie, encoding the transactions in parallel.
+    ///
+    /// As in [`crate::tx_trie::TxsMptHandler::build_trie`], blob sidecars are left out.
+    pub fn build_txs(&self, txs: &[ConsensusTx]) -> IndexedTrie {
+        let values = encode_parallel(txs, |tx| match tx.blob_sidecar() {
+            Some(_) => tx.clone().without_blob_sidecar().0.encoded_2718(),
+            None => tx.0.encoded_2718(),
+        });
+        self.build(&values)
+    }
+
+    /// Builds the receipts trie, encoding the receipts in parallel.
+    pub fn build_receipts(&self, receipts: &[ConsensusTxReceipt]) -> IndexedTrie {
+        let values = encode_parallel(receipts, |receipt| receipt.0.encoded_2718());
+        self.build(&values)
+    }
+}
+
+/// Indices `0..len` in the order of their RLP encoding: `0` is encoded as `0x80`, after the
+/// single byte encodings of `1..=127`, and before the longer encodings of `128..`.
+fn sorted_indices(len: usize) -> impl Iterator<Item = usize> {
+    (1..len.min(0x80))
+        .chain((len > 0).then_some(0))
+        .chain(0x80..len)
+}
+
+/// Encodes the items on as many threads as available.
+fn encode_parallel<T: Sync>(items: &[T], encode: impl Fn(&T) -> Vec<u8> + Sync) -> Vec<Vec<u8>> {
+    let threads = std::thread::available_parallelism().map_or(1, NonZeroUsize::get);
+    let chunk_size = items.len().div_ceil(threads).max(MIN_ENCODE_CHUNK);
+    if items.len() <= chunk_size {
+        return items.iter().map(encode).collect();
+    }
+    std::thread::scope(|scope| {
+        let handles: Vec<_> = items
+            .chunks(chunk_size)
+            .map(|chunk| scope.spawn(|| chunk.iter().map(&encode).collect::<Vec<_>>()))
+            .collect();
+        handles
+            .into_iter()
+            .flat_map(|handle| handle.join().expect("encoding thread panicked"))
+            .collect()
+    })
+}
+
+fn to_nibbles(key: &[u8]) -> Vec<u8> {
+    key.iter()
+        .flat_map(|byte| [byte >> 4, byte & 0x0f])
+        .collect()
+}
+
+fn common_prefix_len(a: &[u8], b: &[u8]) -> usize {
+    a.iter().zip(b).take_while(|(a, b)| a == b).count()
+}
+
+/// Hex-prefix encoding of a path.
+fn compact(nibbles: &[u8], leaf: bool) -> Vec<u8> {
+    let flag = if leaf { 0x20 } else { 0x00 };
+    let mut out = Vec::with_capacity(nibbles.len() / 2 + 1);
+    let rest = if nibbles.len() % 2 == 1 {
+        out.push(flag | 0x10 | nibbles[0]);
+        &nibbles[1..]
+    } else {
+        out.push(flag);
+        nibbles
+    };
+    out.extend(rest.chunks(2).map(|pair| (pair[0] << 4) | pair[1]));
+    out
+}
+
+fn leaf_rlp(key: &[u8], value: &[u8]) -> Vec<u8> {
+    let path = compact(key, true);
+    let mut out = Vec::new();
+    Header {
+        list: true,
+        payload_length: path.as_slice().length() + value.length(),
+    }
+    .encode(&mut out);
+    path.as_slice().encode(&mut out);
+    value.encode(&mut out);
+    out
+}
+
+fn extension_rlp(key: &[u8], child: &[u8]) -> Vec<u8> {
+    let path = compact(key, false);
+    let mut out = Vec::new();
+    Header {
+        list: true,
+        payload_length: path.as_slice().length() + child.len(),
+    }
+    .encode(&mut out);
+    path.as_slice().encode(&mut out);
+    out.extend_from_slice(child);
+    out
+}
+
+/// Branch without value, with `children` at the nibbles set in `mask`.
+fn branch_rlp(mask: u16, children: &[Vec<u8>]) -> Vec<u8> {
+    let empty_children = 16 - children.len();
+    let payload_length = children.iter().map(Vec::len).sum::<usize>() + empty_children + 1;
+    let mut out = Vec::new();
+    Header {
+        list: true,
+        payload_length,
+    }
+    .encode(&mut out);
+    let mut children = children.iter();
+    for nibble in 0..16 {
+        match mask & (1 << nibble) {
+            0 => out.push(alloy_rlp::EMPTY_STRING_CODE),
+            _ => out.extend_from_slice(children.next().expect("branch child")),
+        }
+    }
+    out.push(alloy_rlp::EMPTY_STRING_CODE);
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy::primitives::U256;
+    use eth_trie::{EthTrie, MemoryDB, Trie};
+    use proptest::prelude::*;
+    use std::sync::Arc;
+
+    fn eth_trie(values: &[Vec<u8>]) -> EthTrie<MemoryDB> {
+        let mut trie = EthTrie::new(Arc::new(MemoryDB::new(true)));
+        for (index, value) in values.iter().enumerate() {
+            trie.insert(&alloy_rlp::encode(U256::from(index)), value)
+                .unwrap();
+        }
+        trie
+    }
+
+    fn assert_matches_eth_trie(values: &[Vec<u8>]) {
+        let indices: Vec<u64> = (0..values.len() as u64).collect();
+        let built = IndexedTrieBuilder::new()
+            .with_proofs(indices.clone())
+            .build(values);
+
+        let mut trie = eth_trie(values);
+        assert_eq!(built.root.as_slice(), trie.root_hash().unwrap().as_bytes());
+        for index in indices {
+            let key = alloy_rlp::encode(U256::from(index));
+            assert_eq!(
+                built.proof(index).unwrap(),
+                trie.get_proof(&key).unwrap(),
+                "proof of index {index}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_sorted_indices() {
+        let keys: Vec<Vec<u8>> = sorted_indices(300).map(alloy_rlp::encode).collect();
+        let mut sorted = keys.clone();
+        sorted.sort();
+        assert_eq!(keys, sorted);
+        assert_eq!(keys.len(), 300);
+        assert_eq!(sorted_indices(0).count(), 0);
+        assert_eq!(sorted_indices(1).collect::<Vec<_>>(), vec![0]);
+    }
+
+    #[test]
+    fn test_indexed_trie_matches_eth_trie() {
+        assert_eq!(
+            IndexedTrieBuilder::new().build::<Vec<u8>>(&[]).root,
+            EMPTY_ROOT_HASH
+        );
+        for len in [1, 2, 3, 16, 17, 127, 128, 129, 255, 256, 1000] {
+            // Short values give nodes inlined in their parent, long values hashed nodes.
+            let short: Vec<Vec<u8>> = (0..len).map(|i| vec![i as u8]).collect();
+            let long: Vec<Vec<u8>> = (0..len).map(|i| vec![i as u8; 40]).collect();
+            assert_matches_eth_trie(&short);
+            assert_matches_eth_trie(&long);
+        }
+    }
+
+    #[test]
+    fn test_indexed_trie_requested_proofs() {
+        let values: Vec<Vec<u8>> = (0..500u32).map(|i| i.to_be_bytes().repeat(10)).collect();
+        let built = IndexedTrieBuilder::new()
+            .with_proofs([3, 200, 200, 1000])
+            .build(&values);
+        let trie = eth_trie(&values);
+
+        let proof = built.proof(200).unwrap().to_vec();
+        let key = alloy_rlp::encode(U256::from(200));
+        let value = trie
+            .verify_proof(
+                ethereum_types::H256::from_slice(built.root.as_slice()),
+                &key,
+                proof,
+            )
+            .unwrap();
+        assert_eq!(value, Some(values[200].clone()));
+        assert!(built.proof(3).is_some());
+        assert!(built.proof(4).is_none());
+        assert!(built.proof(1000).is_none());
+    }
+
+    proptest! {
+        #[test]
+        fn test_indexed_trie_random_values(
+            values in prop::collection::vec(prop::collection::vec(any::<u8>(), 1..64), 0..300)
+        ) {
+            assert_matches_eth_trie(&values);
+        }
+    }
+}
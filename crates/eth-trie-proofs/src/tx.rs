@@ -1,17 +1,126 @@
 use crate::error::{EthTrieError, Field};
 use alloy::consensus::{
-    SignableTransaction, TxEip1559, TxEip2930, TxEip4844, TxEnvelope, TxLegacy, TxType,
+    SignableTransaction, Signed, TxEip1559, TxEip2930, TxEip4844, TxEnvelope, TxLegacy, TxType,
 };
 use alloy::consensus::{Transaction as ConsensusTransaction, TxEip4844Variant};
-use alloy::eips::eip2718::Decodable2718;
+use alloy::eips::eip2718::{Decodable2718, Eip2718Result};
 use alloy::eips::eip2930::AccessList;
 use alloy::eips::eip2930::AccessListItem;
+use alloy::eips::eip4844::BlobTransactionSidecar;
 use alloy::network::eip2718::Encodable2718;
-use alloy::primitives::{ChainId, FixedBytes, Parity, Signature, TxKind, U256};
-use alloy::rpc::types::Transaction;
+use alloy::primitives::{
+    Address, ChainId, FixedBytes, Parity, Sealed, Signature, TxKind, B256, U256,
+};
+use alloy::rpc::types::serde_helpers::WithOtherFields;
+use alloy::rpc::types::{Parity as RpcParity, Transaction};
+use alloy_rlp::BufMut;
+use serde::de::DeserializeOwned;
+
+use crate::arbitrum::{
+    ArbitrumContractTx, ArbitrumDepositTx, ArbitrumInternalTx, ArbitrumRetryTx,
+    ArbitrumSubmitRetryableTx, ArbitrumTx, ArbitrumUnsignedTx, ARBITRUM_CONTRACT_TX_TYPE,
+    ARBITRUM_DEPOSIT_TX_TYPE, ARBITRUM_INTERNAL_TX_TYPE, ARBITRUM_RETRY_TX_TYPE,
+    ARBITRUM_SUBMIT_RETRYABLE_TX_TYPE, ARBITRUM_UNSIGNED_TX_TYPE,
+};
+use crate::chain::{ChainSpec, ChainTxType};
+use crate::op::{TxDeposit, DEPOSIT_TX_TYPE};
+
+/// Transaction envelope covering Ethereum transactions and the transaction types added
+/// by other EVM chains.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ChainTxEnvelope {
+    Ethereum(TxEnvelope),
+    /// OP Stack deposit transaction, sealed with its hash.
+    OpDeposit(Sealed<TxDeposit>),
+    /// Arbitrum specific transaction, sealed with its hash.
+    Arbitrum(Sealed<ArbitrumTx>),
+}
+
+impl ChainTxEnvelope {
+    pub fn tx_hash(&self) -> B256 {
+        match self {
+            ChainTxEnvelope::Ethereum(tx) => *tx.tx_hash(),
+            ChainTxEnvelope::OpDeposit(tx) => tx.seal(),
+            ChainTxEnvelope::Arbitrum(tx) => tx.seal(),
+        }
+    }
+}
+
+impl From<TxEnvelope> for ChainTxEnvelope {
+    fn from(tx: TxEnvelope) -> Self {
+        ChainTxEnvelope::Ethereum(tx)
+    }
+}
+
+impl<T> From<Signed<T>> for ChainTxEnvelope
+where
+    TxEnvelope: From<Signed<T>>,
+{
+    fn from(tx: Signed<T>) -> Self {
+        ChainTxEnvelope::Ethereum(tx.into())
+    }
+}
+
+impl From<TxDeposit> for ChainTxEnvelope {
+    fn from(tx: TxDeposit) -> Self {
+        let hash = tx.tx_hash();
+        ChainTxEnvelope::OpDeposit(Sealed::new_unchecked(tx, hash))
+    }
+}
+
+impl From<ArbitrumTx> for ChainTxEnvelope {
+    fn from(tx: ArbitrumTx) -> Self {
+        let hash = tx.tx_hash();
+        ChainTxEnvelope::Arbitrum(Sealed::new_unchecked(tx, hash))
+    }
+}
+
+impl Encodable2718 for ChainTxEnvelope {
+    fn type_flag(&self) -> Option<u8> {
+        match self {
+            ChainTxEnvelope::Ethereum(tx) => tx.type_flag(),
+            ChainTxEnvelope::OpDeposit(_) => Some(DEPOSIT_TX_TYPE),
+            ChainTxEnvelope::Arbitrum(tx) => Some(tx.tx_type()),
+        }
+    }
+
+    fn encode_2718_len(&self) -> usize {
+        match self {
+            ChainTxEnvelope::Ethereum(tx) => tx.encode_2718_len(),
+            ChainTxEnvelope::OpDeposit(tx) => tx.encode_2718_len(),
+            ChainTxEnvelope::Arbitrum(tx) => tx.encode_2718_len(),
+        }
+    }
+
+    fn encode_2718(&self, out: &mut dyn BufMut) {
+        match self {
+            ChainTxEnvelope::Ethereum(tx) => tx.encode_2718(out),
+            ChainTxEnvelope::OpDeposit(tx) => tx.encode_2718(out),
+            ChainTxEnvelope::Arbitrum(tx) => tx.encode_2718(out),
+        }
+    }
+
+    fn trie_hash(&self) -> B256 {
+        self.tx_hash()
+    }
+}
+
+impl Decodable2718 for ChainTxEnvelope {
+    fn typed_decode(ty: u8, buf: &mut &[u8]) -> Eip2718Result<Self> {
+        match ChainTxType::try_from(ty) {
+            Ok(ChainTxType::OpDeposit) => Ok(TxDeposit::rlp_decode(buf)?.into()),
+            Ok(ChainTxType::Arbitrum(ty)) => Ok(ArbitrumTx::typed_decode(ty, buf)?.into()),
+            _ => Ok(TxEnvelope::typed_decode(ty, buf)?.into()),
+        }
+    }
+
+    fn fallback_decode(buf: &mut &[u8]) -> Eip2718Result<Self> {
+        Ok(TxEnvelope::fallback_decode(buf)?.into())
+    }
+}
 
 #[derive(Debug, Clone)]
-pub struct ConsensusTx(pub TxEnvelope);
+pub struct ConsensusTx(pub ChainTxEnvelope);
 
 impl ConsensusTx {
     pub fn rlp_encode(&self) -> Vec<u8> {
@@ -19,12 +128,42 @@ impl ConsensusTx {
     }
 
     pub fn rlp_decode(mut data: &[u8]) -> Result<Self, EthTrieError> {
-        let tx = TxEnvelope::decode_2718(&mut data).map_err(EthTrieError::Eip)?;
+        let tx = ChainTxEnvelope::decode_2718(&mut data).map_err(EthTrieError::Eip)?;
         Ok(ConsensusTx(tx))
     }
 
-    pub fn nonce(&self) -> u64 {
+    /// Decodes a transaction, failing if its type does not exist on the chain.
+    pub fn rlp_decode_for_chain(data: &[u8], spec: &ChainSpec) -> Result<Self, EthTrieError> {
+        if let Some(ty) = data.first().filter(|ty| **ty < 0x7f) {
+            spec.tx_type(*ty)?;
+        }
+        Self::rlp_decode(data)
+    }
+
+    /// Returns the OP Stack deposit transaction, if the transaction is one.
+    pub fn deposit(&self) -> Option<&TxDeposit> {
+        match &self.0 {
+            ChainTxEnvelope::OpDeposit(tx) => Some(tx.inner()),
+            _ => None,
+        }
+    }
+
+    /// Returns the Arbitrum specific transaction, if the transaction is one.
+    pub fn arbitrum(&self) -> Option<&ArbitrumTx> {
         match &self.0 {
+            ChainTxEnvelope::Arbitrum(tx) => Some(tx.inner()),
+            _ => None,
+        }
+    }
+
+    pub fn nonce(&self) -> u64 {
+        let tx = match &self.0 {
+            ChainTxEnvelope::Ethereum(tx) => tx,
+            // Deposits have no nonce
+            ChainTxEnvelope::OpDeposit(_) => return 0,
+            ChainTxEnvelope::Arbitrum(tx) => return tx.nonce(),
+        };
+        match tx {
             TxEnvelope::Legacy(tx) => tx.tx().nonce(),
             TxEnvelope::Eip2930(tx) => tx.tx().nonce(),
             TxEnvelope::Eip1559(tx) => tx.tx().nonce(),
@@ -34,7 +173,12 @@ impl ConsensusTx {
     }
 
     pub fn gas_limit(&self) -> u64 {
-        match &self.0 {
+        let tx = match &self.0 {
+            ChainTxEnvelope::Ethereum(tx) => tx,
+            ChainTxEnvelope::OpDeposit(tx) => return tx.gas_limit,
+            ChainTxEnvelope::Arbitrum(tx) => return tx.gas_limit(),
+        };
+        match tx {
             TxEnvelope::Legacy(tx) => tx.tx().gas_limit(),
             TxEnvelope::Eip2930(tx) => tx.tx().gas_limit(),
             TxEnvelope::Eip1559(tx) => tx.tx().gas_limit(),
@@ -44,7 +188,11 @@ impl ConsensusTx {
     }
 
     pub fn gas_price(&self) -> Option<u128> {
-        match &self.0 {
+        let tx = match &self.0 {
+            ChainTxEnvelope::Ethereum(tx) => tx,
+            ChainTxEnvelope::OpDeposit(_) | ChainTxEnvelope::Arbitrum(_) => return None,
+        };
+        match tx {
             TxEnvelope::Legacy(tx) => tx.tx().gas_price(),
             TxEnvelope::Eip2930(tx) => tx.tx().gas_price(),
             TxEnvelope::Eip1559(tx) => tx.tx().gas_price(),
@@ -54,7 +202,12 @@ impl ConsensusTx {
     }
 
     pub fn to(&self) -> TxKind {
-        match &self.0 {
+        let tx = match &self.0 {
+            ChainTxEnvelope::Ethereum(tx) => tx,
+            ChainTxEnvelope::OpDeposit(tx) => return tx.to,
+            ChainTxEnvelope::Arbitrum(tx) => return tx.to(),
+        };
+        match tx {
             TxEnvelope::Legacy(tx) => tx.tx().to(),
             TxEnvelope::Eip2930(tx) => tx.tx().to(),
             TxEnvelope::Eip1559(tx) => tx.tx().to(),
@@ -64,7 +217,12 @@ impl ConsensusTx {
     }
 
     pub fn value(&self) -> U256 {
-        match &self.0 {
+        let tx = match &self.0 {
+            ChainTxEnvelope::Ethereum(tx) => tx,
+            ChainTxEnvelope::OpDeposit(tx) => return tx.value,
+            ChainTxEnvelope::Arbitrum(tx) => return tx.value(),
+        };
+        match tx {
             TxEnvelope::Legacy(tx) => tx.tx().value(),
             TxEnvelope::Eip2930(tx) => tx.tx().value(),
             TxEnvelope::Eip1559(tx) => tx.tx().value(),
@@ -74,7 +232,12 @@ impl ConsensusTx {
     }
 
     pub fn input(&self) -> &[u8] {
-        match &self.0 {
+        let tx = match &self.0 {
+            ChainTxEnvelope::Ethereum(tx) => tx,
+            ChainTxEnvelope::OpDeposit(tx) => return &tx.input,
+            ChainTxEnvelope::Arbitrum(tx) => return tx.input(),
+        };
+        match tx {
             TxEnvelope::Legacy(tx) => tx.tx().input(),
             TxEnvelope::Eip2930(tx) => tx.tx().input(),
             TxEnvelope::Eip1559(tx) => tx.tx().input(),
@@ -84,7 +247,12 @@ impl ConsensusTx {
     }
 
     pub fn v(&self) -> u64 {
-        match &self.0 {
+        let tx = match &self.0 {
+            ChainTxEnvelope::Ethereum(tx) => tx,
+            // Deposits and Arbitrum specific transactions are not signed
+            ChainTxEnvelope::OpDeposit(_) | ChainTxEnvelope::Arbitrum(_) => return 0,
+        };
+        match tx {
             TxEnvelope::Legacy(tx) => tx.signature().v().to_u64(),
             TxEnvelope::Eip2930(tx) => tx.signature().v().to_u64(),
             TxEnvelope::Eip1559(tx) => tx.signature().v().to_u64(),
@@ -94,7 +262,11 @@ impl ConsensusTx {
     }
 
     pub fn r(&self) -> U256 {
-        match &self.0 {
+        let tx = match &self.0 {
+            ChainTxEnvelope::Ethereum(tx) => tx,
+            ChainTxEnvelope::OpDeposit(_) | ChainTxEnvelope::Arbitrum(_) => return U256::ZERO,
+        };
+        match tx {
             TxEnvelope::Legacy(tx) => tx.signature().r(),
             TxEnvelope::Eip2930(tx) => tx.signature().r(),
             TxEnvelope::Eip1559(tx) => tx.signature().r(),
@@ -104,7 +276,11 @@ impl ConsensusTx {
     }
 
     pub fn s(&self) -> U256 {
-        match &self.0 {
+        let tx = match &self.0 {
+            ChainTxEnvelope::Ethereum(tx) => tx,
+            ChainTxEnvelope::OpDeposit(_) | ChainTxEnvelope::Arbitrum(_) => return U256::ZERO,
+        };
+        match tx {
             TxEnvelope::Legacy(tx) => tx.signature().s(),
             TxEnvelope::Eip2930(tx) => tx.signature().s(),
             TxEnvelope::Eip1559(tx) => tx.signature().s(),
@@ -114,7 +290,13 @@ impl ConsensusTx {
     }
 
     pub fn sender(&self) -> Result<alloy::primitives::Address, alloy::primitives::SignatureError> {
-        match &self.0 {
+        let tx = match &self.0 {
+            ChainTxEnvelope::Ethereum(tx) => tx,
+            // The sender of deposits is set by the derivation, not recovered
+            ChainTxEnvelope::OpDeposit(tx) => return Ok(tx.from),
+            ChainTxEnvelope::Arbitrum(tx) => return Ok(tx.from()),
+        };
+        match tx {
             TxEnvelope::Legacy(tx) => tx.recover_signer(),
             TxEnvelope::Eip2930(tx) => tx.recover_signer(),
             TxEnvelope::Eip1559(tx) => tx.recover_signer(),
@@ -124,7 +306,12 @@ impl ConsensusTx {
     }
 
     pub fn chain_id(&self) -> Option<ChainId> {
-        match &self.0 {
+        let tx = match &self.0 {
+            ChainTxEnvelope::Ethereum(tx) => tx,
+            ChainTxEnvelope::OpDeposit(_) => return None,
+            ChainTxEnvelope::Arbitrum(tx) => return u64::try_from(tx.chain_id()).ok(),
+        };
+        match tx {
             TxEnvelope::Legacy(tx) => tx.tx().chain_id(),
             TxEnvelope::Eip2930(tx) => tx.tx().chain_id(),
             TxEnvelope::Eip1559(tx) => tx.tx().chain_id(),
@@ -134,7 +321,11 @@ impl ConsensusTx {
     }
 
     pub fn access_list(&self) -> Option<AccessList> {
-        match &self.0 {
+        let tx = match &self.0 {
+            ChainTxEnvelope::Ethereum(tx) => tx,
+            ChainTxEnvelope::OpDeposit(_) | ChainTxEnvelope::Arbitrum(_) => return None,
+        };
+        match tx {
             TxEnvelope::Legacy(_) => None,
             TxEnvelope::Eip2930(tx) => Some(tx.tx().access_list.clone()),
             TxEnvelope::Eip1559(tx) => Some(tx.tx().access_list.clone()),
@@ -147,7 +338,14 @@ impl ConsensusTx {
     }
 
     pub fn max_fee_per_gas(&self) -> Option<u128> {
-        match &self.0 {
+        let tx = match &self.0 {
+            ChainTxEnvelope::Ethereum(tx) => tx,
+            ChainTxEnvelope::OpDeposit(_) => return None,
+            ChainTxEnvelope::Arbitrum(tx) => {
+                return tx.gas_fee_cap().map(|cap| cap.saturating_to())
+            }
+        };
+        match tx {
             TxEnvelope::Legacy(_) => None,
             TxEnvelope::Eip2930(_) => None,
             TxEnvelope::Eip1559(tx) => Some(tx.tx().max_fee_per_gas),
@@ -160,7 +358,11 @@ impl ConsensusTx {
     }
 
     pub fn max_priority_fee_per_gas(&self) -> Option<u128> {
-        match &self.0 {
+        let tx = match &self.0 {
+            ChainTxEnvelope::Ethereum(tx) => tx,
+            ChainTxEnvelope::OpDeposit(_) | ChainTxEnvelope::Arbitrum(_) => return None,
+        };
+        match tx {
             TxEnvelope::Legacy(_) => None,
             TxEnvelope::Eip2930(_) => None,
             TxEnvelope::Eip1559(tx) => Some(tx.tx().max_priority_fee_per_gas),
@@ -175,7 +377,11 @@ impl ConsensusTx {
     }
 
     pub fn blob_versioned_hashes(&self) -> Option<Vec<FixedBytes<32>>> {
-        match &self.0 {
+        let tx = match &self.0 {
+            ChainTxEnvelope::Ethereum(tx) => tx,
+            ChainTxEnvelope::OpDeposit(_) | ChainTxEnvelope::Arbitrum(_) => return None,
+        };
+        match tx {
             TxEnvelope::Legacy(_) => None,
             TxEnvelope::Eip2930(_) => None,
             TxEnvelope::Eip1559(_) => None,
@@ -190,7 +396,11 @@ impl ConsensusTx {
     }
 
     pub fn max_fee_per_blob_gas(&self) -> Option<u128> {
-        match &self.0 {
+        let tx = match &self.0 {
+            ChainTxEnvelope::Ethereum(tx) => tx,
+            ChainTxEnvelope::OpDeposit(_) | ChainTxEnvelope::Arbitrum(_) => return None,
+        };
+        match tx {
             TxEnvelope::Legacy(_) => None,
             TxEnvelope::Eip2930(_) => None,
             TxEnvelope::Eip1559(_) => None,
@@ -201,27 +411,72 @@ impl ConsensusTx {
             _ => todo!(),
         }
     }
+
+    /// Returns the blob sidecar carried by a type-3 transaction in its network form.
+    pub fn blob_sidecar(&self) -> Option<&BlobTransactionSidecar> {
+        match &self.0 {
+            ChainTxEnvelope::Ethereum(TxEnvelope::Eip4844(tx)) => match tx.tx() {
+                TxEip4844Variant::TxEip4844(_) => None,
+                TxEip4844Variant::TxEip4844WithSidecar(tx) => Some(&tx.sidecar),
+            },
+            _ => None,
+        }
+    }
+
+    /// Drops the blob sidecar of a type-3 transaction, as the transactions trie only
+    /// commits to its versioned hashes.
+    pub fn without_blob_sidecar(self) -> Self {
+        match self.0 {
+            ChainTxEnvelope::Ethereum(TxEnvelope::Eip4844(tx)) => {
+                let (tx, signature, hash) = tx.into_parts();
+                let tx = match tx {
+                    TxEip4844Variant::TxEip4844WithSidecar(tx) => {
+                        TxEip4844Variant::TxEip4844(tx.tx)
+                    }
+                    tx => tx,
+                };
+                ConsensusTx(Signed::new_unchecked(tx, signature, hash).into())
+            }
+            tx => ConsensusTx(tx),
+        }
+    }
 }
 
 #[derive(Debug, Clone)]
-pub(crate) struct RpcTx(pub Transaction);
+pub(crate) struct RpcTx(pub WithOtherFields<Transaction>);
 
 impl TryFrom<RpcTx> for ConsensusTx {
     type Error = EthTrieError;
     fn try_from(tx: RpcTx) -> Result<ConsensusTx, EthTrieError> {
-        let chain_id = tx.chain_id();
-        let nonce: u64 = tx.0.nonce;
-        let gas_limit: u64 = tx.0.gas;
+        let spec = ChainSpec::generic(tx.chain_id().unwrap_or_default());
+        tx.into_consensus_tx(&spec)
+    }
+}
+
+impl RpcTx {
+    /// Converts the RPC transaction, failing if its type does not exist on the chain.
+    pub(crate) fn into_consensus_tx(self, spec: &ChainSpec) -> Result<ConsensusTx, EthTrieError> {
+        let tx_type = match spec.tx_type(self.0.transaction_type.unwrap_or_default())? {
+            ChainTxType::Ethereum(tx_type) => tx_type,
+            ChainTxType::OpDeposit => return Ok(ConsensusTx(self.deposit()?.into())),
+            ChainTxType::Arbitrum(ty) => return Ok(ConsensusTx(self.arbitrum(ty)?.into())),
+        };
 
-        let value = tx.0.value;
-        let input = tx.0.input.clone();
-        match &tx.version()? {
+        let chain_id = self.chain_id();
+        let nonce: u64 = self.0.nonce;
+        let gas_limit: u64 = self.0.gas;
+
+        let value = self.0.value;
+        let input = self.0.input.clone();
+        match tx_type {
             TxType::Legacy => {
-                let to = tx.to();
-                let gas_price: u128 = tx.0.gas_price.unwrap_or_default();
+                let to = self.to();
+                let gas_price: u128 = self.0.gas_price.unwrap_or_default();
+                let signature = self.signature(tx_type)?;
 
                 let res = TxLegacy {
-                    chain_id,
+                    // Only EIP-155 transactions commit to a chain id, found in their `v`.
+                    chain_id: signature.v().chain_id(),
                     nonce,
                     gas_price,
                     gas_limit,
@@ -229,11 +484,11 @@ impl TryFrom<RpcTx> for ConsensusTx {
                     value,
                     input,
                 };
-                Ok(ConsensusTx(res.into_signed(tx.signature()?).into()))
+                Ok(ConsensusTx(res.into_signed(signature).into()))
             }
             TxType::Eip2930 => {
-                let to = tx.to();
-                let gas_price: u128 = tx.0.gas_price.unwrap_or_default();
+                let to = self.to();
+                let gas_price: u128 = self.0.gas_price.unwrap_or_default();
 
                 let res = TxEip2930 {
                     chain_id: chain_id.unwrap(),
@@ -243,14 +498,16 @@ impl TryFrom<RpcTx> for ConsensusTx {
                     to,
                     value,
                     input,
-                    access_list: tx.access_list()?,
+                    access_list: self.access_list()?,
                 };
-                Ok(ConsensusTx(res.into_signed(tx.signature()?).into()))
+                Ok(ConsensusTx(
+                    res.into_signed(self.signature(tx_type)?).into(),
+                ))
             }
             TxType::Eip1559 => {
-                let to = tx.to();
-                let max_fee_per_gas = tx.max_fee_per_gas()?;
-                let max_priority_fee_per_gas = tx.max_priority_fee_per_gas()?;
+                let to = self.to();
+                let max_fee_per_gas = self.max_fee_per_gas()?;
+                let max_priority_fee_per_gas = self.max_priority_fee_per_gas()?;
                 let res = TxEip1559 {
                     chain_id: chain_id.unwrap(),
                     nonce,
@@ -258,25 +515,27 @@ impl TryFrom<RpcTx> for ConsensusTx {
                     to,
                     value,
                     input,
-                    access_list: tx.access_list()?,
+                    access_list: self.access_list()?,
                     max_fee_per_gas,
                     max_priority_fee_per_gas,
                 };
-                Ok(ConsensusTx(res.into_signed(tx.signature()?).into()))
+                Ok(ConsensusTx(
+                    res.into_signed(self.signature(tx_type)?).into(),
+                ))
             }
             TxType::Eip4844 => {
-                let to = match tx.to() {
+                let to = match self.to() {
                     TxKind::Call(to) => to,
                     TxKind::Create => return Err(EthTrieError::InvalidTxVersion),
                 };
-                let blob_versioned_hashes = tx
-                    .clone()
+                let blob_versioned_hashes = self
                     .0
                     .blob_versioned_hashes
+                    .clone()
                     .ok_or(EthTrieError::ConversionError(Field::Input))?;
-                let max_fee_per_gas = tx.max_fee_per_gas()?;
-                let max_priority_fee_per_gas = tx.max_priority_fee_per_gas()?;
-                let max_fee_per_blob_gas = tx.max_fee_per_blob_gas()?;
+                let max_fee_per_gas = self.max_fee_per_gas()?;
+                let max_priority_fee_per_gas = self.max_priority_fee_per_gas()?;
+                let max_fee_per_blob_gas = self.max_fee_per_blob_gas()?;
 
                 let res = TxEip4844 {
                     chain_id: chain_id.unwrap(),
@@ -285,20 +544,20 @@ impl TryFrom<RpcTx> for ConsensusTx {
                     to,
                     value,
                     input,
-                    access_list: tx.access_list()?,
+                    access_list: self.access_list()?,
                     max_fee_per_gas,
                     max_priority_fee_per_gas,
                     max_fee_per_blob_gas,
                     blob_versioned_hashes,
                 };
-                Ok(ConsensusTx(res.into_signed(tx.signature()?).into()))
+                Ok(ConsensusTx(
+                    res.into_signed(self.signature(tx_type)?).into(),
+                ))
             }
             TxType::Eip7702 => todo!(),
         }
     }
-}
 
-impl RpcTx {
     fn chain_id(&self) -> Option<u64> {
         self.0.chain_id
     }
@@ -310,15 +569,135 @@ impl RpcTx {
         }
     }
 
-    fn version(&self) -> Result<TxType, EthTrieError> {
-        match self.0.transaction_type {
-            Some(0) => Ok(TxType::Legacy),
-            Some(1) => Ok(TxType::Eip2930),
-            Some(2) => Ok(TxType::Eip1559),
-            Some(3) => Ok(TxType::Eip4844),
-            None => Ok(TxType::Legacy),
-            _ => Err(EthTrieError::InvalidTxVersion),
-        }
+    /// Converts an OP Stack deposit, whose specific fields are extra fields of the RPC transaction.
+    fn deposit(&self) -> Result<TxDeposit, EthTrieError> {
+        let other = &self.0.other;
+        let source_hash = other
+            .get_deserialized::<B256>("sourceHash")
+            .and_then(Result::ok)
+            .ok_or(EthTrieError::ConversionError(Field::SourceHash))?;
+        let mint = match other.get_deserialized::<U256>("mint") {
+            Some(mint) => Some(
+                mint.ok()
+                    .and_then(|mint| u128::try_from(mint).ok())
+                    .ok_or(EthTrieError::ConversionError(Field::Mint))?,
+            ),
+            None => None,
+        };
+        let is_system_transaction = match other.get_deserialized::<bool>("isSystemTx") {
+            Some(is_system_transaction) => is_system_transaction
+                .map_err(|_| EthTrieError::ConversionError(Field::IsSystemTx))?,
+            None => false,
+        };
+
+        Ok(TxDeposit {
+            source_hash,
+            from: self.0.from,
+            to: self.to(),
+            mint: mint.filter(|mint| *mint != 0),
+            value: self.0.value,
+            gas_limit: self.0.gas,
+            is_system_transaction,
+            input: self.0.input.clone(),
+        })
+    }
+
+    /// Converts an Arbitrum specific transaction, whose specific fields are extra fields of
+    /// the RPC transaction.
+    fn arbitrum(&self, ty: u8) -> Result<ArbitrumTx, EthTrieError> {
+        let chain_id = U256::from(
+            self.chain_id()
+                .ok_or(EthTrieError::ConversionError(Field::ChainId))?,
+        );
+        let from = self.0.from;
+        let gas_fee_cap = U256::from(self.max_fee_per_gas()?);
+        let gas = self.0.gas;
+        let data = self.0.input.clone();
+
+        let tx = match ty {
+            ARBITRUM_DEPOSIT_TX_TYPE => ArbitrumTx::Deposit(ArbitrumDepositTx {
+                chain_id,
+                l1_request_id: self.other_field("requestId", Field::RequestId)?,
+                from,
+                to: self.0.to.ok_or(EthTrieError::ConversionError(Field::To))?,
+                value: self.0.value,
+            }),
+            ARBITRUM_UNSIGNED_TX_TYPE => ArbitrumTx::Unsigned(ArbitrumUnsignedTx {
+                chain_id,
+                from,
+                nonce: self.0.nonce,
+                gas_fee_cap,
+                gas,
+                to: self.to(),
+                value: self.0.value,
+                data,
+            }),
+            ARBITRUM_CONTRACT_TX_TYPE => ArbitrumTx::Contract(ArbitrumContractTx {
+                chain_id,
+                request_id: self.other_field("requestId", Field::RequestId)?,
+                from,
+                gas_fee_cap,
+                gas,
+                to: self.to(),
+                value: self.0.value,
+                data,
+            }),
+            ARBITRUM_RETRY_TX_TYPE => ArbitrumTx::Retry(ArbitrumRetryTx {
+                chain_id,
+                nonce: self.0.nonce,
+                from,
+                gas_fee_cap,
+                gas,
+                to: self.to(),
+                value: self.0.value,
+                data,
+                ticket_id: self.other_field("ticketId", Field::TicketId)?,
+                refund_to: self.other_field("refundTo", Field::RefundTo)?,
+                max_refund: self.other_field("maxRefund", Field::MaxRefund)?,
+                submission_fee_refund: self
+                    .other_field("submissionFeeRefund", Field::SubmissionFeeRefund)?,
+            }),
+            // The RPC transaction describes the call to the ArbRetryableTx precompile, the
+            // ticket is described by the extra fields.
+            ARBITRUM_SUBMIT_RETRYABLE_TX_TYPE => {
+                let retry_to = self
+                    .0
+                    .other
+                    .get_deserialized::<Address>("retryTo")
+                    .transpose()
+                    .map_err(|_| EthTrieError::ConversionError(Field::To))?;
+                ArbitrumTx::SubmitRetryable(ArbitrumSubmitRetryableTx {
+                    chain_id,
+                    request_id: self.other_field("requestId", Field::RequestId)?,
+                    from,
+                    l1_base_fee: self.other_field("l1BaseFee", Field::L1BaseFee)?,
+                    deposit_value: self.other_field("depositValue", Field::DepositValue)?,
+                    gas_fee_cap,
+                    gas,
+                    retry_to: retry_to.into(),
+                    retry_value: self.other_field("retryValue", Field::RetryValue)?,
+                    beneficiary: self.other_field("beneficiary", Field::Beneficiary)?,
+                    max_submission_fee: self
+                        .other_field("maxSubmissionFee", Field::MaxSubmissionFee)?,
+                    fee_refund_addr: self.other_field("refundTo", Field::RefundTo)?,
+                    retry_data: self.other_field("retryData", Field::RetryData)?,
+                })
+            }
+            ARBITRUM_INTERNAL_TX_TYPE => {
+                ArbitrumTx::Internal(ArbitrumInternalTx { chain_id, data })
+            }
+            _ => return Err(EthTrieError::InvalidTxVersion),
+        };
+        Ok(tx)
+    }
+
+    /// Deserializes an extra field of the RPC transaction.
+    fn other_field<T: DeserializeOwned>(&self, key: &str, field: Field) -> Result<T, EthTrieError> {
+        self.0
+            .other
+            .get_deserialized(key)
+            .and_then(Result::ok)
+            .ok_or(EthTrieError::ConversionError(field))
     }
 
     fn max_fee_per_gas(&self) -> Result<u128, EthTrieError> {
@@ -345,24 +724,60 @@ impl RpcTx {
         }
     }
 
-    fn signature(&self) -> Result<Signature, EthTrieError> {
-        if let Some(signature) = self.0.signature {
-            let sig = Signature::from_rs_and_parity(
+    /// Reconstructs the signature, with the parity encoding of the transaction type.
+    ///
+    /// Legacy transactions keep their `v`: 27 or 28 before EIP-155, and
+    /// `chain_id * 2 + 35 + y_parity` after. Typed transactions only carry the y parity, taken
+    /// from `yParity` or else from `v`. When the RPC returns no `v`, the signature is left in
+    /// the extra fields.
+    ///
+    /// Legacy `v` values overflowing u64 are valid EIP-155 signatures of chain ids above
+    /// 2^63 - 18, but the consensus types only hold u64 chain ids, so they are reported as
+    /// [`EthTrieError::UnsupportedSignatureV`].
+    fn signature(&self, tx_type: TxType) -> Result<Signature, EthTrieError> {
+        let error = || EthTrieError::ConversionError(Field::Signature);
+        let (r, s, v, y_parity) = match self.0.signature {
+            Some(signature) => (
                 signature.r,
                 signature.s,
-                Parity::Eip155(
-                    signature
-                        .v
-                        .try_into()
-                        .map_err(|_| EthTrieError::ConversionError(Field::Signature))?,
-                ),
-            )
-            .map_err(|_| EthTrieError::ConversionError(Field::Signature))?;
-
-            Ok(sig)
-        } else {
-            Err(EthTrieError::ConversionError(Field::Signature))
-        }
+                Some(signature.v),
+                signature.y_parity.map(|y_parity| y_parity.0),
+            ),
+            None => {
+                let other = &self.0.other;
+                let v = other
+                    .get_deserialized::<U256>("v")
+                    .transpose()
+                    .map_err(|_| error())?;
+                let y_parity = other
+                    .get_deserialized::<RpcParity>("yParity")
+                    .transpose()
+                    .map_err(|_| error())?
+                    .map(|y_parity| y_parity.0);
+                (
+                    self.other_field("r", Field::Signature)?,
+                    self.other_field("s", Field::Signature)?,
+                    v,
+                    y_parity,
+                )
+            }
+        };
+
+        let parity = match tx_type {
+            TxType::Legacy => match v.map(u64::try_from) {
+                Some(Ok(v @ (27 | 28))) => Parity::NonEip155(v == 28),
+                Some(Ok(v @ 35..)) => Parity::Eip155(v),
+                Some(Err(_)) => return Err(EthTrieError::UnsupportedSignatureV(v.unwrap())),
+                _ => return Err(error()),
+            },
+            _ => match (y_parity, v.map(u64::try_from)) {
+                (Some(y_parity), _) => Parity::Parity(y_parity),
+                (None, Some(Ok(0 | 27))) => Parity::Parity(false),
+                (None, Some(Ok(1 | 28))) => Parity::Parity(true),
+                _ => return Err(error()),
+            },
+        };
+        Signature::from_rs_and_parity(r, s, parity).map_err(|_| error())
     }
 
     fn access_list(&self) -> Result<AccessList, EthTrieError> {
@@ -374,3 +789,477 @@ impl RpcTx {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use alloy::primitives::{keccak256, Bytes};
+    use proptest::prelude::*;
+
+    use super::*;
+    use crate::rpc::RpcProvider;
+
+    const MAINNET_RPC_URL: &str = "https://ethereum-rpc.publicnode.com";
+
+    #[test]
+    fn test_chain_tx_envelope() {
+        let deposit = TxDeposit {
+            source_hash: B256::repeat_byte(0x01),
+            from: Address::repeat_byte(0x02),
+            to: TxKind::Call(Address::repeat_byte(0x03)),
+            mint: Some(7),
+            value: U256::from(7),
+            gas_limit: 100_000,
+            is_system_transaction: false,
+            input: Bytes::new(),
+        };
+        let tx = ConsensusTx(deposit.clone().into());
+        let encoded = tx.rlp_encode();
+        assert_eq!(encoded[0], DEPOSIT_TX_TYPE);
+        assert_eq!(tx.0.tx_hash(), keccak256(&encoded));
+        assert_eq!(tx.0.trie_hash(), tx.0.tx_hash());
+
+        let decoded = ConsensusTx::rlp_decode(&encoded).unwrap();
+        assert_eq!(decoded.deposit(), Some(&deposit));
+        assert_eq!(decoded.sender().unwrap(), deposit.from);
+        assert_eq!(decoded.nonce(), 0);
+        assert_eq!(decoded.to(), deposit.to);
+
+        let legacy = TxLegacy {
+            chain_id: Some(1),
+            nonce: 3,
+            gas_price: 10,
+            gas_limit: 21_000,
+            to: TxKind::Call(Address::repeat_byte(0x11)),
+            value: U256::from(5),
+            input: Default::default(),
+        };
+        let signature =
+            Signature::from_rs_and_parity(U256::from(1234), U256::from(5678), Parity::Eip155(37))
+                .unwrap();
+        let tx = ConsensusTx(legacy.into_signed(signature).into());
+        let decoded = ConsensusTx::rlp_decode(&tx.rlp_encode()).unwrap();
+        assert_eq!(decoded.0, tx.0);
+        assert_eq!(decoded.deposit(), None);
+        assert_eq!(decoded.nonce(), 3);
+    }
+
+    fn rpc_tx(tx: serde_json::Value) -> RpcTx {
+        let mut rpc_tx = serde_json::json!({
+            "hash": "0x0000000000000000000000000000000000000000000000000000000000000001",
+            "nonce": "0x0",
+            "blockHash": null,
+            "blockNumber": null,
+            "transactionIndex": null,
+            "from": "0x0202020202020202020202020202020202020202",
+            "value": "0x0",
+            "gas": "0x0",
+            "input": "0x",
+            "chainId": "0xa4b1",
+        });
+        rpc_tx
+            .as_object_mut()
+            .unwrap()
+            .extend(tx.as_object().unwrap().clone());
+        RpcTx(serde_json::from_value(rpc_tx).unwrap())
+    }
+
+    #[test]
+    fn test_arbitrum_rpc_conversion() {
+        let retry = rpc_tx(serde_json::json!({
+            "type": "0x68",
+            "nonce": "0x1",
+            "gas": "0x7a120",
+            "maxFeePerGas": "0x5f5e100",
+            "to": "0x0505050505050505050505050505050505050505",
+            "value": "0x5",
+            "input": "0xabcd",
+            "ticketId": "0x0606060606060606060606060606060606060606060606060606060606060606",
+            "refundTo": "0x0707070707070707070707070707070707070707",
+            "maxRefund": "0x3e8",
+            "submissionFeeRefund": "0xa",
+        }));
+        let tx = ConsensusTx::try_from(retry).unwrap();
+        let ArbitrumTx::Retry(retry) = tx.arbitrum().unwrap() else {
+            panic!("expected a retry transaction");
+        };
+        assert_eq!(retry.chain_id, U256::from(42161));
+        assert_eq!(retry.nonce, 1);
+        assert_eq!(retry.ticket_id, B256::repeat_byte(0x06));
+        assert_eq!(retry.max_refund, U256::from(1000));
+        assert_eq!(tx.sender().unwrap(), Address::repeat_byte(0x02));
+
+        let encoded = tx.rlp_encode();
+        assert_eq!(encoded[0], ARBITRUM_RETRY_TX_TYPE);
+        assert_eq!(tx.0.tx_hash(), keccak256(&encoded));
+        assert_eq!(ConsensusTx::rlp_decode(&encoded).unwrap().0, tx.0);
+
+        // Ticket creating a contract, without `retryTo`
+        let submit_retryable = serde_json::json!({
+            "type": "0x69",
+            "gas": "0x7a120",
+            "maxFeePerGas": "0x5f5e100",
+            "to": "0x000000000000000000000000000000000000006e",
+            "requestId": "0x0808080808080808080808080808080808080808080808080808080808080808",
+            "l1BaseFee": "0x6fc23ac00",
+            "depositValue": "0xf4240",
+            "retryValue": "0x0",
+            "retryData": "0x6080",
+            "beneficiary": "0x0909090909090909090909090909090909090909",
+            "maxSubmissionFee": "0x7d0",
+            "refundTo": "0x0707070707070707070707070707070707070707",
+        });
+        let tx = ConsensusTx::try_from(rpc_tx(submit_retryable.clone())).unwrap();
+        assert_eq!(tx.to(), TxKind::Create);
+        assert_eq!(tx.input(), &[0x60, 0x80]);
+
+        let mut invalid = submit_retryable;
+        invalid.as_object_mut().unwrap().remove("requestId");
+        assert!(ConsensusTx::try_from(rpc_tx(invalid)).is_err());
+    }
+
+    /// How an RPC returns the signature parity.
+    #[derive(Debug, Clone, Copy)]
+    enum RpcParityFields {
+        V,
+        YParity,
+        Both,
+        /// Typed transaction returned with a legacy style `v` of 27 or 28.
+        LegacyV,
+    }
+
+    fn rpc_signed_tx(
+        tx_type: u8,
+        chain_id: u64,
+        r: U256,
+        s: U256,
+        v: U256,
+        y_parity: bool,
+        fields: RpcParityFields,
+    ) -> RpcTx {
+        let mut tx = serde_json::json!({
+            "type": format!("{tx_type:#x}"),
+            "chainId": format!("{chain_id:#x}"),
+            "nonce": "0x7",
+            "gas": "0x5208",
+            "gasPrice": "0x3b9aca00",
+            "maxFeePerGas": "0x3b9aca00",
+            "maxPriorityFeePerGas": "0x1",
+            "maxFeePerBlobGas": "0x1",
+            "blobVersionedHashes": [B256::repeat_byte(0x01)],
+            "accessList": [],
+            "to": "0x0303030303030303030303030303030303030303",
+            "value": "0x2a",
+            "r": r,
+            "s": s,
+        });
+        let fields_mut = tx.as_object_mut().unwrap();
+        let y_parity = serde_json::json!(if y_parity { "0x1" } else { "0x0" });
+        match fields {
+            RpcParityFields::V => {
+                fields_mut.insert("v".into(), serde_json::json!(v));
+            }
+            RpcParityFields::YParity => {
+                fields_mut.insert("yParity".into(), y_parity);
+            }
+            RpcParityFields::Both => {
+                fields_mut.insert("v".into(), serde_json::json!(v));
+                fields_mut.insert("yParity".into(), y_parity);
+            }
+            RpcParityFields::LegacyV => {
+                fields_mut.insert("v".into(), serde_json::json!(v + U256::from(27)));
+            }
+        }
+        rpc_tx(tx)
+    }
+
+    /// Checks that a converted transaction round-trips through its trie encoding.
+    fn assert_round_trip(tx: &ConsensusTx) {
+        let encoded = tx.rlp_encode();
+        let decoded = ConsensusTx::rlp_decode(&encoded).unwrap();
+        assert_eq!(decoded.0, tx.0);
+        assert_eq!(decoded.0.trie_hash(), keccak256(&encoded));
+        assert_eq!(decoded.0.trie_hash(), tx.0.tx_hash());
+    }
+
+    #[test]
+    fn test_legacy_signature_parity() {
+        let (r, s) = (U256::from(1234), U256::from(5678));
+
+        // Before EIP-155, `v` is 27 or 28 and no chain id is committed to
+        for v in [27u64, 28] {
+            let tx: ConsensusTx =
+                rpc_signed_tx(0, 1, r, s, U256::from(v), false, RpcParityFields::V)
+                    .try_into()
+                    .unwrap();
+            assert_eq!(tx.v(), v);
+            assert_eq!(tx.chain_id(), None);
+            assert_round_trip(&tx);
+        }
+
+        // The chain id of EIP-155 transactions comes from `v`
+        let tx: ConsensusTx = rpc_signed_tx(0, 1, r, s, U256::from(38), false, RpcParityFields::V)
+            .try_into()
+            .unwrap();
+        assert_eq!(tx.v(), 38);
+        assert_eq!(tx.chain_id(), Some(1));
+        assert_round_trip(&tx);
+
+        // Largest chain id whose `v` fits in u64
+        let chain_id = (u64::MAX - 36) / 2;
+        let v = chain_id * 2 + 36;
+        let tx: ConsensusTx = rpc_signed_tx(
+            0,
+            chain_id,
+            r,
+            s,
+            U256::from(v),
+            true,
+            RpcParityFields::Both,
+        )
+        .try_into()
+        .unwrap();
+        assert_eq!(tx.v(), v);
+        assert_eq!(tx.chain_id(), Some(chain_id));
+        assert_round_trip(&tx);
+
+        // Invalid `v` values are rejected
+        for v in [U256::ZERO, U256::from(29)] {
+            let tx = rpc_signed_tx(0, 1, r, s, v, false, RpcParityFields::V);
+            assert!(matches!(
+                ConsensusTx::try_from(tx),
+                Err(EthTrieError::ConversionError(Field::Signature))
+            ));
+        }
+        // Chain ids whose `v` overflows u64 are not supported by the consensus types
+        let v = U256::from(u64::MAX) + U256::from(1);
+        let tx = rpc_signed_tx(0, 1, r, s, v, false, RpcParityFields::V);
+        assert!(matches!(
+            ConsensusTx::try_from(tx),
+            Err(EthTrieError::UnsupportedSignatureV(unsupported)) if unsupported == v
+        ));
+        // Legacy transactions need their `v`
+        let tx = rpc_signed_tx(0, 1, r, s, U256::ZERO, true, RpcParityFields::YParity);
+        assert!(ConsensusTx::try_from(tx).is_err());
+    }
+
+    #[test]
+    fn test_typed_signature_parity() {
+        let (r, s) = (U256::from(1234), U256::from(5678));
+        for tx_type in [1, 2, 3] {
+            let txs: Vec<ConsensusTx> = [
+                RpcParityFields::V,
+                RpcParityFields::YParity,
+                RpcParityFields::Both,
+                RpcParityFields::LegacyV,
+            ]
+            .into_iter()
+            .map(|fields| {
+                rpc_signed_tx(tx_type, 1, r, s, U256::from(1), true, fields)
+                    .try_into()
+                    .unwrap()
+            })
+            .collect();
+            for tx in &txs {
+                assert_eq!(tx.v(), 1);
+                assert_eq!(tx.0.tx_hash(), txs[0].0.tx_hash());
+                assert_round_trip(tx);
+            }
+        }
+    }
+
+    proptest! {
+        #[test]
+        fn test_signature_round_trip(
+            tx_type in 0u8..=3,
+            chain_id in 1u64..=(u64::MAX - 36) / 2,
+            eip155 in any::<bool>(),
+            y_parity in any::<bool>(),
+            r in any::<[u8; 32]>(),
+            s in any::<[u8; 32]>(),
+            fields in 0usize..4,
+        ) {
+            let (r, s) = (U256::from_be_bytes(r), U256::from_be_bytes(s));
+            let fields = [
+                RpcParityFields::V,
+                RpcParityFields::YParity,
+                RpcParityFields::Both,
+                RpcParityFields::LegacyV,
+            ][fields];
+            let (v, fields) = match tx_type {
+                0 if eip155 => (chain_id * 2 + 35 + y_parity as u64, RpcParityFields::V),
+                0 => (27 + y_parity as u64, RpcParityFields::V),
+                _ => (y_parity as u64, fields),
+            };
+
+            let tx: ConsensusTx =
+                rpc_signed_tx(tx_type, chain_id, r, s, U256::from(v), y_parity, fields)
+                    .try_into()
+                    .unwrap();
+            prop_assert_eq!(tx.v(), v);
+            prop_assert_eq!(tx.r(), r);
+            prop_assert_eq!(tx.s(), s);
+            let expected_chain_id = (tx_type != 0 || eip155).then_some(chain_id);
+            prop_assert_eq!(tx.chain_id(), expected_chain_id);
+            assert_round_trip(&tx);
+        }
+    }
+
+    /// Signed transactions as returned by the RPC: a Frontier transaction of mainnet block
+    /// 1000000, an EIP-155 legacy transaction, a London (EIP-1559) transaction, and Dencun
+    /// blob transactions of mainnet and Sepolia.
+    /// Blocks of the EIP-155 activation and of Berlin are only covered over the RPC, by
+    /// `test_historical_signatures`.
+    const SIGNED_TXS: &str = r#"[
+        {
+            "hash": "0xe9e91f1ee4b56c0df2e9f06c2b8c27c6076195a88a7b8537ba8313d80e6f124e",
+            "blockHash": "0x8e38b4dbf6b11fcc3b9dee84fb7986e29ca0a02cecd8977c161ff7333329681e",
+            "blockNumber": "0xf4240",
+            "transactionIndex": "0x1",
+            "type": "0x0",
+            "nonce": "0x43eb",
+            "from": "0x32be343b94f860124dc4fee278fdcbd38c102d88",
+            "to": "0xdf190dc7190dfba737d7777a163445b7fff16133",
+            "value": "0x6113a84987be800",
+            "gasPrice": "0xdf8475800",
+            "gas": "0xc350",
+            "input": "0x",
+            "r": "0x3b08715b4403c792b8c7567edea634088bedcd7f60d9352b1f16c69830f3afd5",
+            "s": "0x10b9afb67d2ec8b956f0e1dbc07eb79152904f3a7bf789fc869db56320adfe09",
+            "v": "0x1c"
+        },
+        {
+            "hash": "0x280cde7cdefe4b188750e76c888f13bd05ce9a4d7767730feefe8a0e50ca6fc4",
+            "blockHash": null,
+            "blockNumber": null,
+            "transactionIndex": null,
+            "type": "0x0",
+            "chainId": "0x1",
+            "nonce": "0x78b",
+            "from": "0xa12e1462d0ced572f396f58b6e2d03894cd7c8a4",
+            "to": "0x7a250d5630b4cf539739df2c5dacb4c659f2488d",
+            "value": "0xc46549a521b13d8",
+            "gasPrice": "0x5d21dba00",
+            "gas": "0x22ef1",
+            "input": "0x7ff36ab50000000000000000000000000000000000000000000066ab5a608bd00a23f2fe000000000000000000000000000000000000000000000000000000000000008000000000000000000000000048c04ed5691981c42154c6167398f95e8f38a7ff00000000000000000000000000000000000000000000000000000000632ceac70000000000000000000000000000000000000000000000000000000000000002000000000000000000000000c02aaa39b223fe8d0a0e5c4f27ead9083c756cc20000000000000000000000006c6ee5e31d828de241282b9606c8e98ea48526e2",
+            "r": "0xc9077369501641a92ef7399ff81c21639ed4fd8fc69cb793cfa1dbfab342e10a",
+            "s": "0x615facb2f1bcf3274a354cfe384a38d0cc008a11c2dd23a69111bc6930ba27a8",
+            "v": "0x25"
+        },
+        {
+            "hash": "0xce4dc6d7a7549a98ee3b071b67e970879ff51b5b95d1c340bacd80fa1e1aab31",
+            "blockHash": null,
+            "blockNumber": null,
+            "transactionIndex": null,
+            "type": "0x2",
+            "chainId": "0x1",
+            "nonce": "0x2",
+            "from": "0x001e2b7de757ba469a57bf6b23d982458a07efce",
+            "to": "0xd9e1459a7a482635700cbc20bbaf52d495ab9c96",
+            "value": "0x0",
+            "maxPriorityFeePerGas": "0x3b9aca00",
+            "maxFeePerGas": "0x29e7822d6",
+            "gas": "0x98f0",
+            "input": "0x1b55ba3a",
+            "accessList": [],
+            "r": "0xc199674fcb29f353693dd779c017823b954b3c69dffa3cd6b2a6ff7888798039",
+            "s": "0x28ca912de909e7e6cdef9cdcaf24c54dd8c1032946dfa1d85c206b32a9064fe8",
+            "v": "0x0",
+            "yParity": "0x0"
+        },
+        {
+            "hash": "0x93fc9daaa0726c3292a2e939df60f7e773c6a6a726a61ce43f4a217c64d85e87",
+            "blockHash": null,
+            "blockNumber": null,
+            "transactionIndex": null,
+            "type": "0x3",
+            "chainId": "0x1",
+            "nonce": "0x3c4b",
+            "from": "0x0d3250c3d5facb74ac15834096397a3ef790ec99",
+            "to": "0xa8cb082a5a689e0d594d7da1e2d72a3d63adc1bd",
+            "value": "0x0",
+            "maxPriorityFeePerGas": "0x3b9aca00",
+            "maxFeePerGas": "0x27618393c",
+            "maxFeePerBlobGas": "0x1",
+            "gas": "0x7a1200",
+            "input": "0x701f58c50000000000000000000000000000000000000000000000000000000000073fb1ed12e288def5b439ea074b398dbb4c967f2852baac3238c5fe4b62b871a59a6d00000000000000000000000000000000000000000000000000000000123971da000000000000000000000000000000000000000000000000000000000000000ac39b2a24e1dbdd11a1e7bd7c0f4dfd7d9b9cfa0997d033ad05f961ba3b82c6c83312c967f10daf5ed2bffe309249416e03ee0b101f2b84d2102b9e38b0e4dfdf0000000000000000000000000000000000000000000000000000000066254c8b538dcc33ecf5334bbd294469f9d4fd084a3090693599a46d6c62567747cbc8660000000000000000000000000000000000000000000000000000000000000120000000000000000000000000000000000000000000000000000000000000000100000000000000000000000000000000000000000000000000000000000000200000000000000000000000000000000000000000000000000000000000073fb20000000000000000000000000000000000000000000000000000000066254da10000000000000000000000000000000000000000000000000000000012397d5e20b09b263779fda4171c341e720af8fa469621ff548651f8dbbc06c2d320400c000000000000000000000000000000000000000000000000000000000000000b50a833bb11af92814e99c6ff7cf7ba7042827549d6f306a04270753702d897d8fc3c411b99159939ac1c16d21d3057ddc8b2333d1331ab34c938cff0eb29ce2e43241c170344db6819f76b1f1e0ab8206f3ec34120312d275c4f5bbea7f5c55700000000000000000000000000000000000000000000000000000000000001400000000000000000000000000000000000000000000000000000000000000480000000000000000000000000000000000000000000000000000000000000031800000000000000000000000000000000000000000000800b0000000000000000000000000000000000000000000000000000000000000004ed12e288def5b439ea074b398dbb4c967f2852baac3238c5fe4b62b871a59a6d00000ca8000000000000000000000000000000000000800b000000000000000000000000000000000000000000000000000000000000000300000000000000000000000066254da100000000000000000000000066254e9d00010ca80000000000000000000000000000000000008001000000000000000000000000000000000000000000000000000000000000000550a833bb11af92814e99c6ff7cf7ba7042827549d6f306a04270753702d897d800010ca800000000000000000000000000000000000080010000000000000000000000000000000000000000000000000000000000000006000000000000000000000000000000000000000000000000000000000000000b00010ca8000000000000000000000000000000000000801100000000000000000000000000000000000000000000000000000000000000075c1cd5bd0fd333ce9d7c8edfc79f43b8f345b4a394f6aba12a2cc78ce4012ed700010ca80000000000000000000000000000000000008011000000000000000000000000000000000000000000000000000000000000000845392775318aa47beaafbdc827da38c9f1e88c3bdcabba2cb493062e17cbf21e00010ca800000000000000000000000000000000000080080000000000000000000000000000000000000000000000000000000000000000c094e20e7ac9b433f44a5885e3bdc07e51b309aeb993caa24ba84a661ac010c100010ca800000000000000000000000000000000000080080000000000000000000000000000000000000000000000000000000000000001ab42db8f4ed810bdb143368a2b641edf242af6e3d0de8b1486e2b0e7880d431100010ca8000000000000000000000000000000000000800800000000000000000000000000000000000000000000000000000000000000022d94e4cc4525e4e2d81e8227b6172e97076431a2cf98792d978035edd6e6f3100000000000000000000000000000000000000000000000000000000000000000000000000000012101c74dfb80a80fccb9a4022b2406f79f56305e6a7c931d30140f5d372fe793837e93f9ec6b8d89a9d0ab222eeb27547f66b90ec40fbbdd2a4936b0b0c19ca684ff78888fbf5840d7c8dc3c493b139471750938d7d2c443e2d283e6c5ee9fde3765a756542c42f002af45c362b4b5b1687a8fc24cbf16532b903f7bb289728170dcf597f5255508c623ba247735538376f494cdcdd5bd0c4cb067526eeda0f4745a28d8baf8893ecc1b8cee80690538d66455294a028da03ff2add9d8a88e6ee03ba9ffe3ad7d91d6ac9c69a1f28c468f00fe55eba5651a2b32dc2458e0d14b4dd6d0173df255cd56aa01e8e38edec17ea8933f68543cbdc713279d195551d4211bed5c91f77259a695e6768f6c4b110b2158fcc42423a96dcc4e7f6fddb3e2369d00000000000000000000000000000000000000000000000000000000000000",
+            "accessList": [],
+            "blobVersionedHashes": [
+                "0x01e5276d91ac1ddb3b1c2d61295211220036e9a04be24c00f76916cc2659d004",
+                "0x0128eb58aff09fd3a7957cd80aa86186d5849569997cdfcfa23772811b706cc2"
+            ],
+            "r": "0x6c173c3c8db3e3299f2f728d293b912c12e75243e3aa66911c2329b58434e2a4",
+            "s": "0x7dd4d1c228cedc5a414a668ab165d9e888e61e4c3b44cd7daf9cdcc4cec5d6b2",
+            "v": "0x0",
+            "yParity": "0x0"
+        },
+        {
+            "hash": "0x9a22ccb0029bc8b0ddd073be1a1d923b7ae2b2ea52100bae0db4424f9107e9c0",
+            "blockHash": null,
+            "blockNumber": null,
+            "transactionIndex": null,
+            "type": "0x3",
+            "chainId": "0xaa36a7",
+            "nonce": "0xfa2",
+            "from": "0xa83c816d4f9b2783761a22ba6fadb0eb0606d7b2",
+            "to": "0x11e9ca82a3a762b4b5bd264d4173a242e7a77064",
+            "value": "0x0",
+            "maxPriorityFeePerGas": "0x77359400",
+            "maxFeePerGas": "0x2e90edd000",
+            "maxFeePerBlobGas": "0x4a817c800",
+            "gas": "0x5208",
+            "input": "0x",
+            "accessList": [],
+            "blobVersionedHashes": [
+                "0x012ec3d6f66766bedb002a190126b3549fce0047de0d4c25cffce0dc1c57921a",
+                "0x0152d8e24762ff22b1cfd9f8c0683786a7ca63ba49973818b3d1e9512cd2cec4",
+                "0x013b98c6c83e066d5b14af2b85199e3d4fc7d1e778dd53130d180f5077e2d1c7",
+                "0x01148b495d6e859114e670ca54fb6e2657f0cbae5b08063605093a4b3dc9f8f1",
+                "0x011ac212f13c5dff2b2c6b600a79635103d6f580a4221079951181b25c7e6549"
+            ],
+            "r": "0xc8de4cced43169f9aa3d36506363b2d2c44f6c49fc1fd91ea114c86f3757077e",
+            "s": "0x1e11fdd0d1934eda0492606ee0bb80a7bf8f35cc5f86ec60fe5031ba48bfd544",
+            "v": "0x1",
+            "yParity": "0x1"
+        }
+    ]"#;
+
+    #[test]
+    fn test_signed_txs() {
+        let rpc_txs: Vec<WithOtherFields<Transaction>> = serde_json::from_str(SIGNED_TXS).unwrap();
+        let mut vs = vec![];
+        for rpc_tx in rpc_txs {
+            let tx: ConsensusTx = RpcTx(rpc_tx.clone()).try_into().unwrap();
+            assert_eq!(tx.0.tx_hash(), rpc_tx.hash);
+            assert_eq!(tx.sender().unwrap(), rpc_tx.from);
+            assert_round_trip(&tx);
+            vs.push((tx.v(), tx.chain_id()));
+        }
+        assert_eq!(
+            vs,
+            [
+                (28, None),
+                (37, Some(1)),
+                (0, Some(1)),
+                (0, Some(1)),
+                (1, Some(11155111))
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_historical_signatures() {
+        let url = url::Url::parse(MAINNET_RPC_URL).unwrap();
+        let provider = RpcProvider::new(url);
+
+        // Frontier, EIP-155 activation, Berlin, London and Dencun blocks
+        for block_number in [46147, 2675000, 12244000, 12965000, 19426589] {
+            let (txs, _) = provider.get_block_transactions(block_number).await.unwrap();
+            for rpc_tx in txs {
+                let tx: ConsensusTx = RpcTx(rpc_tx.clone()).try_into().unwrap();
+                assert_eq!(tx.0.tx_hash(), rpc_tx.hash);
+                assert_eq!(tx.sender().unwrap(), rpc_tx.from);
+                assert_round_trip(&tx);
+            }
+        }
+    }
+}
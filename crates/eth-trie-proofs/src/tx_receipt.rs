@@ -1,14 +1,110 @@
-use crate::error::EthTrieError;
+use crate::chain::{ChainSpec, ChainTxType};
+use crate::error::{EthTrieError, Field};
+use crate::op::{OpDepositReceipt, DEPOSIT_TX_TYPE};
 use alloy::consensus::{Eip658Value, Receipt, ReceiptWithBloom, TxReceipt};
 use alloy::consensus::{ReceiptEnvelope, TxType};
-use alloy::eips::eip2718::Decodable2718;
+use alloy::eips::eip2718::{Decodable2718, Eip2718Result};
 use alloy::network::eip2718::Encodable2718;
 
-use alloy::primitives::{Bloom, Log, LogData};
-use alloy::rpc::types::{Log as RpcLog, TransactionReceipt};
+use alloy::primitives::{Bloom, Log, LogData, B256, U64};
+use alloy::rpc::types::{AnyTransactionReceipt, Log as RpcLog};
+use alloy_rlp::{BufMut, Encodable, Header};
+
+/// Receipt envelope covering Ethereum receipts and the receipt types added by other EVM
+/// chains.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ChainReceiptEnvelope {
+    Ethereum(ReceiptEnvelope),
+    /// Receipt of an OP Stack deposit transaction.
+    OpDeposit(OpDepositReceipt),
+}
+
+impl From<ReceiptEnvelope> for ChainReceiptEnvelope {
+    fn from(receipt: ReceiptEnvelope) -> Self {
+        ChainReceiptEnvelope::Ethereum(receipt)
+    }
+}
+
+impl From<OpDepositReceipt> for ChainReceiptEnvelope {
+    fn from(receipt: OpDepositReceipt) -> Self {
+        ChainReceiptEnvelope::OpDeposit(receipt)
+    }
+}
+
+impl ChainReceiptEnvelope {
+    /// Returns the RLP header, intermediate state root and contents of a legacy receipt
+    /// carrying a state root instead of a status (pre-Byzantium).
+    ///
+    /// `ReceiptWithBloom` counts the root without its RLP header, so such receipts are encoded
+    /// from their contents instead.
+    fn post_state_receipt(&self) -> Option<(Header, B256, &ReceiptWithBloom<Log>)> {
+        let ChainReceiptEnvelope::Ethereum(ReceiptEnvelope::Legacy(receipt)) = self else {
+            return None;
+        };
+        let Eip658Value::PostState(root) = receipt.receipt.status else {
+            return None;
+        };
+        let payload_length = root.length()
+            + receipt.receipt.cumulative_gas_used.length()
+            + receipt.logs_bloom.length()
+            + receipt.receipt.logs.length();
+        let header = Header {
+            list: true,
+            payload_length,
+        };
+        Some((header, root, receipt))
+    }
+}
+
+impl Encodable2718 for ChainReceiptEnvelope {
+    fn type_flag(&self) -> Option<u8> {
+        match self {
+            ChainReceiptEnvelope::Ethereum(receipt) => receipt.type_flag(),
+            ChainReceiptEnvelope::OpDeposit(_) => Some(DEPOSIT_TX_TYPE),
+        }
+    }
+
+    fn encode_2718_len(&self) -> usize {
+        if let Some((header, ..)) = self.post_state_receipt() {
+            return header.length() + header.payload_length;
+        }
+        match self {
+            ChainReceiptEnvelope::Ethereum(receipt) => receipt.encode_2718_len(),
+            ChainReceiptEnvelope::OpDeposit(receipt) => receipt.encode_2718_len(),
+        }
+    }
+
+    fn encode_2718(&self, out: &mut dyn BufMut) {
+        if let Some((header, root, receipt)) = self.post_state_receipt() {
+            header.encode(out);
+            root.encode(out);
+            receipt.receipt.cumulative_gas_used.encode(out);
+            receipt.logs_bloom.encode(out);
+            receipt.receipt.logs.encode(out);
+            return;
+        }
+        match self {
+            ChainReceiptEnvelope::Ethereum(receipt) => receipt.encode_2718(out),
+            ChainReceiptEnvelope::OpDeposit(receipt) => receipt.encode_2718(out),
+        }
+    }
+}
+
+impl Decodable2718 for ChainReceiptEnvelope {
+    fn typed_decode(ty: u8, buf: &mut &[u8]) -> Eip2718Result<Self> {
+        match ChainTxType::try_from(ty) {
+            Ok(ChainTxType::OpDeposit) => Ok(OpDepositReceipt::rlp_decode(buf)?.into()),
+            _ => Ok(ReceiptEnvelope::typed_decode(ty, buf)?.into()),
+        }
+    }
+
+    fn fallback_decode(buf: &mut &[u8]) -> Eip2718Result<Self> {
+        Ok(ReceiptEnvelope::fallback_decode(buf)?.into())
+    }
+}
 
 #[derive(Debug, Clone)]
-pub struct ConsensusTxReceipt(pub ReceiptEnvelope);
+pub struct ConsensusTxReceipt(pub ChainReceiptEnvelope);
 
 impl ConsensusTxReceipt {
     pub fn rlp_encode(&self) -> Vec<u8> {
@@ -16,12 +112,34 @@ impl ConsensusTxReceipt {
     }
 
     pub fn rlp_decode(mut data: &[u8]) -> Result<Self, EthTrieError> {
-        let envelope = ReceiptEnvelope::decode_2718(&mut data).map_err(EthTrieError::Eip)?;
+        let envelope = ChainReceiptEnvelope::decode_2718(&mut data).map_err(EthTrieError::Eip)?;
         Ok(ConsensusTxReceipt(envelope))
     }
 
-    pub fn status(&self) -> Eip658Value {
+    /// Decodes a receipt, failing if its type does not exist on the chain.
+    pub fn rlp_decode_for_chain(data: &[u8], spec: &ChainSpec) -> Result<Self, EthTrieError> {
+        if let Some(ty) = data.first().filter(|ty| **ty < 0x7f) {
+            spec.tx_type(*ty)?;
+        }
+        Self::rlp_decode(data)
+    }
+
+    /// Returns the OP Stack deposit receipt, if the receipt is one.
+    pub fn deposit(&self) -> Option<&OpDepositReceipt> {
         match &self.0 {
+            ChainReceiptEnvelope::OpDeposit(receipt) => Some(receipt),
+            ChainReceiptEnvelope::Ethereum(_) => None,
+        }
+    }
+
+    pub fn status(&self) -> Eip658Value {
+        let receipt = match &self.0 {
+            ChainReceiptEnvelope::Ethereum(receipt) => receipt,
+            ChainReceiptEnvelope::OpDeposit(receipt) => {
+                return receipt.inner.receipt.status_or_post_state()
+            }
+        };
+        match receipt {
             ReceiptEnvelope::Legacy(receipt) => receipt.receipt.status_or_post_state(),
             ReceiptEnvelope::Eip2930(receipt) => receipt.receipt.status_or_post_state(),
             ReceiptEnvelope::Eip1559(receipt) => receipt.receipt.status_or_post_state(),
@@ -31,7 +149,13 @@ impl ConsensusTxReceipt {
     }
 
     pub fn cumulative_gas_used(&self) -> u128 {
-        match &self.0 {
+        let receipt = match &self.0 {
+            ChainReceiptEnvelope::Ethereum(receipt) => receipt,
+            ChainReceiptEnvelope::OpDeposit(receipt) => {
+                return receipt.inner.receipt.cumulative_gas_used
+            }
+        };
+        match receipt {
             ReceiptEnvelope::Legacy(receipt) => receipt.receipt.cumulative_gas_used,
             ReceiptEnvelope::Eip2930(receipt) => receipt.receipt.cumulative_gas_used,
             ReceiptEnvelope::Eip1559(receipt) => receipt.receipt.cumulative_gas_used,
@@ -41,7 +165,11 @@ impl ConsensusTxReceipt {
     }
 
     pub fn logs(&self) -> Vec<Log<LogData>> {
-        match &self.0 {
+        let receipt = match &self.0 {
+            ChainReceiptEnvelope::Ethereum(receipt) => receipt,
+            ChainReceiptEnvelope::OpDeposit(receipt) => return receipt.inner.receipt.logs.clone(),
+        };
+        match receipt {
             ReceiptEnvelope::Legacy(receipt) => receipt.receipt.logs.clone(),
             ReceiptEnvelope::Eip2930(receipt) => receipt.receipt.logs.clone(),
             ReceiptEnvelope::Eip1559(receipt) => receipt.receipt.logs.clone(),
@@ -51,7 +179,11 @@ impl ConsensusTxReceipt {
     }
 
     pub fn bloom(&self) -> Bloom {
-        match &self.0 {
+        let receipt = match &self.0 {
+            ChainReceiptEnvelope::Ethereum(receipt) => receipt,
+            ChainReceiptEnvelope::OpDeposit(receipt) => return receipt.inner.bloom(),
+        };
+        match receipt {
             ReceiptEnvelope::Legacy(receipt) => receipt.bloom(),
             ReceiptEnvelope::Eip2930(receipt) => receipt.bloom(),
             ReceiptEnvelope::Eip1559(receipt) => receipt.bloom(),
@@ -62,77 +194,99 @@ impl ConsensusTxReceipt {
 }
 
 #[derive(Debug, Clone)]
-pub(crate) struct RpcTxReceipt(pub TransactionReceipt);
+pub(crate) struct RpcTxReceipt<'a> {
+    pub receipt: AnyTransactionReceipt,
+    /// Profile of the chain, deciding the receipt types and fields.
+    pub chain_spec: &'a ChainSpec,
+    /// Timestamp of the block, deciding the fields of OP Stack deposit receipts.
+    pub timestamp: u64,
+}
 
-impl TryFrom<RpcTxReceipt> for ConsensusTxReceipt {
+impl TryFrom<RpcTxReceipt<'_>> for ConsensusTxReceipt {
     type Error = EthTrieError;
-    fn try_from(tx: RpcTxReceipt) -> Result<ConsensusTxReceipt, EthTrieError> {
-        match &tx.version()? {
+    fn try_from(tx: RpcTxReceipt<'_>) -> Result<ConsensusTxReceipt, EthTrieError> {
+        let tx_type = match tx.chain_spec.tx_type(tx.receipt.inner.inner.r#type)? {
+            ChainTxType::Ethereum(tx_type) => tx_type,
+            ChainTxType::OpDeposit => return Ok(ConsensusTxReceipt(tx.deposit()?.into())),
+            // Receipts of Arbitrum specific transactions are not supported yet
+            ChainTxType::Arbitrum(_) => return Err(EthTrieError::InvalidTxVersion),
+        };
+
+        match tx_type {
             TxType::Legacy => {
                 let res = ReceiptEnvelope::Legacy(ReceiptWithBloom {
                     receipt: Receipt {
-                        status: Eip658Value::from(tx.status()),
+                        status: tx.status()?,
                         cumulative_gas_used: tx.cumulative_gas_used(),
                         logs: tx.logs(),
                     },
                     logs_bloom: tx.bloom(),
                 });
-                Ok(ConsensusTxReceipt(res))
+                Ok(ConsensusTxReceipt(res.into()))
             }
             TxType::Eip2930 => {
                 let res = ReceiptEnvelope::Eip2930(ReceiptWithBloom {
                     receipt: Receipt {
-                        status: Eip658Value::from(tx.status()),
+                        status: tx.status()?,
                         cumulative_gas_used: tx.cumulative_gas_used(),
                         logs: tx.logs(),
                     },
                     logs_bloom: tx.bloom(),
                 });
-                Ok(ConsensusTxReceipt(res))
+                Ok(ConsensusTxReceipt(res.into()))
             }
             TxType::Eip1559 => {
                 let res = ReceiptEnvelope::Eip1559(ReceiptWithBloom {
                     receipt: Receipt {
-                        status: Eip658Value::from(tx.status()),
+                        status: tx.status()?,
                         cumulative_gas_used: tx.cumulative_gas_used(),
                         logs: tx.logs(),
                     },
                     logs_bloom: tx.bloom(),
                 });
-                Ok(ConsensusTxReceipt(res))
+                Ok(ConsensusTxReceipt(res.into()))
             }
             TxType::Eip4844 => {
                 let res = ReceiptEnvelope::Eip4844(ReceiptWithBloom {
                     receipt: Receipt {
-                        status: Eip658Value::from(tx.status()),
+                        status: tx.status()?,
                         cumulative_gas_used: tx.cumulative_gas_used(),
                         logs: tx.logs(),
                     },
                     logs_bloom: tx.bloom(),
                 });
-                Ok(ConsensusTxReceipt(res))
+                Ok(ConsensusTxReceipt(res.into()))
             }
             TxType::Eip7702 => todo!(),
         }
     }
 }
 
-impl RpcTxReceipt {
-    fn version(&self) -> Result<TxType, EthTrieError> {
-        Ok(self.0.transaction_type())
-    }
-
-    fn status(&self) -> bool {
-        self.0.status()
+impl RpcTxReceipt<'_> {
+    /// Returns the status of the receipt, or its intermediate state root before Byzantium
+    /// (EIP-658), checked against the forks of the chain.
+    fn status(&self) -> Result<Eip658Value, EthTrieError> {
+        let status = self.receipt.inner.inner.status_or_post_state();
+        let byzantium = self.receipt.block_number.and_then(|block_number| {
+            self.chain_spec
+                .byzantium
+                .is_active_at(block_number, self.timestamp)
+        });
+        match (byzantium, status) {
+            (Some(true), Eip658Value::PostState(_)) | (Some(false), Eip658Value::Eip658(_)) => {
+                Err(EthTrieError::ConversionError(Field::Status))
+            }
+            _ => Ok(status),
+        }
     }
 
     fn cumulative_gas_used(&self) -> u128 {
-        self.0.inner.cumulative_gas_used()
+        self.receipt.inner.inner.cumulative_gas_used()
     }
 
     fn logs(&self) -> Vec<alloy::primitives::Log<LogData>> {
         let mut logs = Vec::new();
-        for log in self.0.inner.logs() {
+        for log in self.receipt.inner.inner.logs() {
             let rpc_log: RpcLog = log.clone();
             let result = rpc_log.inner;
             logs.push(result);
@@ -141,6 +295,193 @@ impl RpcTxReceipt {
     }
 
     fn bloom(&self) -> Bloom {
-        self.0.inner.bloom()
+        self.receipt.inner.inner.bloom()
+    }
+
+    /// Converts an OP Stack deposit receipt, whose specific fields are extra fields of the
+    /// RPC receipt.
+    fn deposit(&self) -> Result<OpDepositReceipt, EthTrieError> {
+        let other = &self.receipt.other;
+        let deposit_nonce = other
+            .get_deserialized::<U64>("depositNonce")
+            .transpose()
+            .map_err(|_| EthTrieError::ConversionError(Field::DepositNonce))?
+            .map(|nonce| nonce.to::<u64>());
+        let deposit_receipt_version = other
+            .get_deserialized::<U64>("depositReceiptVersion")
+            .transpose()
+            .map_err(|_| EthTrieError::ConversionError(Field::DepositReceiptVersion))?
+            .map(|version| version.to::<u64>());
+
+        let timestamp = self.timestamp;
+        let (deposit_nonce, deposit_receipt_version) = match self.chain_spec.op_hardforks {
+            Some(hardforks) => {
+                let deposit_nonce = if hardforks.is_regolith_active_at(timestamp) {
+                    Some(deposit_nonce.ok_or(EthTrieError::ConversionError(Field::DepositNonce))?)
+                } else {
+                    None
+                };
+                let deposit_receipt_version = if hardforks.is_canyon_active_at(timestamp) {
+                    Some(
+                        deposit_receipt_version
+                            .ok_or(EthTrieError::ConversionError(Field::DepositReceiptVersion))?,
+                    )
+                } else {
+                    None
+                };
+                (deposit_nonce, deposit_receipt_version)
+            }
+            None => (deposit_nonce, deposit_receipt_version),
+        };
+
+        Ok(OpDepositReceipt {
+            inner: ReceiptWithBloom {
+                receipt: Receipt {
+                    status: self.status()?,
+                    cumulative_gas_used: self.cumulative_gas_used(),
+                    logs: self.logs(),
+                },
+                logs_bloom: self.bloom(),
+            },
+            deposit_nonce,
+            deposit_receipt_version,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::op::OpHardforks;
+    use crate::rpc::deserialize_receipt;
+
+    fn rpc_legacy_receipt(block_number: u64) -> AnyTransactionReceipt {
+        let receipt = serde_json::json!({
+            "blockHash": "0x0000000000000000000000000000000000000000000000000000000000000001",
+            "blockNumber": format!("{block_number:#x}"),
+            "contractAddress": null,
+            "cumulativeGasUsed": "0x5208",
+            "effectiveGasPrice": "0x4a817c800",
+            "from": "0x0000000000000000000000000000000000000001",
+            "gasUsed": "0x5208",
+            "logs": [],
+            "logsBloom": format!("0x{}", "00".repeat(256)),
+            "root": format!("0x{}", "ab".repeat(32)),
+            "to": "0x0000000000000000000000000000000000000002",
+            "transactionHash": "0x0000000000000000000000000000000000000000000000000000000000000002",
+            "transactionIndex": "0x0",
+            "type": "0x0"
+        });
+        deserialize_receipt(receipt).unwrap()
+    }
+
+    fn rpc_deposit_receipt(chain_spec: &ChainSpec, timestamp: u64) -> RpcTxReceipt<'_> {
+        let receipt = serde_json::json!({
+            "blockHash": "0x0000000000000000000000000000000000000000000000000000000000000001",
+            "blockNumber": "0x1",
+            "contractAddress": null,
+            "cumulativeGasUsed": "0xb71b",
+            "depositNonce": "0x4d5",
+            "depositReceiptVersion": "0x1",
+            "effectiveGasPrice": "0x0",
+            "from": "0xdeaddeaddeaddeaddeaddeaddeaddeaddead0001",
+            "gasUsed": "0xb71b",
+            "logs": [],
+            "logsBloom": format!("0x{}", "00".repeat(256)),
+            "status": "0x1",
+            "to": "0x4200000000000000000000000000000000000015",
+            "transactionHash": "0x0000000000000000000000000000000000000000000000000000000000000002",
+            "transactionIndex": "0x0",
+            "type": "0x7e"
+        });
+        RpcTxReceipt {
+            receipt: serde_json::from_value(receipt).unwrap(),
+            chain_spec,
+            timestamp,
+        }
+    }
+
+    #[test]
+    fn test_op_deposit_receipt_conversion() {
+        let canyon = OpHardforks::OP_MAINNET.canyon_time;
+
+        let receipt: ConsensusTxReceipt = rpc_deposit_receipt(&ChainSpec::OP_MAINNET, canyon)
+            .try_into()
+            .unwrap();
+        let deposit = receipt.deposit().unwrap();
+        assert_eq!(deposit.deposit_nonce, Some(0x4d5));
+        assert_eq!(deposit.deposit_receipt_version, Some(1));
+        assert_eq!(receipt.cumulative_gas_used(), 0xb71b);
+
+        let encoded = receipt.rlp_encode();
+        assert_eq!(encoded[0], DEPOSIT_TX_TYPE);
+        let decoded = ConsensusTxReceipt::rlp_decode(&encoded).unwrap();
+        assert_eq!(decoded.0, receipt.0);
+
+        // Before Canyon, the receipt version is not part of the receipt.
+        let receipt: ConsensusTxReceipt = rpc_deposit_receipt(&ChainSpec::OP_MAINNET, canyon - 1)
+            .try_into()
+            .unwrap();
+        let deposit = receipt.deposit().unwrap();
+        assert_eq!(deposit.deposit_nonce, Some(0x4d5));
+        assert_eq!(deposit.deposit_receipt_version, None);
+
+        // Without known hardforks, the fields returned by the RPC are used.
+        let receipt: ConsensusTxReceipt = rpc_deposit_receipt(&ChainSpec::generic(10), canyon)
+            .try_into()
+            .unwrap();
+        assert_eq!(receipt.deposit().unwrap().deposit_receipt_version, Some(1));
+
+        // Fields required by an active hardfork must be returned by the RPC.
+        let chain_spec = ChainSpec::OP_MAINNET;
+        let mut rpc_receipt = rpc_deposit_receipt(&chain_spec, canyon);
+        rpc_receipt.receipt.other.remove("depositReceiptVersion");
+        assert!(ConsensusTxReceipt::try_from(rpc_receipt).is_err());
+    }
+
+    #[test]
+    fn test_pre_byzantium_receipt_conversion() {
+        let chain_spec = ChainSpec::MAINNET;
+        let receipt: ConsensusTxReceipt = RpcTxReceipt {
+            receipt: rpc_legacy_receipt(4369999),
+            chain_spec: &chain_spec,
+            timestamp: 0,
+        }
+        .try_into()
+        .unwrap();
+        assert_eq!(
+            receipt.status(),
+            Eip658Value::PostState(B256::repeat_byte(0xab))
+        );
+
+        // The intermediate state root is encoded in place of the status
+        let encoded = receipt.rlp_encode();
+        assert!(encoded
+            .windows(33)
+            .any(|window| window[0] == 0xa0 && window[1..] == [0xab; 32]));
+        let decoded = ConsensusTxReceipt::rlp_decode(&encoded).unwrap();
+        assert_eq!(decoded.0, receipt.0);
+
+        // Receipts must follow the forks of the chain
+        let rpc_receipt = RpcTxReceipt {
+            receipt: rpc_legacy_receipt(4370000),
+            chain_spec: &chain_spec,
+            timestamp: 0,
+        };
+        assert!(matches!(
+            ConsensusTxReceipt::try_from(rpc_receipt),
+            Err(EthTrieError::ConversionError(Field::Status))
+        ));
+
+        // Without known forks, the receipt is taken as returned by the RPC
+        let chain_spec = ChainSpec::generic(1);
+        let receipt: ConsensusTxReceipt = RpcTxReceipt {
+            receipt: rpc_legacy_receipt(4370000),
+            chain_spec: &chain_spec,
+            timestamp: 0,
+        }
+        .try_into()
+        .unwrap();
+        assert!(matches!(receipt.status(), Eip658Value::PostState(_)));
     }
 }
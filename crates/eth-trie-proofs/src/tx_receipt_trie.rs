@@ -2,12 +2,16 @@ use std::sync::Arc;
 
 use alloy::network::eip2718::Encodable2718;
 use alloy::primitives::{B256, U256};
+use alloy::rpc::types::{AnyTransactionReceipt, Header};
 use eth_trie::{EthTrie, MemoryDB, Trie};
 use ethereum_types::H256;
 use url::Url;
 
 use crate::{
+    chain::{ChainSpec, ChainSpecRegistry},
     error::EthTrieError,
+    polygon::bor_state_sync_tx_hash,
+    proof::ReceiptInclusionProof,
     rpc::RpcProvider,
     tx_receipt::{ConsensusTxReceipt, RpcTxReceipt},
 };
@@ -20,6 +24,9 @@ pub struct TxReceiptsMptHandler {
     /// Optional MPT structure to hold transaction receipts.
     /// If `None`, the trie has not been built yet.
     trie: Option<TxReceiptsMpt>,
+    /// Profile of the chain.
+    /// If `None`, it is resolved from the chain id of the provider when first needed.
+    chain_spec: Option<ChainSpec>,
 }
 
 /// The [`TxReceiptsMpt`] struct encapsulates the MPT (Merkle Patricia Trie) specifically for transaction receipts,
@@ -28,6 +35,8 @@ pub struct TxReceiptsMpt {
     pub trie: EthTrie<MemoryDB>,
     elements: Vec<ConsensusTxReceipt>,
     root: B256,
+    /// Number and hash of the block, known when the trie is built from a block.
+    block: Option<(u64, B256)>,
 }
 
 impl TxReceiptsMptHandler {
@@ -35,11 +44,34 @@ impl TxReceiptsMptHandler {
     ///
     /// This does not initialize the trie yet.
     pub fn new(url: Url) -> Result<Self, EthTrieError> {
-        let provider = RpcProvider::new(url);
-        Ok(Self {
+        Ok(Self::with_provider(RpcProvider::new(url), None))
+    }
+
+    /// Creates a new [`TxReceiptsMptHandler`] with a given RPC provider URL and chain profile.
+    ///
+    /// This does not initialize the trie yet.
+    pub fn new_with_chain_spec(url: Url, chain_spec: ChainSpec) -> Result<Self, EthTrieError> {
+        Ok(Self::with_provider(RpcProvider::new(url), Some(chain_spec)))
+    }
+
+    /// Creates a new [`TxReceiptsMptHandler`] sharing an existing provider.
+    pub(crate) fn with_provider(provider: RpcProvider, chain_spec: Option<ChainSpec>) -> Self {
+        Self {
             provider,
             trie: None,
-        })
+            chain_spec,
+        }
+    }
+
+    /// Returns the profile of the chain, resolving it from the built-in profiles if needed.
+    async fn resolve_chain_spec(&mut self) -> Result<ChainSpec, EthTrieError> {
+        if let Some(chain_spec) = &self.chain_spec {
+            return Ok(chain_spec.clone());
+        }
+        let chain_id = self.provider.get_chain_id().await?;
+        let chain_spec = ChainSpecRegistry::new().resolve(chain_id);
+        self.chain_spec = Some(chain_spec.clone());
+        Ok(chain_spec)
     }
 
     /// Retrieves the index of a transaction within the trie based on its hash.
@@ -69,17 +101,48 @@ impl TxReceiptsMptHandler {
         &mut self,
         block_number: u64,
     ) -> Result<(), EthTrieError> {
-        let (txs, tx_receipt_root) = self
+        let (txs, header) = self
             .provider
             .get_block_transaction_receipts(block_number)
             .await?;
+        self.build_tx_receipts_tree_from_rpc(block_number, txs, header)
+            .await
+    }
+
+    /// Builds the transaction receipts trie from the receipts and header of a block, as
+    /// returned by the RPC.
+    pub(crate) async fn build_tx_receipts_tree_from_rpc(
+        &mut self,
+        block_number: u64,
+        mut txs: Vec<AnyTransactionReceipt>,
+        header: Header,
+    ) -> Result<(), EthTrieError> {
+        let chain_spec = self.resolve_chain_spec().await?;
+        chain_spec.validate_header(&header)?;
+        let block_hash = header.hash;
+
+        // Bor receipts are returned along the block receipts by Polygon PoS nodes, but are
+        // not part of the trie. See `BorReceiptHandler`.
+        if chain_spec.bor_state_sync {
+            let state_sync_tx_hash = bor_state_sync_tx_hash(block_number, block_hash);
+            txs.retain(|tx_receipt| tx_receipt.transaction_hash != state_sync_tx_hash);
+        }
 
         let converted_tx_receipts: Vec<ConsensusTxReceipt> = txs
-            .iter()
-            .map(|tx_receipt| RpcTxReceipt(tx_receipt.clone()).try_into())
-            .collect::<Result<Vec<_>, _>>()
-            .unwrap();
-        self.build_trie(converted_tx_receipts, tx_receipt_root)?;
+            .into_iter()
+            .map(|tx_receipt| {
+                RpcTxReceipt {
+                    receipt: tx_receipt,
+                    chain_spec: &chain_spec,
+                    timestamp: header.timestamp,
+                }
+                .try_into()
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        self.build_trie(converted_tx_receipts, header.receipts_root)?;
+        if let Some(trie) = self.trie.as_mut() {
+            trie.block = Some((block_number, block_hash));
+        }
         Ok(())
     }
 
@@ -107,6 +170,7 @@ impl TxReceiptsMptHandler {
             trie,
             elements: tx_receipts,
             root: expected_root,
+            block: None,
         };
 
         self.trie = Some(result_mpt);
@@ -122,6 +186,28 @@ impl TxReceiptsMptHandler {
         Ok(proof)
     }
 
+    /// Generates a [`ReceiptInclusionProof`] for a receipt at a given index within the trie.
+    ///
+    /// The trie must have been built from a block, for the block number and hash to be known.
+    pub fn get_inclusion_proof(
+        &mut self,
+        tx_index: u64,
+    ) -> Result<ReceiptInclusionProof, EthTrieError> {
+        let target_trie = self.trie.as_ref().ok_or(EthTrieError::TrieNotFound)?;
+        let (block_number, block_hash) = target_trie.block.ok_or(EthTrieError::BlockNotFound)?;
+        let root = target_trie.root;
+        let value = self.get_tx_receipt(tx_index)?.rlp_encode();
+        let nodes = self.get_proof(tx_index)?;
+        Ok(ReceiptInclusionProof::new(
+            block_number,
+            block_hash,
+            root,
+            tx_index,
+            nodes,
+            value,
+        ))
+    }
+
     /// Verifies a proof for a transaction at a given index against the stored trie.
     pub fn verify_proof(
         &self,
@@ -149,6 +235,12 @@ impl TxReceiptsMptHandler {
             .cloned()
     }
 
+    /// Borrows all elements within the trie, without cloning them as [`Self::get_elements`] does.
+    pub fn elements(&self) -> Result<&[ConsensusTxReceipt], EthTrieError> {
+        let target_trie = self.trie.as_ref().ok_or(EthTrieError::TrieNotFound)?;
+        Ok(&target_trie.elements)
+    }
+
     /// Retrieves all elements within the trie.
     pub fn get_elements(&self) -> Result<Vec<ConsensusTxReceipt>, EthTrieError> {
         let target_trie = self.trie.as_ref().ok_or(EthTrieError::TrieNotFound)?;
@@ -160,23 +252,89 @@ impl TxReceiptsMptHandler {
         let target_trie = self.trie.as_ref().ok_or(EthTrieError::TrieNotFound)?;
         Ok(target_trie.root)
     }
+
+    /// Retrieves the number and hash of the block the trie was built from.
+    ///
+    /// Fails with [`EthTrieError::BlockNotFound`] if the trie was built from elements.
+    pub fn get_block(&self) -> Result<(u64, B256), EthTrieError> {
+        let target_trie = self.trie.as_ref().ok_or(EthTrieError::TrieNotFound)?;
+        target_trie.block.ok_or(EthTrieError::BlockNotFound)
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use alloy::consensus::Eip658Value;
     use alloy::primitives::hex;
     use alloy::primitives::B256;
 
     const MAINNET_RPC_URL: &str = "https://mainnet.infura.io/v3/720000a7936b45c79d0868f70478e2e9";
     const MAINNET_RPC_URL2: &str = "https://ethereum-rpc.publicnode.com";
+    const OP_MAINNET_RPC_URL: &str = "https://mainnet.optimism.io";
+    const BASE_MAINNET_RPC_URL: &str = "https://mainnet.base.org";
 
     // Test cases
+    // OP Stack Regolith: OP Mainnet 110000000, Base 5000000
+    // OP Stack Canyon: OP Mainnet 124000000, Base 20000000
+    // Frontier: 46147
+    // Pre-Byzantium: 4369999
     // Byzantium: 4370000
     // EIP-2930(Berlin): 12244000
     // EIP-1559(London): 12965000
     // EIP-4844(Dencun): 19426589
 
+    #[tokio::test]
+    async fn test_tx_receipt_frontier() {
+        let url = Url::parse(MAINNET_RPC_URL2).unwrap();
+        let target_tx_hash = B256::from(hex!(
+            "5c504ed432cb51138bcf09aa5e8a410dd4a1e204ef84bfed1be16dfba1b22060"
+        ));
+
+        let mut tx_receipts_mpt_handler = TxReceiptsMptHandler::new(url).unwrap();
+        tx_receipts_mpt_handler
+            .build_tx_receipts_tree_from_block(46147)
+            .await
+            .unwrap();
+
+        let tx_index = tx_receipts_mpt_handler
+            .tx_hash_to_tx_index(target_tx_hash)
+            .await
+            .unwrap();
+        let receipt = tx_receipts_mpt_handler.get_tx_receipt(tx_index).unwrap();
+        assert!(matches!(receipt.status(), Eip658Value::PostState(_)));
+        let proof = tx_receipts_mpt_handler.get_proof(tx_index).unwrap();
+        tx_receipts_mpt_handler
+            .verify_proof(tx_index, proof.clone())
+            .unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_tx_receipt_pre_byzantium() {
+        let url = Url::parse(MAINNET_RPC_URL2).unwrap();
+        let mut tx_receipts_mpt_handler = TxReceiptsMptHandler::new(url).unwrap();
+
+        // Last block before Byzantium, whose receipts carry intermediate state roots
+        tx_receipts_mpt_handler
+            .build_tx_receipts_tree_from_block(4369999)
+            .await
+            .unwrap();
+
+        let receipt = tx_receipts_mpt_handler.get_tx_receipt(0).unwrap();
+        assert!(matches!(receipt.status(), Eip658Value::PostState(_)));
+        let proof = tx_receipts_mpt_handler.get_proof(0).unwrap();
+        let value = tx_receipts_mpt_handler.verify_proof(0, proof).unwrap();
+        assert_eq!(value, receipt.rlp_encode());
+
+        // The first Byzantium block carries statuses
+        tx_receipts_mpt_handler
+            .build_tx_receipts_tree_from_block(4370000)
+            .await
+            .unwrap();
+        let receipt = tx_receipts_mpt_handler.get_tx_receipt(0).unwrap();
+        assert!(matches!(receipt.status(), Eip658Value::Eip658(_)));
+    }
+
     #[tokio::test]
     async fn test_tx_receipt_byzantium() {
         let url = Url::parse(MAINNET_RPC_URL).unwrap();
@@ -269,4 +427,68 @@ mod tests {
             .verify_proof(tx_index, proof.clone())
             .unwrap();
     }
+
+    #[tokio::test]
+    async fn test_tx_receipt_inclusion_proof() {
+        let url = Url::parse(MAINNET_RPC_URL2).unwrap();
+        let target_tx_hash = B256::from(hex!(
+            "2055b7e01304f87f9412cd44758cd248bc2da2dab95c97026064ffb084711735"
+        ));
+
+        let mut tx_receipts_mpt_handler = TxReceiptsMptHandler::new(url).unwrap();
+        tx_receipts_mpt_handler
+            .build_tx_receipts_tree_from_block(12965000)
+            .await
+            .unwrap();
+
+        let tx_index = tx_receipts_mpt_handler
+            .tx_hash_to_tx_index(target_tx_hash)
+            .await
+            .unwrap();
+        let proof = tx_receipts_mpt_handler
+            .get_inclusion_proof(tx_index)
+            .unwrap();
+        assert_eq!(proof.block_number, 12965000);
+        assert_eq!(proof.root, tx_receipts_mpt_handler.get_root().unwrap());
+        proof.verify().unwrap();
+    }
+
+    async fn check_op_deposit_receipt(rpc_url: &str, block_number: u64, canyon: bool) {
+        let url = Url::parse(rpc_url).unwrap();
+        let mut tx_receipts_mpt_handler = TxReceiptsMptHandler::new(url).unwrap();
+        tx_receipts_mpt_handler
+            .build_tx_receipts_tree_from_block(block_number)
+            .await
+            .unwrap();
+
+        // The L1 attributes deposit comes first
+        let receipt = tx_receipts_mpt_handler.get_tx_receipt(0).unwrap();
+        let deposit = receipt.deposit().unwrap();
+        assert!(deposit.deposit_nonce.is_some());
+        assert_eq!(deposit.deposit_receipt_version.is_some(), canyon);
+
+        let proof = tx_receipts_mpt_handler.get_proof(0).unwrap();
+        let value = tx_receipts_mpt_handler.verify_proof(0, proof).unwrap();
+        assert_eq!(value, receipt.rlp_encode());
+    }
+
+    #[tokio::test]
+    async fn test_tx_receipt_op_mainnet_regolith() {
+        check_op_deposit_receipt(OP_MAINNET_RPC_URL, 110000000, false).await;
+    }
+
+    #[tokio::test]
+    async fn test_tx_receipt_op_mainnet_canyon() {
+        check_op_deposit_receipt(OP_MAINNET_RPC_URL, 124000000, true).await;
+    }
+
+    #[tokio::test]
+    async fn test_tx_receipt_base_regolith() {
+        check_op_deposit_receipt(BASE_MAINNET_RPC_URL, 5000000, false).await;
+    }
+
+    #[tokio::test]
+    async fn test_tx_receipt_base_canyon() {
+        check_op_deposit_receipt(BASE_MAINNET_RPC_URL, 20000000, true).await;
+    }
 }
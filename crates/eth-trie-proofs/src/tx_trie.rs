@@ -2,12 +2,17 @@ use std::sync::Arc;
 
 use alloy::network::eip2718::Encodable2718;
 use alloy::primitives::{B256, U256};
+use alloy::rpc::types::{serde_helpers::WithOtherFields, Header, Transaction};
 use eth_trie::{EthTrie, MemoryDB, Trie as _};
 use ethereum_types::H256;
 use url::Url;
 
 use crate::{
+    blob::{BlobInclusionProof, BlobSidecarSource},
+    chain::{ChainSpec, ChainSpecRegistry},
     error::EthTrieError,
+    polygon::bor_state_sync_tx_hash,
+    proof::TxInclusionProof,
     rpc::RpcProvider,
     tx::{ConsensusTx, RpcTx},
 };
@@ -20,6 +25,9 @@ pub struct TxsMptHandler {
     /// Optional MPT structure to hold transactions.
     /// If `None`, the trie has not been built yet.
     trie: Option<TxsMpt>,
+    /// Profile of the chain.
+    /// If `None`, it is resolved from the chain id of the provider when first needed.
+    chain_spec: Option<ChainSpec>,
 }
 
 /// The [`TxsMpt`] struct encapsulates the MPT (Merkle Patricia Trie) specifically for transactions,
@@ -28,6 +36,10 @@ pub struct TxsMpt {
     pub trie: EthTrie<MemoryDB>,
     elements: Vec<ConsensusTx>,
     root: B256,
+    /// Number and hash of the block, known when the trie is built from a block.
+    block: Option<(u64, B256)>,
+    /// Timestamp of the block, known when the trie is built from a block.
+    timestamp: Option<u64>,
 }
 
 impl TxsMptHandler {
@@ -35,11 +47,34 @@ impl TxsMptHandler {
     ///
     /// This does not initialize the trie yet.
     pub fn new(url: Url) -> Result<Self, EthTrieError> {
-        let provider = RpcProvider::new(url);
-        Ok(Self {
+        Ok(Self::with_provider(RpcProvider::new(url), None))
+    }
+
+    /// Creates a new [`TxsMptHandler`] with a given RPC provider URL and chain profile.
+    ///
+    /// This does not initialize the trie yet.
+    pub fn new_with_chain_spec(url: Url, chain_spec: ChainSpec) -> Result<Self, EthTrieError> {
+        Ok(Self::with_provider(RpcProvider::new(url), Some(chain_spec)))
+    }
+
+    /// Creates a new [`TxsMptHandler`] sharing an existing provider.
+    pub(crate) fn with_provider(provider: RpcProvider, chain_spec: Option<ChainSpec>) -> Self {
+        Self {
             provider,
             trie: None,
-        })
+            chain_spec,
+        }
+    }
+
+    /// Returns the profile of the chain, resolving it from the built-in profiles if needed.
+    async fn resolve_chain_spec(&mut self) -> Result<ChainSpec, EthTrieError> {
+        if let Some(chain_spec) = &self.chain_spec {
+            return Ok(chain_spec.clone());
+        }
+        let chain_id = self.provider.get_chain_id().await?;
+        let chain_spec = ChainSpecRegistry::new().resolve(chain_id);
+        self.chain_spec = Some(chain_spec.clone());
+        Ok(chain_spec)
     }
 
     /// Retrieves the index of a transaction within the trie based on its hash.
@@ -55,6 +90,13 @@ impl TxsMptHandler {
         Ok(tx_index as u64)
     }
 
+    /// Retrieves the number and hash of the block including a transaction.
+    ///
+    /// Returns an error if the transaction does not exist or is still pending.
+    pub async fn get_tx_block(&self, tx_hash: B256) -> Result<(u64, B256), EthTrieError> {
+        self.provider.get_tx_block(tx_hash).await
+    }
+
     /// Builds the transaction trie from a specific transaction hash.
     ///
     /// This fetches the block height for the transaction and delegates to [`build_tx_tree_from_block`].
@@ -71,13 +113,37 @@ impl TxsMptHandler {
         &mut self,
         block_number: u64,
     ) -> Result<(), EthTrieError> {
-        let (txs, tx_root) = self.provider.get_block_transactions(block_number).await?;
+        let (txs, header) = self.provider.get_block_transactions(block_number).await?;
+        self.build_tx_tree_from_rpc(block_number, txs, header).await
+    }
+
+    /// Builds the transactions trie from the transactions and header of a block, as returned
+    /// by the RPC.
+    pub(crate) async fn build_tx_tree_from_rpc(
+        &mut self,
+        block_number: u64,
+        txs: Vec<WithOtherFields<Transaction>>,
+        header: Header,
+    ) -> Result<(), EthTrieError> {
+        let chain_spec = self.resolve_chain_spec().await?;
+        chain_spec.validate_header(&header)?;
+        let block_hash = header.hash;
+
+        // Bor state sync transactions are returned along the block transactions by Polygon PoS
+        // nodes, but are not part of the trie.
+        let state_sync_tx_hash = chain_spec
+            .bor_state_sync
+            .then(|| bor_state_sync_tx_hash(block_number, block_hash));
         let converted_txs: Vec<ConsensusTx> = txs
-            .iter()
-            .map(|tx| RpcTx(tx.clone()).try_into())
-            .collect::<Result<Vec<_>, _>>()
-            .unwrap();
-        self.build_trie(converted_txs, tx_root)?;
+            .into_iter()
+            .filter(|tx| Some(tx.hash) != state_sync_tx_hash)
+            .map(|tx| RpcTx(tx).into_consensus_tx(&chain_spec))
+            .collect::<Result<Vec<_>, _>>()?;
+        self.build_trie(converted_txs, header.transactions_root)?;
+        if let Some(trie) = self.trie.as_mut() {
+            trie.block = Some((block_number, block_hash));
+            trie.timestamp = Some(header.timestamp);
+        }
         Ok(())
     }
 
@@ -92,6 +158,11 @@ impl TxsMptHandler {
         let memdb = Arc::new(MemoryDB::new(true));
         let mut trie = EthTrie::new(memdb.clone());
 
+        // Blob sidecars travel next to the block, the trie only commits to their versioned hashes.
+        let txs: Vec<ConsensusTx> = txs
+            .into_iter()
+            .map(ConsensusTx::without_blob_sidecar)
+            .collect();
         for (idx, tx) in txs.iter().enumerate() {
             let key = alloy_rlp::encode(U256::from(idx));
             let rlp = tx.0.encoded_2718();
@@ -106,6 +177,8 @@ impl TxsMptHandler {
             trie,
             elements: txs,
             root: expected_root,
+            block: None,
+            timestamp: None,
         };
 
         self.trie = Some(result_mpt);
@@ -121,6 +194,44 @@ impl TxsMptHandler {
         Ok(proof)
     }
 
+    /// Generates a [`TxInclusionProof`] for a transaction at a given index within the trie.
+    ///
+    /// The trie must have been built from a block, for the block number and hash to be known.
+    pub fn get_inclusion_proof(&mut self, tx_index: u64) -> Result<TxInclusionProof, EthTrieError> {
+        let target_trie = self.trie.as_ref().ok_or(EthTrieError::TrieNotFound)?;
+        let (block_number, block_hash) = target_trie.block.ok_or(EthTrieError::BlockNotFound)?;
+        let root = target_trie.root;
+        let value = self.get_tx(tx_index)?.0.encoded_2718();
+        let nodes = self.get_proof(tx_index)?;
+        Ok(TxInclusionProof::new(
+            block_number,
+            block_hash,
+            root,
+            tx_index,
+            nodes,
+            value,
+        ))
+    }
+
+    /// Generates a [`BlobInclusionProof`] for a type-3 transaction at a given index within the trie,
+    /// attaching the blobs of the transaction fetched from `source`.
+    ///
+    /// The trie must have been built from a block, for the block number, hash and timestamp to be known.
+    pub async fn get_blob_inclusion_proof(
+        &mut self,
+        tx_index: u64,
+        source: &BlobSidecarSource,
+    ) -> Result<BlobInclusionProof, EthTrieError> {
+        let target_trie = self.trie.as_ref().ok_or(EthTrieError::TrieNotFound)?;
+        let timestamp = target_trie.timestamp.ok_or(EthTrieError::BlockNotFound)?;
+        let versioned_hashes = self
+            .get_tx(tx_index)?
+            .blob_versioned_hashes()
+            .ok_or(EthTrieError::InvalidTxVersion)?;
+        let sidecar = source.fetch(timestamp, &versioned_hashes).await?;
+        BlobInclusionProof::new(self.get_inclusion_proof(tx_index)?, sidecar)
+    }
+
     /// Verifies a proof for a transaction at a given index against the stored trie.
     pub fn verify_proof(
         &self,
@@ -148,6 +259,12 @@ impl TxsMptHandler {
             .cloned()
     }
 
+    /// Borrows all elements within the trie, without cloning them as [`Self::get_elements`] does.
+    pub fn elements(&self) -> Result<&[ConsensusTx], EthTrieError> {
+        let target_trie = self.trie.as_ref().ok_or(EthTrieError::TrieNotFound)?;
+        Ok(&target_trie.elements)
+    }
+
     /// Retrieves all elements within the trie.
     pub fn get_elements(&self) -> Result<Vec<ConsensusTx>, EthTrieError> {
         let target_trie = self.trie.as_ref().ok_or(EthTrieError::TrieNotFound)?;
@@ -159,16 +276,27 @@ impl TxsMptHandler {
         let target_trie = self.trie.as_ref().ok_or(EthTrieError::TrieNotFound)?;
         Ok(target_trie.root)
     }
+
+    /// Retrieves the number and hash of the block the trie was built from.
+    ///
+    /// Fails with [`EthTrieError::BlockNotFound`] if the trie was built from elements.
+    pub fn get_block(&self) -> Result<(u64, B256), EthTrieError> {
+        let target_trie = self.trie.as_ref().ok_or(EthTrieError::TrieNotFound)?;
+        target_trie.block.ok_or(EthTrieError::BlockNotFound)
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::arbitrum::ArbitrumTx;
     use alloy::primitives::hex;
     use alloy::primitives::B256;
 
     const MAINNET_RPC_URL: &str = "https://mainnet.infura.io/v3/720000a7936b45c79d0868f70478e2e9";
     const MAINNET_RPC_URL_SUB: &str = "https://ethereum-rpc.publicnode.com";
+    const OP_MAINNET_RPC_URL: &str = "https://mainnet.optimism.io";
+    const ARBITRUM_ONE_RPC_URL: &str = "https://arb1.arbitrum.io/rpc";
 
     // Test cases
     // Frontier: 46147
@@ -347,4 +475,65 @@ mod tests {
             txs_mpt_handler2.get_root().unwrap()
         );
     }
+
+    #[tokio::test]
+    async fn test_tx_inclusion_proof() {
+        let url = Url::parse(MAINNET_RPC_URL_SUB).unwrap();
+        let target_tx_hash = B256::from(hex!(
+            "2055b7e01304f87f9412cd44758cd248bc2da2dab95c97026064ffb084711735"
+        ));
+
+        let mut txs_mpt_handler = TxsMptHandler::new(url).unwrap();
+        txs_mpt_handler
+            .build_tx_tree_from_tx_hash(target_tx_hash)
+            .await
+            .unwrap();
+
+        let tx_index = txs_mpt_handler.tx_hash_to_tx_index(target_tx_hash).unwrap();
+        let proof = txs_mpt_handler.get_inclusion_proof(tx_index).unwrap();
+        assert_eq!(proof.block_number, 12965000);
+        assert_eq!(proof.verify().unwrap().0.tx_hash(), target_tx_hash);
+    }
+
+    #[tokio::test]
+    async fn test_tx_mpt_op_deposit() {
+        let url = Url::parse(OP_MAINNET_RPC_URL).unwrap();
+        let mut txs_mpt_handler = TxsMptHandler::new(url).unwrap();
+
+        // Every OP Stack block starts with the L1 attributes deposit
+        txs_mpt_handler
+            .build_tx_tree_from_block(124000000)
+            .await
+            .unwrap();
+
+        let tx = txs_mpt_handler.get_tx(0).unwrap();
+        let deposit = tx.deposit().unwrap();
+        assert_eq!(
+            deposit.from,
+            hex!("deaddeaddeaddeaddeaddeaddeaddeaddead0001")
+        );
+
+        let proof = txs_mpt_handler.get_proof(0).unwrap();
+        let value = txs_mpt_handler.verify_proof(0, proof).unwrap();
+        assert_eq!(value, tx.rlp_encode());
+    }
+
+    #[tokio::test]
+    async fn test_tx_mpt_arbitrum() {
+        let url = Url::parse(ARBITRUM_ONE_RPC_URL).unwrap();
+        let mut txs_mpt_handler = TxsMptHandler::new(url).unwrap();
+
+        // Every Arbitrum block starts with the ArbOS start of block transaction
+        txs_mpt_handler
+            .build_tx_tree_from_block(250000000)
+            .await
+            .unwrap();
+
+        let tx = txs_mpt_handler.get_tx(0).unwrap();
+        assert!(matches!(tx.arbitrum(), Some(ArbitrumTx::Internal(_))));
+
+        let proof = txs_mpt_handler.get_proof(0).unwrap();
+        let value = txs_mpt_handler.verify_proof(0, proof).unwrap();
+        assert_eq!(value, tx.rlp_encode());
+    }
 }
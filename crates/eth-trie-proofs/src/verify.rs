@@ -0,0 +1,66 @@
+use std::sync::Arc;
+
+use alloy::primitives::{B256, U256};
+use eth_trie::{EthTrie, MemoryDB, Trie};
+use ethereum_types::H256;
+
+use crate::error::EthTrieError;
+
+/// Verifies a proof for the element at `tx_index` against a trie root, without the trie.
+///
+/// Returns the encoded element proven at the index, as inserted in the trie.
+pub fn verify_proof(
+    root: B256,
+    tx_index: u64,
+    proof: Vec<Vec<u8>>,
+) -> Result<Vec<u8>, EthTrieError> {
+    let trie = EthTrie::new(Arc::new(MemoryDB::new(true)));
+    match trie.verify_proof(
+        H256::from_slice(root.as_slice()),
+        alloy_rlp::encode(U256::from(tx_index)).as_slice(),
+        proof,
+    ) {
+        Ok(Some(result)) => Ok(result),
+        _ => Err(EthTrieError::InvalidMPTProof),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn build_trie(values: &[Vec<u8>]) -> (EthTrie<MemoryDB>, B256) {
+        let mut trie = EthTrie::new(Arc::new(MemoryDB::new(true)));
+        for (idx, value) in values.iter().enumerate() {
+            let key = alloy_rlp::encode(U256::from(idx));
+            trie.insert(key.as_slice(), value).unwrap();
+        }
+        let root = B256::from_slice(trie.root_hash().unwrap().as_bytes());
+        (trie, root)
+    }
+
+    #[test]
+    fn test_verify_proof() {
+        let values: Vec<Vec<u8>> = (0..200u8).map(|i| vec![i; 40]).collect();
+        let (mut trie, root) = build_trie(&values);
+
+        for idx in [0, 1, 127, 128, 199] {
+            let key = alloy_rlp::encode(U256::from(idx));
+            let proof = trie.get_proof(key.as_slice()).unwrap();
+            let value = verify_proof(root, idx, proof).unwrap();
+            assert_eq!(value, values[idx as usize]);
+        }
+    }
+
+    #[test]
+    fn test_verify_proof_invalid() {
+        let values: Vec<Vec<u8>> = (0..20u8).map(|i| vec![i; 40]).collect();
+        let (mut trie, root) = build_trie(&values);
+        let key = alloy_rlp::encode(U256::from(3));
+        let proof = trie.get_proof(key.as_slice()).unwrap();
+
+        assert!(verify_proof(B256::ZERO, 3, proof.clone()).is_err());
+        assert!(verify_proof(root, 4, proof.clone()).is_err());
+        assert!(verify_proof(root, 3, proof[..proof.len() - 1].to_vec()).is_err());
+    }
+}
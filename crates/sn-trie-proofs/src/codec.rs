@@ -0,0 +1,356 @@
+//! Compact, versioned binary encoding of [`TrieProof`].
+//!
+//! Every encoding starts with the header of `trie-proof-header`, shared with the Ethereum
+//! proofs of `eth-trie-proofs`:
+//!
+//! ```text
+//! magic "TRPF" (4) | version (1) | chain (1) | trie (1) | hash (1) | flags (1)
+//! ```
+//!
+//! `chain` is [`CHAIN_STARKNET`], `trie` is [`TRIE_TX`], [`TRIE_RECEIPT`] or [`TRIE_EVENT`],
+//! `hash` is [`HASH_PEDERSEN`] or [`HASH_POSEIDON`], and `flags` is a combination of
+//! [`FLAG_BATCH`] and [`FLAG_DEDUP`]. Integers are big-endian and felts are 32 bytes
+//! big-endian. A single proof follows as:
+//!
+//! ```text
+//! commitment (32) | index (8) | leaf (32) | node_count (4) | nodes
+//!   0 | left (32) | right (32)              -- binary node
+//!   1 | child (32) | path (32) | length (1) -- edge node
+//! ```
+//!
+//! A batch holds `proof_count (4)` proofs of the same trie and hash function. With [`FLAG_DEDUP`], the
+//! distinct nodes of the batch are stored once in a table (`node_count (4) | nodes`)
+//! placed before the proofs, and proofs refer to them by their `u32` index in the table.
+//! The reader, writer and batch layout are those of [`trie_proof_header::codec`], shared
+//! with `eth-trie-proofs`.
+//!
+//! Decoding is strict: unknown versions, kinds and flags, truncated or trailing bytes,
+//! non-canonical felts, invalid edges and non-canonical node tables are rejected.
+
+use sn_merkle_trie::node::TrieNode;
+use starknet_types_core::felt::Felt;
+
+use trie_proof_header::codec::{self, InvalidEncoding, Reader, Writer};
+use trie_proof_header::Header;
+
+use crate::error::SnTrieError;
+use crate::proof::{ProofNode, TrieKind, TrieProof};
+use crate::verify::HashFunction;
+
+pub use trie_proof_header::{
+    CHAIN_STARKNET, FLAG_BATCH, FLAG_DEDUP, HASH_PEDERSEN, HASH_POSEIDON, MAGIC, TRIE_EVENT,
+    TRIE_RECEIPT, TRIE_TX, VERSION,
+};
+
+impl TrieProof {
+    /// Encodes the proof alone.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut writer = writer(self.trie, self.hash_function, 0);
+        write_fields(&mut writer, self);
+        writer.nodes(&self.nodes, write_node);
+        writer.into_bytes()
+    }
+
+    /// Decodes a proof encoded by [`TrieProof::to_bytes`].
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, SnTrieError> {
+        let (trie, hash_function, flags, mut reader) = reader(bytes)?;
+        if flags != 0 {
+            return Err(SnTrieError::InvalidProofEncoding);
+        }
+        let fields = read_fields(&mut reader)?;
+        let nodes = reader.nodes(read_node)?;
+        reader.finish()?;
+        Ok(proof(trie, hash_function, fields, nodes))
+    }
+}
+
+impl From<InvalidEncoding> for SnTrieError {
+    fn from(_: InvalidEncoding) -> Self {
+        Self::InvalidProofEncoding
+    }
+}
+
+/// Encodes a batch of proofs of `trie` tries hashed with `hash_function`.
+///
+/// With `dedup`, nodes shared between proofs, e.g. the top of the trie for proofs of
+/// the same block, are stored once. Fails if a proof is of another trie or uses another
+/// hash function.
+pub fn encode_batch(
+    trie: TrieKind,
+    hash_function: HashFunction,
+    proofs: &[TrieProof],
+    dedup: bool,
+) -> Result<Vec<u8>, SnTrieError> {
+    if proofs
+        .iter()
+        .any(|proof| proof.trie != trie || proof.hash_function != hash_function)
+    {
+        return Err(SnTrieError::InvalidProofEncoding);
+    }
+
+    let mut writer = writer(trie, hash_function, codec::batch_flags(dedup));
+    codec::write_batch(
+        &mut writer,
+        proofs,
+        dedup,
+        |proof| proof.nodes.as_slice(),
+        write_fields,
+        write_node,
+    );
+    Ok(writer.into_bytes())
+}
+
+/// Decodes a batch encoded by [`encode_batch`], with or without dedup.
+pub fn decode_batch(bytes: &[u8]) -> Result<Vec<TrieProof>, SnTrieError> {
+    let (trie, hash_function, flags, mut reader) = reader(bytes)?;
+    let dedup = codec::batch_dedup(flags)?;
+    let proofs = codec::read_batch(&mut reader, dedup, read_fields, read_node)?;
+    reader.finish()?;
+
+    Ok(proofs
+        .into_iter()
+        .map(|(fields, nodes)| proof(trie, hash_function, fields, nodes))
+        .collect())
+}
+
+/// Fields of a proof besides its nodes: commitment, index and leaf.
+type ProofFields = (Felt, u64, Felt);
+
+fn proof(
+    trie: TrieKind,
+    hash_function: HashFunction,
+    (commitment, index, leaf): ProofFields,
+    nodes: Vec<ProofNode>,
+) -> TrieProof {
+    TrieProof {
+        trie,
+        commitment,
+        index,
+        leaf,
+        hash_function,
+        nodes,
+    }
+}
+
+fn writer(trie: TrieKind, hash_function: HashFunction, flags: u8) -> Writer {
+    let trie = match trie {
+        TrieKind::Transaction => TRIE_TX,
+        TrieKind::Receipt => TRIE_RECEIPT,
+        TrieKind::Event => TRIE_EVENT,
+    };
+    let hash = match hash_function {
+        HashFunction::Pedersen => HASH_PEDERSEN,
+        HashFunction::Poseidon => HASH_POSEIDON,
+    };
+    Writer::new(Header {
+        chain: CHAIN_STARKNET,
+        trie,
+        hash,
+        flags,
+    })
+}
+
+/// Checks the header and returns the trie, its hash function and the flags.
+fn reader(bytes: &[u8]) -> Result<(TrieKind, HashFunction, u8, Reader<'_>), SnTrieError> {
+    let (header, reader) = Reader::new(bytes)?;
+    if header.chain != CHAIN_STARKNET {
+        return Err(SnTrieError::InvalidProofEncoding);
+    }
+    let trie = match header.trie {
+        TRIE_TX => TrieKind::Transaction,
+        TRIE_RECEIPT => TrieKind::Receipt,
+        TRIE_EVENT => TrieKind::Event,
+        _ => return Err(SnTrieError::InvalidProofEncoding),
+    };
+    let hash_function = match header.hash {
+        HASH_PEDERSEN => HashFunction::Pedersen,
+        HASH_POSEIDON => HashFunction::Poseidon,
+        _ => return Err(SnTrieError::InvalidProofEncoding),
+    };
+    Ok((trie, hash_function, header.flags, reader))
+}
+
+fn write_felt(writer: &mut Writer, value: &Felt) {
+    writer.raw(&value.to_bytes_be());
+}
+
+fn write_fields(writer: &mut Writer, proof: &TrieProof) {
+    write_felt(writer, &proof.commitment);
+    writer.u64(proof.index);
+    write_felt(writer, &proof.leaf);
+}
+
+fn write_node(writer: &mut Writer, node: &ProofNode) {
+    match node {
+        ProofNode::Binary { left, right } => {
+            writer.u8(0);
+            write_felt(writer, left);
+            write_felt(writer, right);
+        }
+        ProofNode::Edge {
+            child,
+            path,
+            length,
+        } => {
+            writer.u8(1);
+            write_felt(writer, child);
+            write_felt(writer, path);
+            writer.u8(*length);
+        }
+    }
+}
+
+fn read_felt(reader: &mut Reader<'_>) -> Result<Felt, SnTrieError> {
+    let bytes = reader.array()?;
+    let felt = Felt::from_bytes_be(&bytes);
+    // Values above the field prime would be silently reduced.
+    if felt.to_bytes_be() != bytes {
+        return Err(SnTrieError::InvalidProofEncoding);
+    }
+    Ok(felt)
+}
+
+fn read_fields(reader: &mut Reader<'_>) -> Result<ProofFields, SnTrieError> {
+    Ok((read_felt(reader)?, reader.u64()?, read_felt(reader)?))
+}
+
+fn read_node(reader: &mut Reader<'_>) -> Result<ProofNode, SnTrieError> {
+    let node = match reader.u8()? {
+        0 => ProofNode::Binary {
+            left: read_felt(reader)?,
+            right: read_felt(reader)?,
+        },
+        1 => ProofNode::Edge {
+            child: read_felt(reader)?,
+            path: read_felt(reader)?,
+            length: reader.u8()?,
+        },
+        _ => return Err(SnTrieError::InvalidProofEncoding),
+    };
+    // Rejects edges whose path doesn't fit their length.
+    TrieNode::try_from(&node)?;
+    Ok(node)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use sn_merkle_trie::conversion::from_u64_to_bits;
+    use sn_merkle_trie::transaction::TransactionMerkleTree;
+    use sn_merkle_trie::MerkleTree;
+
+    fn build_proofs(hash_function: HashFunction) -> Vec<TrieProof> {
+        let leaves: Vec<Felt> = (1..=9u64).map(Felt::from).collect();
+        let mut tree = match hash_function {
+            HashFunction::Pedersen => TransactionMerkleTree::Pedersen(MerkleTree::default()),
+            HashFunction::Poseidon => TransactionMerkleTree::Poseidon(MerkleTree::default()),
+        };
+        for (idx, leaf) in leaves.iter().enumerate() {
+            tree.set(from_u64_to_bits(idx as u64), *leaf).unwrap();
+        }
+        let (root, root_idx) = tree.commit().unwrap();
+
+        (0..leaves.len() as u64)
+            .map(|index| {
+                let nodes = tree
+                    .get_proof(root_idx, from_u64_to_bits(index))
+                    .unwrap()
+                    .unwrap();
+                TrieProof::new(
+                    TrieKind::Transaction,
+                    root,
+                    index,
+                    leaves[index as usize],
+                    hash_function,
+                    &nodes,
+                )
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_round_trip() {
+        for hash_function in [HashFunction::Pedersen, HashFunction::Poseidon] {
+            for proof in build_proofs(hash_function) {
+                let bytes = proof.to_bytes();
+                let decoded = TrieProof::from_bytes(&bytes).unwrap();
+                assert_eq!(decoded, proof);
+                assert!(decoded
+                    .verify(proof.commitment, hash_function)
+                    .unwrap()
+                    .is_member());
+            }
+        }
+
+        let proof = &build_proofs(HashFunction::Poseidon)[4];
+        let bytes = proof.to_bytes();
+        assert_eq!(&bytes[..9], &[b'T', b'R', b'P', b'F', 2, 2, 1, 3, 0]);
+        assert!(bytes.len() < serde_json::to_string(proof).unwrap().len());
+    }
+
+    #[test]
+    fn test_batch_round_trip() {
+        let proofs = build_proofs(HashFunction::Poseidon);
+
+        let trie = TrieKind::Transaction;
+        let plain = encode_batch(trie, HashFunction::Poseidon, &proofs, false).unwrap();
+        let deduped = encode_batch(trie, HashFunction::Poseidon, &proofs, true).unwrap();
+        assert!(deduped.len() < plain.len());
+
+        assert_eq!(decode_batch(&plain).unwrap(), proofs);
+        assert_eq!(decode_batch(&deduped).unwrap(), proofs);
+        assert!(encode_batch(trie, HashFunction::Pedersen, &proofs, true).is_err());
+        assert!(encode_batch(TrieKind::Receipt, HashFunction::Poseidon, &proofs, true).is_err());
+
+        // The trie kind is kept apart from the hash function
+        let mut receipts = proofs.clone();
+        for proof in &mut receipts {
+            proof.trie = TrieKind::Receipt;
+        }
+        let encoded = encode_batch(TrieKind::Receipt, HashFunction::Poseidon, &receipts, false);
+        assert_eq!(decode_batch(&encoded.unwrap()).unwrap(), receipts);
+    }
+
+    #[test]
+    fn test_strict_decoding() {
+        let proof = build_proofs(HashFunction::Pedersen).remove(2);
+        let bytes = proof.to_bytes();
+
+        assert!(decode_batch(&bytes).is_err());
+
+        // Truncated and trailing bytes
+        for len in 0..bytes.len() {
+            assert!(TrieProof::from_bytes(&bytes[..len]).is_err());
+        }
+        let mut trailing = bytes.clone();
+        trailing.push(0);
+        assert!(TrieProof::from_bytes(&trailing).is_err());
+
+        // Magic, version, chain, trie, hash function and flags
+        for position in [0, 4, 5, 6, 7, 8] {
+            let mut invalid = bytes.clone();
+            invalid[position] ^= 0x80;
+            assert!(TrieProof::from_bytes(&invalid).is_err());
+        }
+
+        // Non-canonical commitment
+        let mut invalid = bytes.clone();
+        invalid[9..41].fill(0xff);
+        assert!(TrieProof::from_bytes(&invalid).is_err());
+
+        // Edge path longer than its length
+        let mut invalid = proof.clone();
+        invalid.nodes = vec![ProofNode::Edge {
+            child: Felt::ONE,
+            path: Felt::from(4),
+            length: 2,
+        }];
+        assert!(TrieProof::from_bytes(&invalid.to_bytes()).is_err());
+
+        // Counts larger than the input
+        let mut huge = bytes[..9].to_vec();
+        huge[8] = FLAG_BATCH;
+        huge.extend(u32::MAX.to_be_bytes());
+        assert!(decode_batch(&huge).is_err());
+    }
+}
@@ -7,15 +7,30 @@ pub enum SnTrieError {
     #[error("Trie not found")]
     TrieNotFound,
 
+    #[error("Transaction not found")]
+    TxNotFound,
+
     #[error("Invalid transaction index")]
     InvalidTxIndex,
 
+    #[error("Invalid message index")]
+    InvalidMessageIndex,
+
+    #[error("Receipt has no events")]
+    ReceiptWithoutEvents,
+
     #[error("Invalid commit")]
     InvalidCommitment,
 
     #[error("Verification error")]
     VerificationError,
 
+    #[error("Invalid proof encoding")]
+    InvalidProofEncoding,
+
     #[error("Unsupported protocol")]
     UnsupportedProtocol,
+
+    #[error("Receipts and L1 gas amounts differ in number")]
+    L1GasMismatch,
 }
@@ -0,0 +1,328 @@
+use crate::error::SnTrieError;
+use crate::proof::{TrieKind, TrieProof};
+use crate::tx_receipt_hash::{
+    calculate_event_hash_legacy, common_receipt_properties, receipt_tx_hash,
+};
+use crate::verify::HashFunction;
+use serde::{Deserialize, Serialize};
+use sn_merkle_trie::conversion::from_u64_to_bits;
+use sn_merkle_trie::transaction::TransactionMerkleTree;
+use sn_merkle_trie::MerkleTree;
+use starknet_types_core::felt::Felt;
+use starknet_types_rpc::{Event, TransactionAndReceipt};
+
+use super::rpc::RpcProvider;
+use super::rpc::GATEWAY_URL;
+
+/// Event proofs for blocks before 0.13.2.
+///
+/// Those blocks have no receipt commitment, and no other commitment covers receipt
+/// fields such as the fee, the messages or the execution status: receipts of these
+/// blocks can't be proven. The only commitment covering receipt data is the event
+/// commitment, a Pedersen trie over the hashes of every event of the block, in order.
+/// This handler proves events against it.
+pub struct LegacyEventsMptHandler<'a> {
+    provider: RpcProvider<'a>,
+    trie: Option<LegacyEventsMpt>,
+}
+
+/// The event trie of a block together with the [`TransactionAndReceipt`] elements
+/// and the range of event leaves each receipt owns.
+pub struct LegacyEventsMpt {
+    pub trie: TransactionMerkleTree,
+    elements: Vec<TransactionAndReceipt<Felt>>,
+    /// Index of the first event leaf of every receipt, followed by the total number of events.
+    event_offsets: Vec<u64>,
+    leaves: Vec<Felt>,
+    root: Felt,
+    root_idx: u64,
+}
+
+/// Proof that a sequence of events sits at consecutive positions of the event
+/// commitment of a block before 0.13.2.
+///
+/// Note: the legacy event hash does not commit to the transaction hash, and the
+/// block does not commit to the number of events of each receipt. The proof binds
+/// the events to their positions in the block only: it does not prove which
+/// transaction emitted them, nor that they are all the events of a transaction.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LegacyEventsProof {
+    /// Index of the first event within the block.
+    pub first_event_index: u64,
+    pub events: Vec<Event<Felt>>,
+    /// One proof per event, in the same order as `events`.
+    pub event_proofs: Vec<TrieProof>,
+}
+
+impl LegacyEventsProof {
+    /// Verifies every event proof against the given event commitment.
+    pub fn verify(&self, event_commitment: Felt) -> Result<(), SnTrieError> {
+        if self.events.is_empty() || self.events.len() != self.event_proofs.len() {
+            return Err(SnTrieError::VerificationError);
+        }
+
+        for (offset, (event, proof)) in self.events.iter().zip(&self.event_proofs).enumerate() {
+            if proof.trie != TrieKind::Event
+                || proof.index != self.first_event_index + offset as u64
+                || proof.leaf != calculate_event_hash_legacy(event)
+            {
+                return Err(SnTrieError::VerificationError);
+            }
+            if !proof
+                .verify(event_commitment, HashFunction::Pedersen)?
+                .is_member()
+            {
+                return Err(SnTrieError::VerificationError);
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl<'a> LegacyEventsMptHandler<'a> {
+    pub fn new(rpc_url: &'a str) -> Result<Self, SnTrieError> {
+        Self::new_with_gateway(rpc_url, GATEWAY_URL)
+    }
+
+    /// Creates a new handler fetching block commitments from the given feeder gateway,
+    /// e.g. [`crate::rpc::MAINNET_GATEWAY_URL`].
+    pub fn new_with_gateway(rpc_url: &'a str, gateway_url: &'a str) -> Result<Self, SnTrieError> {
+        let provider = RpcProvider::new(rpc_url, gateway_url);
+        Ok(Self {
+            provider,
+            trie: None,
+        })
+    }
+
+    /// Retrieves the index of a transaction within the block based on its hash.
+    ///
+    /// Returns an error if the trie is not found or the transaction does not exist.
+    pub fn tx_hash_to_tx_index(&self, tx_hash: Felt) -> Result<u64, SnTrieError> {
+        let trie = self.trie.as_ref().ok_or(SnTrieError::TrieNotFound)?;
+        let tx_index = trie
+            .elements
+            .iter()
+            .position(|tx| receipt_tx_hash(&tx.receipt) == tx_hash)
+            .ok_or(SnTrieError::TxNotFound)?;
+        Ok(tx_index as u64)
+    }
+
+    /// Builds the event trie from a specific transaction hash.
+    ///
+    /// This fetches the block number for the transaction and delegates to [`Self::build_events_tree_from_block`].
+    pub async fn build_events_tree_from_tx_hash(
+        &mut self,
+        tx_hash: Felt,
+    ) -> Result<(), SnTrieError> {
+        let block_number = self.provider.get_tx_block_number(tx_hash).await?;
+        self.build_events_tree_from_block(block_number).await?;
+        Ok(())
+    }
+
+    /// Builds the event trie of a block before 0.13.2 and checks it against the
+    /// block's event commitment.
+    pub async fn build_events_tree_from_block(
+        &mut self,
+        block_number: u64,
+    ) -> Result<(), SnTrieError> {
+        let (txs, expected_commit) = self
+            .provider
+            .get_block_receipts_with_event_commit(block_number)
+            .await?;
+        let protocol = txs.block_header.starknet_version;
+
+        if protocol.as_str() >= "0.13.2" {
+            return Err(SnTrieError::UnsupportedProtocol);
+        }
+
+        self.build_trie(txs.transactions, &expected_commit)?;
+        Ok(())
+    }
+
+    pub fn build_trie(
+        &mut self,
+        txs: Vec<TransactionAndReceipt<Felt>>,
+        expected_commit: &str,
+    ) -> Result<(), SnTrieError> {
+        let mut event_offsets = vec![0];
+        let mut leaves = vec![];
+        for tx in txs.iter() {
+            let events = &common_receipt_properties(&tx.receipt).events;
+            leaves.extend(events.iter().map(calculate_event_hash_legacy));
+            event_offsets.push(leaves.len() as u64);
+        }
+
+        let mut tree = TransactionMerkleTree::Pedersen(MerkleTree::default());
+        for (idx, hash) in leaves.iter().copied().enumerate() {
+            let idx: u64 = idx.try_into().unwrap();
+            let key = from_u64_to_bits(idx);
+            tree.set(key, hash).expect("set failed");
+        }
+
+        let (root, root_idx) = tree.commit().expect("commit failed");
+
+        let cleaned_expected_commit = expected_commit.trim_matches('"').to_string();
+        if cleaned_expected_commit != root.to_hex_string() {
+            return Err(SnTrieError::InvalidCommitment);
+        }
+
+        self.trie = Some(LegacyEventsMpt {
+            trie: tree,
+            elements: txs,
+            event_offsets,
+            leaves,
+            root,
+            root_idx,
+        });
+        Ok(())
+    }
+
+    /// Builds a [`LegacyEventsProof`] covering every event emitted by the transaction
+    /// at `tx_index`.
+    ///
+    /// Fails with [`SnTrieError::ReceiptWithoutEvents`] if the transaction emitted none,
+    /// as there is nothing to prove.
+    pub fn get_tx_events_proof(&self, tx_index: u64) -> Result<LegacyEventsProof, SnTrieError> {
+        let trie = self.trie.as_ref().ok_or(SnTrieError::TrieNotFound)?;
+        let receipt = trie
+            .elements
+            .get(tx_index as usize)
+            .ok_or(SnTrieError::InvalidTxIndex)?;
+        let first_event_index = trie.event_offsets[tx_index as usize];
+        let end_event_index = trie.event_offsets[tx_index as usize + 1];
+        if first_event_index == end_event_index {
+            return Err(SnTrieError::ReceiptWithoutEvents);
+        }
+
+        let event_proofs = (first_event_index..end_event_index)
+            .map(|event_index| self.get_event_proof(event_index))
+            .collect::<Result<Vec<_>, _>>()?;
+
+        Ok(LegacyEventsProof {
+            first_event_index,
+            events: common_receipt_properties(&receipt.receipt).events.clone(),
+            event_proofs,
+        })
+    }
+
+    /// Builds a [`TrieProof`] for the event at `event_index` within the block.
+    pub fn get_event_proof(&self, event_index: u64) -> Result<TrieProof, SnTrieError> {
+        let trie = self.trie.as_ref().ok_or(SnTrieError::TrieNotFound)?;
+        let leaf = *trie
+            .leaves
+            .get(event_index as usize)
+            .ok_or(SnTrieError::InvalidTxIndex)?;
+        let nodes = trie
+            .trie
+            .get_proof(trie.root_idx, from_u64_to_bits(event_index))
+            .unwrap()
+            .ok_or(SnTrieError::TrieNotFound)?;
+        Ok(TrieProof::new(
+            TrieKind::Event,
+            trie.root,
+            event_index,
+            leaf,
+            HashFunction::Pedersen,
+            &nodes,
+        ))
+    }
+
+    /// Retrieves the event commitment of the trie.
+    pub fn get_root(&self) -> Result<Felt, SnTrieError> {
+        let trie = self.trie.as_ref().ok_or(SnTrieError::TrieNotFound)?;
+        Ok(trie.root)
+    }
+
+    /// Retrieves a [`TransactionAndReceipt`] by its index within the block.
+    pub fn get_receipt(&self, tx_index: u64) -> Result<TransactionAndReceipt<Felt>, SnTrieError> {
+        let trie = self.trie.as_ref().ok_or(SnTrieError::TrieNotFound)?;
+        trie.elements
+            .get(tx_index as usize)
+            .ok_or(SnTrieError::InvalidTxIndex)
+            .cloned()
+    }
+
+    /// Retrieves all elements within the block.
+    pub fn get_elements(&self) -> Result<Vec<TransactionAndReceipt<Felt>>, SnTrieError> {
+        let trie = self.trie.as_ref().ok_or(SnTrieError::TrieNotFound)?;
+        Ok(trie.elements.to_vec())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PATHFINDER_URL: &str = "https://pathfinder.sepolia.iosis.tech/";
+
+    fn event(seed: u64) -> Event<Felt> {
+        Event {
+            from_address: Felt::from(seed),
+            keys: vec![Felt::from(seed + 1)],
+            data: vec![Felt::from(seed + 2), Felt::from(seed + 3)],
+        }
+    }
+
+    #[test]
+    fn test_verify_legacy_events_proof() {
+        let events: Vec<Event<Felt>> = (0..5u64).map(|i| event(i * 10)).collect();
+        let mut tree = TransactionMerkleTree::Pedersen(MerkleTree::default());
+        for (idx, event) in events.iter().enumerate() {
+            tree.set(
+                from_u64_to_bits(idx as u64),
+                calculate_event_hash_legacy(event),
+            )
+            .unwrap();
+        }
+        let (root, root_idx) = tree.commit().unwrap();
+
+        // a receipt owning events 1 and 2
+        let event_proofs = (1..3u64)
+            .map(|idx| {
+                let nodes = tree
+                    .get_proof(root_idx, from_u64_to_bits(idx))
+                    .unwrap()
+                    .unwrap();
+                TrieProof::new(
+                    TrieKind::Event,
+                    root,
+                    idx,
+                    calculate_event_hash_legacy(&events[idx as usize]),
+                    HashFunction::Pedersen,
+                    &nodes,
+                )
+            })
+            .collect();
+        let mut proof = LegacyEventsProof {
+            first_event_index: 1,
+            events: events[1..3].to_vec(),
+            event_proofs,
+        };
+        proof.verify(root).unwrap();
+
+        assert!(proof.verify(Felt::ONE).is_err());
+
+        let mut other_trie = proof.clone();
+        other_trie.event_proofs[0].trie = TrieKind::Transaction;
+        assert!(other_trie.verify(root).is_err());
+
+        proof.events.swap(0, 1);
+        assert!(proof.verify(root).is_err());
+    }
+
+    #[tokio::test]
+    async fn test_build_legacy_events_tree_from_block() {
+        let mut handler = LegacyEventsMptHandler::new(PATHFINDER_URL).unwrap();
+        // # 0.13.1
+        let block_number = 51190;
+        handler
+            .build_events_tree_from_block(block_number)
+            .await
+            .unwrap();
+
+        let root = handler.get_root().unwrap();
+        let proof = handler.get_tx_events_proof(0).unwrap();
+        proof.verify(root).unwrap();
+    }
+}
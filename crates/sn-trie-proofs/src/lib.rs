@@ -1,8 +1,13 @@
+pub mod codec;
 pub mod error;
+pub mod legacy_event_trie;
+pub mod message;
+pub mod proof;
 pub mod rpc;
 pub mod tx_hash;
 pub mod tx_receipt_hash;
 pub mod tx_receipt_trie;
 pub mod tx_trie;
+pub mod verify;
 
 pub use error::SnTrieError;
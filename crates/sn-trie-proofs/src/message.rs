@@ -0,0 +1,208 @@
+use serde::{Deserialize, Serialize};
+use starknet_types_core::felt::Felt;
+use starknet_types_rpc::MsgToL1;
+
+use crate::error::SnTrieError;
+use crate::proof::{TrieKind, TrieProof};
+use crate::tx_receipt_hash::{calculate_messages_sent_hash, ReceiptHashFields};
+use crate::verify::HashFunction;
+
+/// Proves that an L2 → L1 message was sent by a transaction.
+///
+/// Made of the inclusion proof of the transaction's receipt, the receipt fields and
+/// every message the receipt commits to, so the verifier can recompute the receipt
+/// hash from the revealed message.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MessageProof {
+    /// Inclusion proof of the receipt in the receipt commitment.
+    pub receipt_proof: TrieProof,
+    /// Receipt fields other than the messages.
+    pub receipt: ReceiptHashFields,
+    /// All messages sent by the transaction, in order.
+    pub messages: Vec<MsgToL1<Felt>>,
+    /// Index of the proven message within `messages`.
+    pub message_index: u64,
+}
+
+impl MessageProof {
+    /// Retrieves the proven message, without verifying it.
+    pub fn message(&self) -> Result<&MsgToL1<Felt>, SnTrieError> {
+        self.messages
+            .get(self.message_index as usize)
+            .ok_or(SnTrieError::InvalidMessageIndex)
+    }
+
+    /// Recomputes the receipt hash from the revealed fields and messages, checks it
+    /// is the leaf of the receipt proof and verifies that proof against the trusted
+    /// receipt commitment of the block.
+    ///
+    /// Receipt commitments only exist since 0.13.2 and are Poseidon tries, so proofs
+    /// for another commitment or hash function are rejected.
+    ///
+    /// Returns the proven message on success.
+    pub fn verify(&self, receipt_commitment: Felt) -> Result<&MsgToL1<Felt>, SnTrieError> {
+        let message = self.message()?;
+
+        let receipt_hash = self
+            .receipt
+            .hash(calculate_messages_sent_hash(&self.messages));
+        if self.receipt_proof.trie != TrieKind::Receipt || receipt_hash != self.receipt_proof.leaf {
+            return Err(SnTrieError::VerificationError);
+        }
+
+        if !self
+            .receipt_proof
+            .verify(receipt_commitment, HashFunction::Poseidon)?
+            .is_member()
+        {
+            return Err(SnTrieError::VerificationError);
+        }
+
+        Ok(message)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use sn_merkle_trie::conversion::from_u64_to_bits;
+    use sn_merkle_trie::transaction::TransactionMerkleTree;
+    use sn_merkle_trie::MerkleTree;
+
+    fn receipt_fields(idx: u64) -> ReceiptHashFields {
+        ReceiptHashFields {
+            transaction_hash: Felt::from(0x1000 + idx),
+            actual_fee: Felt::from(0x2000 + idx),
+            revert_reason_hash: Felt::ZERO,
+            l1_gas: 100 + idx,
+            l1_data_gas: 200 + idx,
+        }
+    }
+
+    fn messages() -> Vec<MsgToL1<Felt>> {
+        vec![
+            MsgToL1 {
+                from_address: Felt::from(0xabcu64),
+                to_address: Felt::from(0xdefu64),
+                payload: vec![Felt::ONE, Felt::TWO],
+            },
+            MsgToL1 {
+                from_address: Felt::from(0xabcu64),
+                to_address: Felt::from(0x123u64),
+                payload: vec![Felt::THREE],
+            },
+        ]
+    }
+
+    fn build_message_proof(message_index: u64) -> MessageProof {
+        let receipt_index = 2;
+        let leaves: Vec<Felt> = (0..4u64)
+            .map(|idx| {
+                let messages = if idx == receipt_index {
+                    messages()
+                } else {
+                    vec![]
+                };
+                receipt_fields(idx).hash(calculate_messages_sent_hash(&messages))
+            })
+            .collect();
+
+        let mut tree = TransactionMerkleTree::Poseidon(MerkleTree::default());
+        for (idx, leaf) in leaves.iter().enumerate() {
+            tree.set(from_u64_to_bits(idx as u64), *leaf).unwrap();
+        }
+        let (root, root_idx) = tree.commit().unwrap();
+        let nodes = tree
+            .get_proof(root_idx, from_u64_to_bits(receipt_index))
+            .unwrap()
+            .unwrap();
+
+        MessageProof {
+            receipt_proof: TrieProof::new(
+                TrieKind::Receipt,
+                root,
+                receipt_index,
+                leaves[receipt_index as usize],
+                HashFunction::Poseidon,
+                &nodes,
+            ),
+            receipt: receipt_fields(receipt_index),
+            messages: messages(),
+            message_index,
+        }
+    }
+
+    #[test]
+    fn test_verify_message_proof() {
+        for message_index in 0..2 {
+            let proof = build_message_proof(message_index);
+            let message = proof.verify(proof.receipt_proof.commitment).unwrap();
+            assert_eq!(message, &messages()[message_index as usize]);
+        }
+    }
+
+    #[test]
+    fn test_verify_message_proof_invalid() {
+        let commitment = build_message_proof(0).receipt_proof.commitment;
+
+        let mut proof = build_message_proof(1);
+        proof.messages[1].payload[0] = Felt::ZERO;
+        assert!(proof.verify(commitment).is_err());
+
+        let mut proof = build_message_proof(0);
+        proof.receipt.l1_gas += 1;
+        assert!(proof.verify(commitment).is_err());
+
+        // A proof of another trie with the same commitment
+        let mut proof = build_message_proof(0);
+        proof.receipt_proof.trie = TrieKind::Transaction;
+        assert!(proof.verify(commitment).is_err());
+
+        let proof = build_message_proof(2);
+        assert!(matches!(
+            proof.verify(commitment),
+            Err(SnTrieError::InvalidMessageIndex)
+        ));
+    }
+
+    #[test]
+    fn test_verify_message_proof_tampered_commitment() {
+        let commitment = build_message_proof(0).receipt_proof.commitment;
+
+        // A forged message with a receipt proof against a commitment of its own making
+        let mut proof = build_message_proof(0);
+        proof.messages[0].to_address = Felt::from(0xbadu64);
+        let leaf = proof
+            .receipt
+            .hash(calculate_messages_sent_hash(&proof.messages));
+        let mut tree = TransactionMerkleTree::Poseidon(MerkleTree::default());
+        tree.set(from_u64_to_bits(2), leaf).unwrap();
+        let (root, root_idx) = tree.commit().unwrap();
+        let nodes = tree
+            .get_proof(root_idx, from_u64_to_bits(2))
+            .unwrap()
+            .unwrap();
+        proof.receipt_proof = TrieProof::new(
+            TrieKind::Receipt,
+            root,
+            2,
+            leaf,
+            HashFunction::Poseidon,
+            &nodes,
+        );
+
+        assert!(proof.verify(root).is_ok());
+        assert!(matches!(
+            proof.verify(commitment),
+            Err(SnTrieError::InvalidCommitment)
+        ));
+
+        // Receipt commitments are Poseidon tries
+        let mut proof = build_message_proof(0);
+        proof.receipt_proof.hash_function = HashFunction::Pedersen;
+        assert!(matches!(
+            proof.verify(commitment),
+            Err(SnTrieError::InvalidCommitment)
+        ));
+    }
+}
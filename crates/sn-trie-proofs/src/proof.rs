@@ -0,0 +1,403 @@
+use serde::{Deserialize, Serialize};
+use sn_merkle_trie::conversion::{from_bits_to_felt, from_felt_to_bits};
+use sn_merkle_trie::node::TrieNode;
+use sn_merkle_trie::Membership;
+use starknet_types_core::felt::Felt;
+
+use crate::error::SnTrieError;
+use crate::verify::{verify_proof, HashFunction};
+
+/// Maximum length of an edge path, in bits.
+const MAX_EDGE_LENGTH: u8 = 251;
+
+/// Serializable form of a [`TrieNode`].
+///
+/// Edge paths are stored as a felt together with their length in bits, so that
+/// leading zero bits of the path are kept.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(tag = "type")]
+pub enum ProofNode {
+    Binary { left: Felt, right: Felt },
+    Edge { child: Felt, path: Felt, length: u8 },
+}
+
+impl From<&TrieNode> for ProofNode {
+    fn from(node: &TrieNode) -> Self {
+        match node {
+            TrieNode::Binary { left, right } => ProofNode::Binary {
+                left: *left,
+                right: *right,
+            },
+            TrieNode::Edge { child, path } => ProofNode::Edge {
+                child: *child,
+                // Safe as the trie never builds edges longer than 251 bits
+                path: from_bits_to_felt(path).unwrap(),
+                length: path.len() as u8,
+            },
+        }
+    }
+}
+
+impl TryFrom<&ProofNode> for TrieNode {
+    type Error = SnTrieError;
+
+    fn try_from(node: &ProofNode) -> Result<Self, SnTrieError> {
+        match node {
+            ProofNode::Binary { left, right } => Ok(TrieNode::Binary {
+                left: *left,
+                right: *right,
+            }),
+            ProofNode::Edge {
+                child,
+                path,
+                length,
+            } => {
+                if *length == 0 || *length > MAX_EDGE_LENGTH {
+                    return Err(SnTrieError::InvalidProofEncoding);
+                }
+                let bits = from_felt_to_bits(path);
+                let (prefix, path) = bits.split_at(bits.len() - *length as usize);
+                // The path must fit in `length` bits.
+                if prefix.any() {
+                    return Err(SnTrieError::InvalidProofEncoding);
+                }
+                Ok(TrieNode::Edge {
+                    child: *child,
+                    path: path.to_bitvec(),
+                })
+            }
+        }
+    }
+}
+
+/// Trie of a block a [`TrieProof`] belongs to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum TrieKind {
+    Transaction,
+    Receipt,
+    Event,
+}
+
+/// A self-contained inclusion proof for a leaf of a Starknet transaction, receipt or
+/// event trie.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TrieProof {
+    pub trie: TrieKind,
+    /// The transaction, receipt or event commitment of the block.
+    pub commitment: Felt,
+    /// Index of the leaf within the block.
+    pub index: u64,
+    /// The leaf hash committed at `index`.
+    pub leaf: Felt,
+    /// Hash function of the trie nodes.
+    pub hash_function: HashFunction,
+    /// Proof nodes, from the root towards the leaf.
+    pub nodes: Vec<ProofNode>,
+}
+
+impl TrieProof {
+    pub fn new(
+        trie: TrieKind,
+        commitment: Felt,
+        index: u64,
+        leaf: Felt,
+        hash_function: HashFunction,
+        nodes: &[TrieNode],
+    ) -> Self {
+        Self {
+            trie,
+            commitment,
+            index,
+            leaf,
+            hash_function,
+            nodes: nodes.iter().map(ProofNode::from).collect(),
+        }
+    }
+
+    /// Converts the proof nodes back into [`TrieNode`]s.
+    pub fn trie_nodes(&self) -> Result<Vec<TrieNode>, SnTrieError> {
+        self.nodes.iter().map(TrieNode::try_from).collect()
+    }
+
+    /// Verifies the proof against a trusted commitment and hash function, e.g. the
+    /// transaction or receipt commitment of a block header and the hash function of its
+    /// protocol version.
+    ///
+    /// Fails with [`SnTrieError::InvalidCommitment`] if the proof was built for another
+    /// commitment or hash function.
+    pub fn verify(
+        &self,
+        commitment: Felt,
+        hash_function: HashFunction,
+    ) -> Result<Membership, SnTrieError> {
+        if self.commitment != commitment || self.hash_function != hash_function {
+            return Err(SnTrieError::InvalidCommitment);
+        }
+        self.check_consistency()
+    }
+
+    /// Verifies the proof against its own commitment and hash function.
+    ///
+    /// Note: both come with the proof, so this only shows the proof is consistent.
+    /// It proves nothing about a block unless the commitment is checked separately,
+    /// see [`TrieProof::verify`].
+    pub fn check_consistency(&self) -> Result<Membership, SnTrieError> {
+        verify_proof(
+            self.commitment,
+            self.index,
+            self.leaf,
+            &self.trie_nodes()?,
+            self.hash_function,
+        )
+    }
+
+    /// Encodes the proof as flat calldata for a Cairo verifier.
+    ///
+    /// The layout follows Cairo's `Serde` for the equivalent struct: enums are
+    /// prefixed by their variant index and arrays by their length.
+    ///
+    /// ```text
+    /// trie (0: Transaction, 1: Receipt, 2: Event), commitment, index, leaf,
+    ///   hash_function (0: Pedersen, 1: Poseidon), nodes_len,
+    ///   0, left, right           -- binary node
+    ///   1, child, path, length   -- edge node
+    /// ```
+    pub fn to_calldata(&self) -> Vec<Felt> {
+        let mut calldata = vec![
+            match self.trie {
+                TrieKind::Transaction => Felt::ZERO,
+                TrieKind::Receipt => Felt::ONE,
+                TrieKind::Event => Felt::TWO,
+            },
+            self.commitment,
+            Felt::from(self.index),
+            self.leaf,
+            match self.hash_function {
+                HashFunction::Pedersen => Felt::ZERO,
+                HashFunction::Poseidon => Felt::ONE,
+            },
+            Felt::from(self.nodes.len()),
+        ];
+        for node in &self.nodes {
+            match node {
+                ProofNode::Binary { left, right } => {
+                    calldata.extend([Felt::ZERO, *left, *right]);
+                }
+                ProofNode::Edge {
+                    child,
+                    path,
+                    length,
+                } => {
+                    calldata.extend([Felt::ONE, *child, *path, Felt::from(*length)]);
+                }
+            }
+        }
+        calldata
+    }
+
+    /// Decodes a proof from the calldata layout of [`TrieProof::to_calldata`].
+    ///
+    /// Fails on truncated input, unknown variants and trailing felts.
+    pub fn from_calldata(calldata: &[Felt]) -> Result<Self, SnTrieError> {
+        let mut reader = CalldataReader(calldata);
+
+        let trie = match reader.next_u64()? {
+            0 => TrieKind::Transaction,
+            1 => TrieKind::Receipt,
+            2 => TrieKind::Event,
+            _ => return Err(SnTrieError::InvalidProofEncoding),
+        };
+        let commitment = reader.next()?;
+        let index = reader.next_u64()?;
+        let leaf = reader.next()?;
+        let hash_function = match reader.next_u64()? {
+            0 => HashFunction::Pedersen,
+            1 => HashFunction::Poseidon,
+            _ => return Err(SnTrieError::InvalidProofEncoding),
+        };
+        let nodes_len = reader.next_u64()?;
+
+        let mut nodes = Vec::new();
+        for _ in 0..nodes_len {
+            let node = match reader.next_u64()? {
+                0 => ProofNode::Binary {
+                    left: reader.next()?,
+                    right: reader.next()?,
+                },
+                1 => ProofNode::Edge {
+                    child: reader.next()?,
+                    path: reader.next()?,
+                    length: u8::try_from(reader.next()?)
+                        .map_err(|_| SnTrieError::InvalidProofEncoding)?,
+                },
+                _ => return Err(SnTrieError::InvalidProofEncoding),
+            };
+            nodes.push(node);
+        }
+
+        if !reader.0.is_empty() {
+            return Err(SnTrieError::InvalidProofEncoding);
+        }
+
+        Ok(Self {
+            trie,
+            commitment,
+            index,
+            leaf,
+            hash_function,
+            nodes,
+        })
+    }
+}
+
+struct CalldataReader<'a>(&'a [Felt]);
+
+impl CalldataReader<'_> {
+    fn next(&mut self) -> Result<Felt, SnTrieError> {
+        let (first, rest) = self
+            .0
+            .split_first()
+            .ok_or(SnTrieError::InvalidProofEncoding)?;
+        self.0 = rest;
+        Ok(*first)
+    }
+
+    fn next_u64(&mut self) -> Result<u64, SnTrieError> {
+        u64::try_from(self.next()?).map_err(|_| SnTrieError::InvalidProofEncoding)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use sn_merkle_trie::conversion::from_u64_to_bits;
+    use sn_merkle_trie::transaction::TransactionMerkleTree;
+    use sn_merkle_trie::MerkleTree;
+
+    fn build_proof(index: u64) -> TrieProof {
+        let leaves: Vec<Felt> = (1..=5u64).map(Felt::from).collect();
+        let mut tree = TransactionMerkleTree::Poseidon(MerkleTree::default());
+        for (idx, leaf) in leaves.iter().enumerate() {
+            tree.set(from_u64_to_bits(idx as u64), *leaf).unwrap();
+        }
+        let (root, root_idx) = tree.commit().unwrap();
+        let nodes = tree
+            .get_proof(root_idx, from_u64_to_bits(index))
+            .unwrap()
+            .unwrap();
+
+        TrieProof::new(
+            TrieKind::Transaction,
+            root,
+            index,
+            leaves[index as usize],
+            HashFunction::Poseidon,
+            &nodes,
+        )
+    }
+
+    #[test]
+    fn test_proof_json_round_trip() {
+        let proof = build_proof(3);
+        assert!(proof.check_consistency().unwrap().is_member());
+
+        let json = serde_json::to_string(&proof).unwrap();
+        let decoded: TrieProof = serde_json::from_str(&json).unwrap();
+        assert_eq!(proof, decoded);
+        assert!(decoded
+            .verify(proof.commitment, HashFunction::Poseidon)
+            .unwrap()
+            .is_member());
+    }
+
+    #[test]
+    fn test_proof_against_trusted_commitment() {
+        let proof = build_proof(2);
+        let commitment = proof.commitment;
+        assert!(proof
+            .verify(commitment, HashFunction::Poseidon)
+            .unwrap()
+            .is_member());
+        assert!(matches!(
+            proof.verify(commitment, HashFunction::Pedersen),
+            Err(SnTrieError::InvalidCommitment)
+        ));
+
+        // A proof carrying its own forged commitment is self-consistent, but is
+        // rejected against the trusted one.
+        let mut forged = build_proof(2);
+        forged.leaf = Felt::from(42u64);
+        let mut tree = TransactionMerkleTree::Poseidon(MerkleTree::default());
+        tree.set(from_u64_to_bits(2), forged.leaf).unwrap();
+        let (root, root_idx) = tree.commit().unwrap();
+        let nodes = tree
+            .get_proof(root_idx, from_u64_to_bits(2))
+            .unwrap()
+            .unwrap();
+        forged = TrieProof::new(
+            TrieKind::Transaction,
+            root,
+            2,
+            forged.leaf,
+            HashFunction::Poseidon,
+            &nodes,
+        );
+        assert!(forged.check_consistency().unwrap().is_member());
+        assert!(matches!(
+            forged.verify(commitment, HashFunction::Poseidon),
+            Err(SnTrieError::InvalidCommitment)
+        ));
+    }
+
+    #[test]
+    fn test_proof_calldata_round_trip() {
+        for index in 0..5 {
+            let proof = build_proof(index);
+            let calldata = proof.to_calldata();
+            let decoded = TrieProof::from_calldata(&calldata).unwrap();
+            assert_eq!(proof, decoded);
+            assert!(decoded
+                .verify(proof.commitment, HashFunction::Poseidon)
+                .unwrap()
+                .is_member());
+        }
+    }
+
+    #[test]
+    fn test_proof_calldata_invalid() {
+        let calldata = build_proof(1).to_calldata();
+
+        // truncated
+        assert!(TrieProof::from_calldata(&calldata[..calldata.len() - 1]).is_err());
+        // trailing data
+        let mut trailing = calldata.clone();
+        trailing.push(Felt::ZERO);
+        assert!(TrieProof::from_calldata(&trailing).is_err());
+        // unknown trie
+        let mut unknown = calldata.clone();
+        unknown[0] = Felt::THREE;
+        assert!(TrieProof::from_calldata(&unknown).is_err());
+        // unknown hash function
+        let mut unknown = calldata.clone();
+        unknown[4] = Felt::TWO;
+        assert!(TrieProof::from_calldata(&unknown).is_err());
+    }
+
+    #[test]
+    fn test_edge_path_must_fit_length() {
+        let node = ProofNode::Edge {
+            child: Felt::ONE,
+            path: Felt::from(4u64),
+            length: 2,
+        };
+        assert!(TrieNode::try_from(&node).is_err());
+
+        let node = ProofNode::Edge {
+            child: Felt::ONE,
+            path: Felt::from(3u64),
+            length: 2,
+        };
+        assert!(TrieNode::try_from(&node).is_ok());
+    }
+}
@@ -1,6 +1,6 @@
 use serde_json::{json, Value};
 use starknet_types_core::felt::Felt;
-use starknet_types_rpc::{BlockWithReceipts, BlockWithTxs};
+use starknet_types_rpc::{BlockWithReceipts, BlockWithTxs, TxnReceiptWithBlockInfo};
 
 use crate::error::SnTrieError;
 
@@ -73,9 +73,64 @@ impl<'a> RpcProvider<'a> {
 
         Ok((get_proof_output, l1_gas_vec, receipt_commitment))
     }
+
+    pub(crate) async fn get_block_receipts_with_event_commit(
+        &self,
+        block_number: u64,
+    ) -> Result<(BlockWithReceipts<Felt>, String), SnTrieError> {
+        let request = json!({
+            "jsonrpc": "2.0",
+            "id": "0",
+            "method": "starknet_getBlockWithReceipts",
+            "params": {
+                "block_id": {"block_number": block_number},
+            }
+        });
+
+        let url = self.url;
+        let provider = reqwest::Client::new();
+        let response = provider.post(url).json(&request).send().await.unwrap();
+        let response_json =
+            serde_json::from_str::<serde_json::Value>(&response.text().await.unwrap()).unwrap()
+                ["result"]
+                .clone();
+
+        let get_proof_output: BlockWithReceipts<Felt> =
+            serde_json::from_value(response_json).unwrap();
+        let gateway = GatewayProvider::new(self.gateway_url);
+        let event_commitment = gateway.get_event_commit(block_number).await?;
+
+        Ok((get_proof_output, event_commitment))
+    }
+
+    pub(crate) async fn get_tx_block_number(&self, tx_hash: Felt) -> Result<u64, SnTrieError> {
+        let request = json!({
+            "jsonrpc": "2.0",
+            "id": "0",
+            "method": "starknet_getTransactionReceipt",
+            "params": {
+                "transaction_hash": tx_hash,
+            }
+        });
+
+        let url = self.url;
+        let provider = reqwest::Client::new();
+        let response = provider.post(url).json(&request).send().await.unwrap();
+        let response_json =
+            serde_json::from_str::<serde_json::Value>(&response.text().await.unwrap()).unwrap()
+                ["result"]
+                .clone();
+
+        let receipt: TxnReceiptWithBlockInfo<Felt> =
+            serde_json::from_value(response_json).map_err(|_| SnTrieError::TxNotFound)?;
+
+        // Receipts of the pending block carry no block number yet.
+        receipt.block_number.ok_or(SnTrieError::TxNotFound)
+    }
 }
 
 pub const GATEWAY_URL: &str = "https://alpha-sepolia.starknet.io";
+pub const MAINNET_GATEWAY_URL: &str = "https://alpha-mainnet.starknet.io";
 
 pub struct GatewayProvider {
     base_url: String,
@@ -106,6 +161,24 @@ impl GatewayProvider {
         }
     }
 
+    async fn get_event_commit(&self, block_number: u64) -> Result<String, SnTrieError> {
+        let url = format!(
+            "{}/feeder_gateway/get_block?blockNumber={}",
+            self.base_url, block_number
+        );
+
+        let client = reqwest::Client::new();
+        let response = client.get(&url).send().await.unwrap();
+
+        if response.status().is_success() {
+            let block_data: Value = response.json().await.unwrap();
+            let block_data: &Value = &block_data["event_commitment"];
+            Ok(block_data.to_string())
+        } else {
+            Err(SnTrieError::GatewayError(response.status().as_u16()))
+        }
+    }
+
     /// Note: This method is only available after 0.13.2
     async fn get_l1_gas(&self, block_number: u64) -> Result<(Vec<u64>, String), SnTrieError> {
         let url = format!(
@@ -119,7 +192,6 @@ impl GatewayProvider {
         if response.status().is_success() {
             let block_data: Value = response.json().await.unwrap();
 
-            println!("{:?}", block_data);
             let receipt_commitment = block_data["receipt_commitment"].to_string();
             let transaction_receipts = block_data["transaction_receipts"].as_array().unwrap();
 
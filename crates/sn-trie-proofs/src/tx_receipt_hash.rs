@@ -1,45 +1,79 @@
+use serde::{Deserialize, Serialize};
 use starknet_types_core::{
     felt::Felt,
-    hash::{Poseidon, StarkHash},
+    hash::{Pedersen, Poseidon, StarkHash},
+};
+use starknet_types_rpc::{
+    CommonReceiptProperties, Event, MsgToL1, TransactionAndReceipt, TxnReceipt,
 };
-use starknet_types_rpc::{MsgToL1, TransactionAndReceipt};
 
 pub fn calculate_receipt_hash(receipt: &TransactionAndReceipt<Felt>, l1_gas: u64) -> Felt {
-    let mut hash_chains = vec![];
-    let common_properties = match &receipt.receipt {
-        starknet_types_rpc::TxnReceipt::Declare(tx_receipt) => {
-            &tx_receipt.common_receipt_properties
-        }
-        starknet_types_rpc::TxnReceipt::Deploy(tx_receipt) => &tx_receipt.common_receipt_properties,
-        starknet_types_rpc::TxnReceipt::DeployAccount(tx_receipt) => {
-            &tx_receipt.common_receipt_properties
-        }
-        starknet_types_rpc::TxnReceipt::Invoke(tx_receipt) => &tx_receipt.common_receipt_properties,
-        starknet_types_rpc::TxnReceipt::L1Handler(tx_receipt) => {
-            &tx_receipt.common_receipt_properties
+    let common_properties = common_receipt_properties(&receipt.receipt);
+    ReceiptHashFields::new(receipt, l1_gas).hash(calculate_messages_sent_hash(
+        &common_properties.messages_sent,
+    ))
+}
+
+/// The receipt fields committed in the receipt hash, apart from the messages sent.
+///
+/// Revealing these next to the messages is enough to recompute the receipt hash.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ReceiptHashFields {
+    pub transaction_hash: Felt,
+    pub actual_fee: Felt,
+    pub revert_reason_hash: Felt,
+    pub l1_gas: u64,
+    pub l1_data_gas: u64,
+}
+
+impl ReceiptHashFields {
+    pub fn new(receipt: &TransactionAndReceipt<Felt>, l1_gas: u64) -> Self {
+        let common_properties = common_receipt_properties(&receipt.receipt);
+        Self {
+            transaction_hash: common_properties.transaction_hash,
+            actual_fee: common_properties.actual_fee.amount,
+            // TODO: calculate_revert_reason_hash
+            revert_reason_hash: Felt::ZERO,
+            l1_gas,
+            l1_data_gas: common_properties
+                .execution_resources
+                .data_availability
+                .l1_data_gas,
         }
-    };
+    }
 
-    hash_chains.push(common_properties.transaction_hash);
-    hash_chains.push(common_properties.actual_fee.amount);
-    hash_chains.push(calculate_messages_sent_hash(
-        &common_properties.messages_sent,
-    ));
-    // TODO: calculate_revert_reason_hash
-    hash_chains.push(Felt::ZERO);
-
-    // chain_gas_consumed
-    hash_chains.push(Felt::ZERO);
-    hash_chains.push(l1_gas.into());
-    hash_chains.push(
-        common_properties
-            .execution_resources
-            .data_availability
-            .l1_data_gas
-            .into(),
-    );
+    /// Hashes the fields together with the hash of the messages sent.
+    pub fn hash(&self, messages_sent_hash: Felt) -> Felt {
+        let hash_chains = vec![
+            self.transaction_hash,
+            self.actual_fee,
+            messages_sent_hash,
+            self.revert_reason_hash,
+            // chain_gas_consumed
+            Felt::ZERO,
+            self.l1_gas.into(),
+            self.l1_data_gas.into(),
+        ];
 
-    Poseidon::hash_array(&hash_chains)
+        Poseidon::hash_array(&hash_chains)
+    }
+}
+
+/// Returns the hash of the transaction the receipt belongs to.
+pub fn receipt_tx_hash(receipt: &TxnReceipt<Felt>) -> Felt {
+    common_receipt_properties(receipt).transaction_hash
+}
+
+pub(crate) fn common_receipt_properties(
+    receipt: &TxnReceipt<Felt>,
+) -> &CommonReceiptProperties<Felt> {
+    match receipt {
+        TxnReceipt::Declare(tx_receipt) => &tx_receipt.common_receipt_properties,
+        TxnReceipt::Deploy(tx_receipt) => &tx_receipt.common_receipt_properties,
+        TxnReceipt::DeployAccount(tx_receipt) => &tx_receipt.common_receipt_properties,
+        TxnReceipt::Invoke(tx_receipt) => &tx_receipt.common_receipt_properties,
+        TxnReceipt::L1Handler(tx_receipt) => &tx_receipt.common_receipt_properties,
+    }
 }
 
 pub fn calculate_messages_sent_hash(messages: &Vec<MsgToL1<Felt>>) -> Felt {
@@ -56,6 +90,17 @@ pub fn calculate_messages_sent_hash(messages: &Vec<MsgToL1<Felt>>) -> Felt {
     Poseidon::hash_array(&hash_chains)
 }
 
+/// Hash of an event leaf in the event commitment of blocks before 0.13.2.
+///
+/// Note: unlike the 0.13.2 rule, the transaction hash is not part of the event hash.
+pub fn calculate_event_hash_legacy(event: &Event<Felt>) -> Felt {
+    Pedersen::hash_array(&[
+        event.from_address,
+        Pedersen::hash_array(&event.keys),
+        Pedersen::hash_array(&event.data),
+    ])
+}
+
 // Returns starknet-keccak of the revert reason ASCII string, or 0 if the transaction succeeded.
 // pub fn calculate_revert_reason_hash(execution_status: String, revert_reason: String) -> Felt {
 
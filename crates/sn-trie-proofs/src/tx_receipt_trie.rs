@@ -1,37 +1,80 @@
 use crate::error::SnTrieError;
-use crate::tx_receipt_hash::calculate_receipt_hash;
+use crate::message::MessageProof;
+use crate::proof::{TrieKind, TrieProof};
+use crate::tx_receipt_hash::{
+    calculate_receipt_hash, common_receipt_properties, receipt_tx_hash, ReceiptHashFields,
+};
+use crate::verify::HashFunction;
 use sn_merkle_trie::conversion::from_u64_to_bits;
 use sn_merkle_trie::node::TrieNode;
 use sn_merkle_trie::transaction::TransactionMerkleTree;
 use sn_merkle_trie::{Membership, MerkleTree};
 use starknet_types_core::hash::{Poseidon, StarkHash};
 use starknet_types_core::{felt::Felt, hash::Pedersen};
+use starknet_types_rpc::TransactionAndReceipt;
 
 use super::rpc::RpcProvider;
 use super::rpc::GATEWAY_URL;
 
-/// Note: only implemented after 0.13.2 version
+/// Note: only implemented after 0.13.2 version. Older blocks have no receipt
+/// commitment, [`crate::legacy_event_trie::LegacyEventsMptHandler`] proves their events.
 pub struct TxReceiptsMptHandler<'a> {
     provider: RpcProvider<'a>,
     trie: Option<TxReceiptsMpt>,
 }
 
+/// The receipt trie together with the [`TransactionAndReceipt`] elements and
+/// the leaf hashes committed for each of them.
 pub struct TxReceiptsMpt {
     pub trie: TransactionMerkleTree,
-    pub elements: Vec<Felt>,
+    elements: Vec<TransactionAndReceipt<Felt>>,
+    leaves: Vec<Felt>,
+    vec_l1_gas: Vec<u64>,
     root: Felt,
     root_idx: u64,
+    protocol: String,
 }
 
 impl<'a> TxReceiptsMptHandler<'a> {
     pub fn new(rpc_url: &'a str) -> Result<Self, SnTrieError> {
-        let provider = RpcProvider::new(rpc_url, GATEWAY_URL);
+        Self::new_with_gateway(rpc_url, GATEWAY_URL)
+    }
+
+    /// Creates a new handler fetching block commitments from the given feeder gateway,
+    /// e.g. [`crate::rpc::MAINNET_GATEWAY_URL`].
+    pub fn new_with_gateway(rpc_url: &'a str, gateway_url: &'a str) -> Result<Self, SnTrieError> {
+        let provider = RpcProvider::new(rpc_url, gateway_url);
         Ok(Self {
             provider,
             trie: None,
         })
     }
 
+    /// Retrieves the index of a transaction within the trie based on its hash.
+    ///
+    /// Returns an error if the trie is not found or the transaction does not exist.
+    pub fn tx_hash_to_tx_index(&self, tx_hash: Felt) -> Result<u64, SnTrieError> {
+        let trie = self.trie.as_ref().ok_or(SnTrieError::TrieNotFound)?;
+        let tx_index = trie
+            .elements
+            .iter()
+            .position(|tx| receipt_tx_hash(&tx.receipt) == tx_hash)
+            .ok_or(SnTrieError::TxNotFound)?;
+        Ok(tx_index as u64)
+    }
+
+    /// Builds the receipt trie from a specific transaction hash.
+    ///
+    /// This fetches the block number for the transaction and delegates to [`Self::build_tx_receipts_tree_from_block`].
+    pub async fn build_tx_receipt_tree_from_tx_hash(
+        &mut self,
+        tx_hash: Felt,
+    ) -> Result<(), SnTrieError> {
+        let block_number = self.provider.get_tx_block_number(tx_hash).await?;
+        self.build_tx_receipts_tree_from_block(block_number).await?;
+        Ok(())
+    }
+
     /// Build
     pub async fn build_tx_receipts_tree_from_block(
         &mut self,
@@ -44,31 +87,39 @@ impl<'a> TxReceiptsMptHandler<'a> {
             .expect("rpc fetch failed");
         let protocol = txs.block_header.starknet_version;
 
+        // TODO: receipt proofs for 0.11 to 0.13.1 blocks. Only their events can be proven
+        // for now, see `LegacyEventsMptHandler`.
         if protocol.as_str() < "0.13.2" {
             return Err(SnTrieError::UnsupportedProtocol);
         }
 
-        let tx_final_hashes: Vec<Felt> = txs
-            .transactions
-            .iter()
-            .zip(vec_l1_gas.iter())
-            .map(|(t, &l1_gas)| calculate_receipt_hash(t, l1_gas))
-            .collect();
-
-        self.build_trie(tx_final_hashes, &expected_commit, &protocol)?;
+        self.build_trie(txs.transactions, vec_l1_gas, &expected_commit, &protocol)?;
         Ok(())
     }
 
+    /// Constructs the trie from the block's receipts and the L1 gas consumed by
+    /// each of them, as reported by the feeder gateway.
+    ///
+    /// Fails if there is not exactly one L1 gas amount per receipt.
     pub fn build_trie(
         &mut self,
-        txs: Vec<Felt>,
+        txs: Vec<TransactionAndReceipt<Felt>>,
+        vec_l1_gas: Vec<u64>,
         expected_commit: &str,
         protocol: &str,
     ) -> Result<(), SnTrieError> {
+        if txs.len() != vec_l1_gas.len() {
+            return Err(SnTrieError::L1GasMismatch);
+        }
+        let leaves: Vec<Felt> = txs
+            .iter()
+            .zip(vec_l1_gas.iter())
+            .map(|(t, &l1_gas)| calculate_receipt_hash(t, l1_gas))
+            .collect();
         let trie = if protocol >= "0.13.2" {
-            self.build_trie_generic::<Poseidon>(txs, expected_commit)?
+            self.build_trie_generic::<Poseidon>(txs, leaves, vec_l1_gas, expected_commit, protocol)?
         } else {
-            self.build_trie_generic::<Pedersen>(txs, expected_commit)?
+            self.build_trie_generic::<Pedersen>(txs, leaves, vec_l1_gas, expected_commit, protocol)?
         };
 
         self.trie = Some(trie);
@@ -77,8 +128,11 @@ impl<'a> TxReceiptsMptHandler<'a> {
 
     fn build_trie_generic<H: StarkHash + 'static>(
         &self,
-        txs: Vec<Felt>,
+        txs: Vec<TransactionAndReceipt<Felt>>,
+        leaves: Vec<Felt>,
+        vec_l1_gas: Vec<u64>,
         expected_commit: &str,
+        protocol: &str,
     ) -> Result<TxReceiptsMpt, SnTrieError> {
         let mut tree = if std::any::TypeId::of::<H>() == std::any::TypeId::of::<Poseidon>() {
             TransactionMerkleTree::Poseidon(MerkleTree::default())
@@ -86,7 +140,7 @@ impl<'a> TxReceiptsMptHandler<'a> {
             TransactionMerkleTree::Pedersen(MerkleTree::default())
         };
 
-        for (idx, hash) in txs.clone().into_iter().enumerate() {
+        for (idx, hash) in leaves.iter().copied().enumerate() {
             let idx: u64 = idx.try_into().unwrap();
             let key = from_u64_to_bits(idx);
             tree.set(key, hash).expect("set failed");
@@ -103,8 +157,11 @@ impl<'a> TxReceiptsMptHandler<'a> {
         Ok(TxReceiptsMpt {
             trie: tree,
             elements: txs,
+            leaves,
+            vec_l1_gas,
             root,
             root_idx,
+            protocol: protocol.to_string(),
         })
     }
 
@@ -119,6 +176,59 @@ impl<'a> TxReceiptsMptHandler<'a> {
         Ok(proof)
     }
 
+    /// Builds a serializable [`TrieProof`] for the receipt at a given index.
+    pub fn get_trie_proof(&self, tx_index: u64) -> Result<TrieProof, SnTrieError> {
+        let trie = self.trie.as_ref().ok_or(SnTrieError::TrieNotFound)?;
+        let leaf = *trie
+            .leaves
+            .get(tx_index as usize)
+            .ok_or(SnTrieError::InvalidTxIndex)?;
+        let hash_function = match trie.trie {
+            TransactionMerkleTree::Pedersen(_) => HashFunction::Pedersen,
+            TransactionMerkleTree::Poseidon(_) => HashFunction::Poseidon,
+        };
+        let nodes = self.get_proof(tx_index)?;
+        Ok(TrieProof::new(
+            TrieKind::Receipt,
+            trie.root,
+            tx_index,
+            leaf,
+            hash_function,
+            &nodes,
+        ))
+    }
+
+    /// Builds a [`MessageProof`] for the `message_index`-th L2 → L1 message sent by
+    /// the transaction at `tx_index`.
+    pub fn get_message_proof(
+        &self,
+        tx_index: u64,
+        message_index: u64,
+    ) -> Result<MessageProof, SnTrieError> {
+        let trie = self.trie.as_ref().ok_or(SnTrieError::TrieNotFound)?;
+        let receipt = trie
+            .elements
+            .get(tx_index as usize)
+            .ok_or(SnTrieError::InvalidTxIndex)?;
+        let l1_gas = *trie
+            .vec_l1_gas
+            .get(tx_index as usize)
+            .ok_or(SnTrieError::InvalidTxIndex)?;
+        let messages = common_receipt_properties(&receipt.receipt)
+            .messages_sent
+            .clone();
+        if message_index as usize >= messages.len() {
+            return Err(SnTrieError::InvalidMessageIndex);
+        }
+
+        Ok(MessageProof {
+            receipt_proof: self.get_trie_proof(tx_index)?,
+            receipt: ReceiptHashFields::new(receipt, l1_gas),
+            messages,
+            message_index,
+        })
+    }
+
     pub fn verify_proof(
         &self,
         tx_index: u64,
@@ -126,7 +236,7 @@ impl<'a> TxReceiptsMptHandler<'a> {
     ) -> Result<Membership, SnTrieError> {
         let trie = self.trie.as_ref().ok_or(SnTrieError::TrieNotFound)?;
         let value = trie
-            .elements
+            .leaves
             .get(tx_index as usize)
             .ok_or(SnTrieError::InvalidTxIndex)?;
 
@@ -142,6 +252,41 @@ impl<'a> TxReceiptsMptHandler<'a> {
         let root_idx = trie.root_idx;
         Ok(root_idx)
     }
+
+    /// Retrieves the receipt commitment of the trie.
+    pub fn get_root(&self) -> Result<Felt, SnTrieError> {
+        let trie = self.trie.as_ref().ok_or(SnTrieError::TrieNotFound)?;
+        Ok(trie.root)
+    }
+
+    /// Retrieves a [`TransactionAndReceipt`] by its index within the trie.
+    pub fn get_receipt(&self, tx_index: u64) -> Result<TransactionAndReceipt<Felt>, SnTrieError> {
+        let trie = self.trie.as_ref().ok_or(SnTrieError::TrieNotFound)?;
+        trie.elements
+            .get(tx_index as usize)
+            .ok_or(SnTrieError::InvalidTxIndex)
+            .cloned()
+    }
+
+    /// Retrieves the Starknet protocol version of the block the trie was built from.
+    pub fn get_protocol_version(&self) -> Result<String, SnTrieError> {
+        let trie = self.trie.as_ref().ok_or(SnTrieError::TrieNotFound)?;
+        Ok(trie.protocol.clone())
+    }
+
+    /// Retrieves all elements within the trie.
+    pub fn get_elements(&self) -> Result<Vec<TransactionAndReceipt<Felt>>, SnTrieError> {
+        let trie = self.trie.as_ref().ok_or(SnTrieError::TrieNotFound)?;
+        Ok(trie.elements.to_vec())
+    }
+
+    /// Retrieves the leaf hashes committed in the trie, in index order.
+    ///
+    /// These are what the former public `elements` field of the trie held.
+    pub fn get_leaves(&self) -> Result<Vec<Felt>, SnTrieError> {
+        let trie = self.trie.as_ref().ok_or(SnTrieError::TrieNotFound)?;
+        Ok(trie.leaves.to_vec())
+    }
 }
 
 #[cfg(test)]
@@ -150,6 +295,16 @@ mod tests {
 
     const PATHFINDER_URL: &str = "https://pathfinder.sepolia.iosis.tech/";
 
+    #[test]
+    fn test_build_trie_l1_gas_mismatch() {
+        let mut handler = TxReceiptsMptHandler::new(PATHFINDER_URL).unwrap();
+        assert!(matches!(
+            handler.build_trie(vec![], vec![1], "0x0", "0.13.2"),
+            Err(SnTrieError::L1GasMismatch)
+        ));
+        assert!(handler.trie.is_none());
+    }
+
     #[tokio::test]
     async fn test_build_tx_tree_from_block_4() {
         let mut handler = TxReceiptsMptHandler::new(PATHFINDER_URL).unwrap();
@@ -180,4 +335,30 @@ mod tests {
 
         assert!(membership.is_member());
     }
+
+    #[tokio::test]
+    async fn test_build_tx_receipts_tree_from_tx_hash() {
+        let mut handler = TxReceiptsMptHandler::new(PATHFINDER_URL).unwrap();
+        //  # 0.13.2
+        let block_number = 99708;
+        handler
+            .build_tx_receipts_tree_from_block(block_number)
+            .await
+            .unwrap();
+        let tx_hash = receipt_tx_hash(&handler.get_receipt(2).unwrap().receipt);
+
+        let mut handler2 = TxReceiptsMptHandler::new(PATHFINDER_URL).unwrap();
+        handler2
+            .build_tx_receipt_tree_from_tx_hash(tx_hash)
+            .await
+            .unwrap();
+        let tx_index = handler2.tx_hash_to_tx_index(tx_hash).unwrap();
+        assert_eq!(tx_index, 2);
+        assert_eq!(handler.get_root().unwrap(), handler2.get_root().unwrap());
+
+        let proof = handler2.get_proof(tx_index).unwrap();
+        let membership: Membership = handler2.verify_proof(tx_index, proof).unwrap();
+
+        assert!(membership.is_member());
+    }
 }
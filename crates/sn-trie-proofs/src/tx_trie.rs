@@ -1,10 +1,13 @@
 use crate::error::SnTrieError;
+use crate::proof::{TrieKind, TrieProof};
+use crate::verify::HashFunction;
 use sn_merkle_trie::conversion::from_u64_to_bits;
 use sn_merkle_trie::node::TrieNode;
 use sn_merkle_trie::transaction::TransactionMerkleTree;
 use sn_merkle_trie::{Membership, MerkleTree};
 use starknet_types_core::hash::{Poseidon, StarkHash};
 use starknet_types_core::{felt::Felt, hash::Pedersen};
+use starknet_types_rpc::TxnWithHash;
 
 use super::rpc::GATEWAY_URL;
 use super::{rpc::RpcProvider, tx_hash::calculate_transaction_hash};
@@ -14,22 +17,54 @@ pub struct TxsMptHandler<'a> {
     trie: Option<TxsMpt>,
 }
 
+/// The transaction trie together with the [`TxnWithHash`] elements and the
+/// leaf hashes committed for each of them.
 pub struct TxsMpt {
     pub trie: TransactionMerkleTree,
-    pub elements: Vec<Felt>,
+    elements: Vec<TxnWithHash<Felt>>,
+    leaves: Vec<Felt>,
     root: Felt,
     root_idx: u64,
+    protocol: String,
 }
 
 impl<'a> TxsMptHandler<'a> {
     pub fn new(rpc_url: &'a str) -> Result<Self, SnTrieError> {
-        let provider = RpcProvider::new(rpc_url, GATEWAY_URL);
+        Self::new_with_gateway(rpc_url, GATEWAY_URL)
+    }
+
+    /// Creates a new handler fetching block commitments from the given feeder gateway,
+    /// e.g. [`crate::rpc::MAINNET_GATEWAY_URL`].
+    pub fn new_with_gateway(rpc_url: &'a str, gateway_url: &'a str) -> Result<Self, SnTrieError> {
+        let provider = RpcProvider::new(rpc_url, gateway_url);
         Ok(Self {
             provider,
             trie: None,
         })
     }
 
+    /// Retrieves the index of a transaction within the trie based on its hash.
+    ///
+    /// Returns an error if the trie is not found or the transaction does not exist.
+    pub fn tx_hash_to_tx_index(&self, tx_hash: Felt) -> Result<u64, SnTrieError> {
+        let trie = self.trie.as_ref().ok_or(SnTrieError::TrieNotFound)?;
+        let tx_index = trie
+            .elements
+            .iter()
+            .position(|tx| tx.transaction_hash == tx_hash)
+            .ok_or(SnTrieError::TxNotFound)?;
+        Ok(tx_index as u64)
+    }
+
+    /// Builds the transaction trie from a specific transaction hash.
+    ///
+    /// This fetches the block number for the transaction and delegates to [`Self::build_tx_tree_from_block`].
+    pub async fn build_tx_tree_from_tx_hash(&mut self, tx_hash: Felt) -> Result<(), SnTrieError> {
+        let block_number = self.provider.get_tx_block_number(tx_hash).await?;
+        self.build_tx_tree_from_block(block_number).await?;
+        Ok(())
+    }
+
     pub async fn build_tx_tree_from_block(&mut self, block_number: u64) -> Result<(), SnTrieError> {
         let (txs, expected_commit) = self
             .provider
@@ -37,25 +72,26 @@ impl<'a> TxsMptHandler<'a> {
             .await
             .expect("rpc fetch failed");
         let protocol = txs.block_header.starknet_version;
-        let tx_final_hashes: Vec<Felt> = txs
-            .transactions
-            .iter()
-            .map(|t| calculate_transaction_hash(t, &protocol))
-            .collect();
-        self.build_trie(tx_final_hashes, &expected_commit, &protocol)?;
+        self.build_trie(txs.transactions, &expected_commit, &protocol)?;
         Ok(())
     }
 
+    /// Constructs the trie from the block's transactions, hashing each leaf with
+    /// the rules of the given protocol version.
     pub fn build_trie(
         &mut self,
-        txs: Vec<Felt>,
+        txs: Vec<TxnWithHash<Felt>>,
         expected_commit: &str,
         protocol: &str,
     ) -> Result<(), SnTrieError> {
+        let leaves: Vec<Felt> = txs
+            .iter()
+            .map(|t| calculate_transaction_hash(t, protocol))
+            .collect();
         let trie = if protocol >= "0.13.2" {
-            self.build_trie_generic::<Poseidon>(txs, expected_commit)?
+            self.build_trie_generic::<Poseidon>(txs, leaves, expected_commit, protocol)?
         } else {
-            self.build_trie_generic::<Pedersen>(txs, expected_commit)?
+            self.build_trie_generic::<Pedersen>(txs, leaves, expected_commit, protocol)?
         };
 
         self.trie = Some(trie);
@@ -64,8 +100,10 @@ impl<'a> TxsMptHandler<'a> {
 
     fn build_trie_generic<H: StarkHash + 'static>(
         &self,
-        txs: Vec<Felt>,
+        txs: Vec<TxnWithHash<Felt>>,
+        leaves: Vec<Felt>,
         expected_commit: &str,
+        protocol: &str,
     ) -> Result<TxsMpt, SnTrieError> {
         let mut tree = if std::any::TypeId::of::<H>() == std::any::TypeId::of::<Poseidon>() {
             TransactionMerkleTree::Poseidon(MerkleTree::default())
@@ -73,7 +111,7 @@ impl<'a> TxsMptHandler<'a> {
             TransactionMerkleTree::Pedersen(MerkleTree::default())
         };
 
-        for (idx, hash) in txs.clone().into_iter().enumerate() {
+        for (idx, hash) in leaves.iter().copied().enumerate() {
             let idx: u64 = idx.try_into().unwrap();
             let key = from_u64_to_bits(idx);
             tree.set(key, hash).expect("set failed");
@@ -90,8 +128,10 @@ impl<'a> TxsMptHandler<'a> {
         Ok(TxsMpt {
             trie: tree,
             elements: txs,
+            leaves,
             root,
             root_idx,
+            protocol: protocol.to_string(),
         })
     }
 
@@ -106,6 +146,28 @@ impl<'a> TxsMptHandler<'a> {
         Ok(proof)
     }
 
+    /// Builds a serializable [`TrieProof`] for the transaction at a given index.
+    pub fn get_trie_proof(&self, tx_index: u64) -> Result<TrieProof, SnTrieError> {
+        let trie = self.trie.as_ref().ok_or(SnTrieError::TrieNotFound)?;
+        let leaf = *trie
+            .leaves
+            .get(tx_index as usize)
+            .ok_or(SnTrieError::InvalidTxIndex)?;
+        let hash_function = match trie.trie {
+            TransactionMerkleTree::Pedersen(_) => HashFunction::Pedersen,
+            TransactionMerkleTree::Poseidon(_) => HashFunction::Poseidon,
+        };
+        let nodes = self.get_proof(tx_index)?;
+        Ok(TrieProof::new(
+            TrieKind::Transaction,
+            trie.root,
+            tx_index,
+            leaf,
+            hash_function,
+            &nodes,
+        ))
+    }
+
     pub fn verify_proof(
         &self,
         tx_index: u64,
@@ -113,7 +175,7 @@ impl<'a> TxsMptHandler<'a> {
     ) -> Result<Membership, SnTrieError> {
         let trie = self.trie.as_ref().ok_or(SnTrieError::TrieNotFound)?;
         let value = trie
-            .elements
+            .leaves
             .get(tx_index as usize)
             .ok_or(SnTrieError::InvalidTxIndex)?;
 
@@ -129,6 +191,41 @@ impl<'a> TxsMptHandler<'a> {
         let root_idx = trie.root_idx;
         Ok(root_idx)
     }
+
+    /// Retrieves the transaction commitment of the trie.
+    pub fn get_root(&self) -> Result<Felt, SnTrieError> {
+        let trie = self.trie.as_ref().ok_or(SnTrieError::TrieNotFound)?;
+        Ok(trie.root)
+    }
+
+    /// Retrieves a [`TxnWithHash`] by its index within the trie.
+    pub fn get_tx(&self, tx_index: u64) -> Result<TxnWithHash<Felt>, SnTrieError> {
+        let trie = self.trie.as_ref().ok_or(SnTrieError::TrieNotFound)?;
+        trie.elements
+            .get(tx_index as usize)
+            .ok_or(SnTrieError::InvalidTxIndex)
+            .cloned()
+    }
+
+    /// Retrieves the Starknet protocol version of the block the trie was built from.
+    pub fn get_protocol_version(&self) -> Result<String, SnTrieError> {
+        let trie = self.trie.as_ref().ok_or(SnTrieError::TrieNotFound)?;
+        Ok(trie.protocol.clone())
+    }
+
+    /// Retrieves all elements within the trie.
+    pub fn get_elements(&self) -> Result<Vec<TxnWithHash<Felt>>, SnTrieError> {
+        let trie = self.trie.as_ref().ok_or(SnTrieError::TrieNotFound)?;
+        Ok(trie.elements.to_vec())
+    }
+
+    /// Retrieves the leaf hashes committed in the trie, in index order.
+    ///
+    /// These are what the former public `elements` field of the trie held.
+    pub fn get_leaves(&self) -> Result<Vec<Felt>, SnTrieError> {
+        let trie = self.trie.as_ref().ok_or(SnTrieError::TrieNotFound)?;
+        Ok(trie.leaves.to_vec())
+    }
 }
 
 #[cfg(test)]
@@ -249,4 +346,39 @@ mod tests {
 
         assert!(membership.is_member());
     }
+
+    #[tokio::test]
+    async fn test_build_tx_tree_from_tx_hash() {
+        let mut handler = TxsMptHandler::new(PATHFINDER_URL).unwrap();
+        //  # 0.13.2
+        let block_number = 99708;
+        handler
+            .build_tx_tree_from_block(block_number)
+            .await
+            .unwrap();
+        let tx_hash = handler.get_tx(1).unwrap().transaction_hash;
+
+        let mut handler2 = TxsMptHandler::new(PATHFINDER_URL).unwrap();
+        handler2.build_tx_tree_from_tx_hash(tx_hash).await.unwrap();
+        let tx_index = handler2.tx_hash_to_tx_index(tx_hash).unwrap();
+        assert_eq!(tx_index, 1);
+        assert_eq!(handler.get_root().unwrap(), handler2.get_root().unwrap());
+        assert_eq!(
+            handler.get_elements().unwrap(),
+            handler2.get_elements().unwrap()
+        );
+
+        let proof = handler2.get_proof(tx_index).unwrap();
+        let membership: Membership = handler2.verify_proof(tx_index, proof).unwrap();
+
+        assert!(membership.is_member());
+
+        let trie_proof = handler2.get_trie_proof(tx_index).unwrap();
+        let calldata = trie_proof.to_calldata();
+        let decoded = TrieProof::from_calldata(&calldata).unwrap();
+        assert!(decoded
+            .verify(handler2.get_root().unwrap(), trie_proof.hash_function)
+            .unwrap()
+            .is_member());
+    }
 }
@@ -0,0 +1,228 @@
+use serde::{Deserialize, Serialize};
+use sn_merkle_trie::conversion::from_u64_to_bits;
+use sn_merkle_trie::node::TrieNode;
+use sn_merkle_trie::Membership;
+use starknet_types_core::felt::Felt;
+use starknet_types_core::hash::{Pedersen, Poseidon, StarkHash};
+use starknet_types_rpc::{TransactionAndReceipt, TxnWithHash};
+
+use crate::error::SnTrieError;
+use crate::tx_hash::calculate_transaction_hash;
+use crate::tx_receipt_hash::calculate_receipt_hash;
+
+/// Hash function used for the nodes of a Starknet commitment trie.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum HashFunction {
+    Pedersen,
+    Poseidon,
+}
+
+impl HashFunction {
+    /// Returns the hash function the transaction and receipt tries use for the given
+    /// protocol version. Poseidon replaced Pedersen in 0.13.2.
+    pub fn from_protocol(protocol: &str) -> Self {
+        if parse_version(protocol) >= vec![0, 13, 2] {
+            HashFunction::Poseidon
+        } else {
+            HashFunction::Pedersen
+        }
+    }
+}
+
+/// Splits a protocol version into its numeric components, so that e.g. 0.13.10 comes after
+/// 0.13.2. Components that are not numbers count as 0.
+fn parse_version(protocol: &str) -> Vec<u64> {
+    protocol
+        .split('.')
+        .map(|part| part.parse().unwrap_or(0))
+        .collect()
+}
+
+/// Verifies a proof for the leaf at `index` against a commitment, without the trie.
+///
+/// Returns [`Membership::NonMember`] when the proof shows that no leaf exists at `index`,
+/// and an error when the proof does not hash up to the commitment or does not end in `leaf`.
+pub fn verify_proof(
+    commitment: Felt,
+    index: u64,
+    leaf: Felt,
+    proof: &[TrieNode],
+    hash_function: HashFunction,
+) -> Result<Membership, SnTrieError> {
+    match hash_function {
+        HashFunction::Pedersen => verify_proof_generic::<Pedersen>(commitment, index, leaf, proof),
+        HashFunction::Poseidon => verify_proof_generic::<Poseidon>(commitment, index, leaf, proof),
+    }
+}
+
+/// Verifies a transaction proof, hashing the leaf from the original transaction
+/// with the rules of the given protocol version.
+pub fn verify_tx_proof(
+    commitment: Felt,
+    index: u64,
+    tx: &TxnWithHash<Felt>,
+    protocol: &str,
+    proof: &[TrieNode],
+) -> Result<Membership, SnTrieError> {
+    let leaf = calculate_transaction_hash(tx, protocol);
+    verify_proof(
+        commitment,
+        index,
+        leaf,
+        proof,
+        HashFunction::from_protocol(protocol),
+    )
+}
+
+/// Verifies a receipt proof, hashing the leaf from the original receipt.
+///
+/// Note: only receipt commitments from 0.13.2 onwards are supported.
+pub fn verify_receipt_proof(
+    commitment: Felt,
+    index: u64,
+    receipt: &TransactionAndReceipt<Felt>,
+    l1_gas: u64,
+    proof: &[TrieNode],
+) -> Result<Membership, SnTrieError> {
+    let leaf = calculate_receipt_hash(receipt, l1_gas);
+    verify_proof(commitment, index, leaf, proof, HashFunction::Poseidon)
+}
+
+fn verify_proof_generic<H: StarkHash>(
+    commitment: Felt,
+    index: u64,
+    leaf: Felt,
+    proof: &[TrieNode],
+) -> Result<Membership, SnTrieError> {
+    let key = from_u64_to_bits(index);
+    let mut remaining_path = key.as_bitslice();
+    let mut expected_hash = commitment;
+
+    for node in proof {
+        if node.hash::<H>() != expected_hash {
+            return Err(SnTrieError::VerificationError);
+        }
+
+        match node {
+            TrieNode::Binary { left, right } => {
+                let (direction, rest) = remaining_path
+                    .split_first()
+                    .ok_or(SnTrieError::VerificationError)?;
+                expected_hash = if *direction { *right } else { *left };
+                remaining_path = rest;
+            }
+            TrieNode::Edge { child, path } => {
+                if path.len() > remaining_path.len() {
+                    return Err(SnTrieError::VerificationError);
+                }
+                // A diverging edge proves that no leaf exists under the key.
+                if path.as_bitslice() != remaining_path[..path.len()] {
+                    return Ok(Membership::NonMember);
+                }
+                expected_hash = *child;
+                remaining_path = &remaining_path[path.len()..];
+            }
+        }
+    }
+
+    if remaining_path.is_empty() && expected_hash == leaf {
+        Ok(Membership::Member)
+    } else {
+        Err(SnTrieError::VerificationError)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use sn_merkle_trie::transaction::TransactionMerkleTree;
+    use sn_merkle_trie::MerkleTree;
+
+    fn build_tree(
+        leaves: &[Felt],
+        hash_function: HashFunction,
+    ) -> (TransactionMerkleTree, Felt, u64) {
+        let mut tree = match hash_function {
+            HashFunction::Pedersen => TransactionMerkleTree::Pedersen(MerkleTree::default()),
+            HashFunction::Poseidon => TransactionMerkleTree::Poseidon(MerkleTree::default()),
+        };
+        for (idx, leaf) in leaves.iter().enumerate() {
+            tree.set(from_u64_to_bits(idx as u64), *leaf).unwrap();
+        }
+        let (root, root_idx) = tree.commit().unwrap();
+        (tree, root, root_idx)
+    }
+
+    #[test]
+    fn test_hash_function_from_protocol() {
+        for protocol in ["0.11.1", "0.13.1.1", "0.13.1", "0.9.0"] {
+            assert_eq!(
+                HashFunction::from_protocol(protocol),
+                HashFunction::Pedersen
+            );
+        }
+        for protocol in ["0.13.2", "0.13.2.1", "0.13.10", "0.14.0", "1.0.0"] {
+            assert_eq!(
+                HashFunction::from_protocol(protocol),
+                HashFunction::Poseidon
+            );
+        }
+    }
+
+    #[test]
+    fn test_verify_proof_membership() {
+        let leaves: Vec<Felt> = (1..=5u64).map(Felt::from).collect();
+        for hash_function in [HashFunction::Pedersen, HashFunction::Poseidon] {
+            let (tree, root, root_idx) = build_tree(&leaves, hash_function);
+            for (idx, leaf) in leaves.iter().enumerate() {
+                let idx = idx as u64;
+                let proof = tree
+                    .get_proof(root_idx, from_u64_to_bits(idx))
+                    .unwrap()
+                    .unwrap();
+                let membership = verify_proof(root, idx, *leaf, &proof, hash_function).unwrap();
+                assert!(membership.is_member());
+            }
+        }
+    }
+
+    #[test]
+    fn test_verify_proof_non_membership() {
+        let leaves: Vec<Felt> = (1..=4u64).map(Felt::from).collect();
+        let (tree, root, root_idx) = build_tree(&leaves, HashFunction::Poseidon);
+
+        let proof = tree
+            .get_proof(root_idx, from_u64_to_bits(5))
+            .unwrap()
+            .unwrap();
+        let membership = verify_proof(root, 5, Felt::ZERO, &proof, HashFunction::Poseidon).unwrap();
+        assert_eq!(membership, Membership::NonMember);
+    }
+
+    #[test]
+    fn test_verify_proof_invalid() {
+        let leaves: Vec<Felt> = (1..=4u64).map(Felt::from).collect();
+        let (tree, root, root_idx) = build_tree(&leaves, HashFunction::Pedersen);
+        let proof = tree
+            .get_proof(root_idx, from_u64_to_bits(1))
+            .unwrap()
+            .unwrap();
+
+        // wrong leaf value
+        assert!(verify_proof(root, 1, leaves[0], &proof, HashFunction::Pedersen).is_err());
+        // wrong hash function
+        assert!(verify_proof(root, 1, leaves[1], &proof, HashFunction::Poseidon).is_err());
+        // wrong commitment
+        assert!(verify_proof(Felt::ONE, 1, leaves[1], &proof, HashFunction::Pedersen).is_err());
+        // truncated proof
+        assert!(verify_proof(
+            root,
+            1,
+            leaves[1],
+            &proof[..proof.len() - 1],
+            HashFunction::Pedersen
+        )
+        .is_err());
+    }
+}
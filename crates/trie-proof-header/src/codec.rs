@@ -0,0 +1,317 @@
+//! Byte-level encoding shared by the Ethereum and Starknet proofs.
+//!
+//! Integers are big-endian and byte strings are prefixed by their length as a `u32`. A list
+//! of nodes is written as `node_count (4) | nodes`. A batch holds `proof_count (4)` proofs,
+//! each written as its fields followed by its list of nodes. With [`FLAG_DEDUP`], the
+//! distinct nodes of the batch are stored once in a table (`node_count (4) | nodes`) placed
+//! before the proofs, and proofs refer to them by their `u32` index in the table.
+//!
+//! How fields and nodes are encoded is up to each chain.
+
+use std::collections::{HashMap, HashSet};
+use std::hash::Hash;
+
+use crate::{Header, FLAG_BATCH, FLAG_DEDUP};
+
+/// Malformed encoding. Each chain maps it to its own error.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct InvalidEncoding;
+
+/// Returns the flags of a batch.
+pub fn batch_flags(dedup: bool) -> u8 {
+    if dedup {
+        FLAG_BATCH | FLAG_DEDUP
+    } else {
+        FLAG_BATCH
+    }
+}
+
+/// Returns whether the nodes of a batch with these flags are deduplicated, failing if the
+/// flags are not those of a batch.
+pub fn batch_dedup(flags: u8) -> Result<bool, InvalidEncoding> {
+    match flags {
+        FLAG_BATCH => Ok(false),
+        flags if flags == FLAG_BATCH | FLAG_DEDUP => Ok(true),
+        _ => Err(InvalidEncoding),
+    }
+}
+
+pub struct Writer(Vec<u8>);
+
+impl Writer {
+    pub fn new(header: Header) -> Self {
+        Self(header.to_bytes().to_vec())
+    }
+
+    pub fn u8(&mut self, value: u8) {
+        self.0.push(value);
+    }
+
+    pub fn u32(&mut self, value: u32) {
+        self.0.extend(value.to_be_bytes());
+    }
+
+    pub fn u64(&mut self, value: u64) {
+        self.0.extend(value.to_be_bytes());
+    }
+
+    /// Writes bytes of a fixed length, without prefix.
+    pub fn raw(&mut self, value: &[u8]) {
+        self.0.extend(value);
+    }
+
+    /// Writes bytes prefixed by their length.
+    pub fn bytes(&mut self, value: &[u8]) {
+        self.u32(value.len() as u32);
+        self.0.extend(value);
+    }
+
+    /// Writes `node_count (4) | nodes`.
+    pub fn nodes<N>(&mut self, nodes: &[N], mut write_node: impl FnMut(&mut Self, &N)) {
+        self.u32(nodes.len() as u32);
+        for node in nodes {
+            write_node(self, node);
+        }
+    }
+
+    pub fn into_bytes(self) -> Vec<u8> {
+        self.0
+    }
+}
+
+pub struct Reader<'a>(&'a [u8]);
+
+impl<'a> Reader<'a> {
+    /// Splits the header off `bytes`, failing on an unknown magic or version.
+    pub fn new(bytes: &'a [u8]) -> Result<(Header, Self), InvalidEncoding> {
+        let (header, rest) = Header::split(bytes).ok_or(InvalidEncoding)?;
+        Ok((header, Self(rest)))
+    }
+
+    pub fn take(&mut self, len: usize) -> Result<&'a [u8], InvalidEncoding> {
+        if self.0.len() < len {
+            return Err(InvalidEncoding);
+        }
+        let (head, rest) = self.0.split_at(len);
+        self.0 = rest;
+        Ok(head)
+    }
+
+    pub fn array<const N: usize>(&mut self) -> Result<[u8; N], InvalidEncoding> {
+        Ok(self.take(N)?.try_into().unwrap())
+    }
+
+    pub fn u8(&mut self) -> Result<u8, InvalidEncoding> {
+        Ok(self.take(1)?[0])
+    }
+
+    pub fn u32(&mut self) -> Result<u32, InvalidEncoding> {
+        self.array().map(u32::from_be_bytes)
+    }
+
+    pub fn u64(&mut self) -> Result<u64, InvalidEncoding> {
+        self.array().map(u64::from_be_bytes)
+    }
+
+    /// Reads bytes prefixed by their length.
+    pub fn bytes(&mut self) -> Result<&'a [u8], InvalidEncoding> {
+        let len = self.u32()? as usize;
+        self.take(len)
+    }
+
+    /// Reads `node_count (4) | nodes`, without trusting the count for allocations.
+    pub fn nodes<N, E: From<InvalidEncoding>>(
+        &mut self,
+        mut read_node: impl FnMut(&mut Self) -> Result<N, E>,
+    ) -> Result<Vec<N>, E> {
+        let count = self.u32()?;
+        let mut nodes = vec![];
+        for _ in 0..count {
+            nodes.push(read_node(self)?);
+        }
+        Ok(nodes)
+    }
+
+    /// Fails if bytes are left.
+    pub fn finish(self) -> Result<(), InvalidEncoding> {
+        if !self.0.is_empty() {
+            return Err(InvalidEncoding);
+        }
+        Ok(())
+    }
+}
+
+/// Writes the proofs of a batch, after its header.
+///
+/// `nodes` returns the nodes of a proof, written after the fields written by `write_fields`.
+pub fn write_batch<P, N: Hash + Eq>(
+    writer: &mut Writer,
+    proofs: &[P],
+    dedup: bool,
+    nodes: impl Fn(&P) -> &[N],
+    mut write_fields: impl FnMut(&mut Writer, &P),
+    mut write_node: impl FnMut(&mut Writer, &N),
+) {
+    if !dedup {
+        writer.u32(proofs.len() as u32);
+        for proof in proofs {
+            write_fields(writer, proof);
+            writer.nodes(nodes(proof), &mut write_node);
+        }
+        return;
+    }
+
+    // Table of distinct nodes, in order of first appearance.
+    let mut table: Vec<&N> = vec![];
+    let mut positions: HashMap<&N, u32> = HashMap::new();
+    for node in proofs.iter().flat_map(&nodes) {
+        positions.entry(node).or_insert_with(|| {
+            table.push(node);
+            table.len() as u32 - 1
+        });
+    }
+
+    writer.nodes(&table, |writer, node| write_node(writer, node));
+    writer.u32(proofs.len() as u32);
+    for proof in proofs {
+        write_fields(writer, proof);
+        writer.nodes(nodes(proof), |writer, node| writer.u32(positions[node]));
+    }
+}
+
+/// Reads the proofs of a batch written by [`write_batch`], returning the fields and the
+/// nodes of each.
+///
+/// With `dedup`, the node table must be canonical: its nodes are distinct, all used, and
+/// first referenced in order.
+pub fn read_batch<'a, F, N, E>(
+    reader: &mut Reader<'a>,
+    dedup: bool,
+    mut read_fields: impl FnMut(&mut Reader<'a>) -> Result<F, E>,
+    mut read_node: impl FnMut(&mut Reader<'a>) -> Result<N, E>,
+) -> Result<Vec<(F, Vec<N>)>, E>
+where
+    N: Hash + Eq + Clone,
+    E: From<InvalidEncoding>,
+{
+    let mut proofs = vec![];
+    if !dedup {
+        let count = reader.u32()?;
+        for _ in 0..count {
+            let fields = read_fields(reader)?;
+            proofs.push((fields, reader.nodes(&mut read_node)?));
+        }
+        return Ok(proofs);
+    }
+
+    let table = reader.nodes(&mut read_node)?;
+    let mut distinct = HashSet::new();
+    if !table.iter().all(|node| distinct.insert(node)) {
+        return Err(InvalidEncoding.into());
+    }
+    // Nodes are referenced in order of first appearance, so the next node
+    // of the table is the only one that can be referenced for the first time.
+    let mut next_new = 0;
+
+    let count = reader.u32()?;
+    for _ in 0..count {
+        let fields = read_fields(reader)?;
+        let nodes = reader.nodes(|reader| {
+            let position = reader.u32()? as usize;
+            if position > next_new || position >= table.len() {
+                return Err(InvalidEncoding);
+            }
+            if position == next_new {
+                next_new += 1;
+            }
+            Ok(table[position].clone())
+        })?;
+        proofs.push((fields, nodes));
+    }
+
+    // Every node of the table is used.
+    if next_new != table.len() {
+        return Err(InvalidEncoding.into());
+    }
+    Ok(proofs)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{CHAIN_ETHEREUM, HASH_KECCAK, TRIE_TX};
+
+    fn header(flags: u8) -> Header {
+        Header {
+            chain: CHAIN_ETHEREUM,
+            trie: TRIE_TX,
+            hash: HASH_KECCAK,
+            flags,
+        }
+    }
+
+    fn write(proofs: &[(u64, Vec<u8>)], dedup: bool) -> Vec<u8> {
+        let mut writer = Writer::new(header(batch_flags(dedup)));
+        write_batch(
+            &mut writer,
+            proofs,
+            dedup,
+            |proof| proof.1.as_slice(),
+            |writer, proof| writer.u64(proof.0),
+            |writer, node| writer.u8(*node),
+        );
+        writer.into_bytes()
+    }
+
+    fn read(bytes: &[u8]) -> Result<Vec<(u64, Vec<u8>)>, InvalidEncoding> {
+        let (header, mut reader) = Reader::new(bytes)?;
+        let dedup = batch_dedup(header.flags)?;
+        let proofs = read_batch(&mut reader, dedup, Reader::u64, Reader::u8)?;
+        reader.finish()?;
+        Ok(proofs)
+    }
+
+    #[test]
+    fn test_batch_round_trip() {
+        let proofs = vec![(1, vec![7, 8, 9]), (2, vec![7, 8, 10]), (3, vec![])];
+        let plain = write(&proofs, false);
+        let deduped = write(&proofs, true);
+        assert_eq!(read(&plain).unwrap(), proofs);
+        assert_eq!(read(&deduped).unwrap(), proofs);
+        assert_eq!(read(&write(&[], true)).unwrap(), vec![]);
+    }
+
+    #[test]
+    fn test_non_canonical_table() {
+        let deduped = write(&[(1, vec![7, 8])], true);
+        let table = 9;
+        let references = deduped.len() - 8;
+
+        // Out of order, out of range and unused nodes
+        for (position, byte) in [
+            (references + 3, 1),
+            (references + 7, 2),
+            (references + 7, 0),
+        ] {
+            let mut invalid = deduped.clone();
+            invalid[position] = byte;
+            assert_eq!(read(&invalid), Err(InvalidEncoding));
+        }
+        // Duplicated nodes
+        let mut invalid = deduped.clone();
+        invalid[table + 5] = 7;
+        assert_eq!(read(&invalid), Err(InvalidEncoding));
+    }
+
+    #[test]
+    fn test_truncated_and_trailing() {
+        let bytes = write(&[(1, vec![7, 8]), (2, vec![8])], true);
+        for len in 0..bytes.len() {
+            assert_eq!(read(&bytes[..len]), Err(InvalidEncoding));
+        }
+        let mut trailing = bytes;
+        trailing.push(0);
+        assert_eq!(read(&trailing), Err(InvalidEncoding));
+        assert_eq!(batch_dedup(0), Err(InvalidEncoding));
+    }
+}
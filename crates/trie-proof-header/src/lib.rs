@@ -0,0 +1,96 @@
+//! Header shared by the compact binary encodings of `eth-trie-proofs` and `sn-trie-proofs`:
+//!
+//! ```text
+//! magic "TRPF" (4) | version (1) | chain (1) | trie (1) | hash (1) | flags (1)
+//! ```
+//!
+//! `chain` is one of the `CHAIN_*` constants, `trie` one of the `TRIE_*` kinds, `hash` the
+//! `HASH_*` function of the trie nodes and `flags` a combination of the `FLAG_*` bits. The
+//! proofs following the header are written with the reader, writer and node table of
+//! [`codec`], each chain encoding its own fields and nodes.
+
+pub mod codec;
+
+pub const MAGIC: [u8; 4] = *b"TRPF";
+pub const VERSION: u8 = 2;
+pub const HEADER_LEN: usize = 9;
+
+pub const CHAIN_ETHEREUM: u8 = 1;
+pub const CHAIN_STARKNET: u8 = 2;
+
+pub const TRIE_TX: u8 = 1;
+pub const TRIE_RECEIPT: u8 = 2;
+pub const TRIE_EVENT: u8 = 3;
+
+pub const HASH_KECCAK: u8 = 1;
+pub const HASH_PEDERSEN: u8 = 2;
+pub const HASH_POSEIDON: u8 = 3;
+
+pub const FLAG_BATCH: u8 = 1 << 0;
+pub const FLAG_DEDUP: u8 = 1 << 1;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Header {
+    pub chain: u8,
+    pub trie: u8,
+    pub hash: u8,
+    pub flags: u8,
+}
+
+impl Header {
+    pub fn to_bytes(&self) -> [u8; HEADER_LEN] {
+        let [m0, m1, m2, m3] = MAGIC;
+        [
+            m0, m1, m2, m3, VERSION, self.chain, self.trie, self.hash, self.flags,
+        ]
+    }
+
+    /// Splits the header off `bytes`.
+    ///
+    /// Returns `None` if `bytes` is too short, or if the magic or the version is unknown.
+    /// Checking the other fields is up to the caller.
+    pub fn split(bytes: &[u8]) -> Option<(Self, &[u8])> {
+        if bytes.len() < HEADER_LEN {
+            return None;
+        }
+        let (header, rest) = bytes.split_at(HEADER_LEN);
+        if header[..4] != MAGIC || header[4] != VERSION {
+            return None;
+        }
+        let header = Self {
+            chain: header[5],
+            trie: header[6],
+            hash: header[7],
+            flags: header[8],
+        };
+        Some((header, rest))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_header_round_trip() {
+        let header = Header {
+            chain: CHAIN_STARKNET,
+            trie: TRIE_RECEIPT,
+            hash: HASH_POSEIDON,
+            flags: FLAG_BATCH | FLAG_DEDUP,
+        };
+        let mut bytes = header.to_bytes().to_vec();
+        assert_eq!(bytes, [b'T', b'R', b'P', b'F', 2, 2, 2, 3, 3]);
+        bytes.push(0xaa);
+        assert_eq!(Header::split(&bytes), Some((header, &[0xaa][..])));
+
+        for len in 0..HEADER_LEN {
+            assert_eq!(Header::split(&bytes[..len]), None);
+        }
+        for position in 0..5 {
+            let mut invalid = bytes.clone();
+            invalid[position] ^= 0x80;
+            assert_eq!(Header::split(&invalid), None);
+        }
+    }
+}
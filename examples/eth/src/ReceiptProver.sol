@@ -0,0 +1,95 @@
+// SPDX-License-Identifier: MIT
+pragma solidity 0.8.25;
+
+// Generated by eth-trie-proofs, do not edit.
+
+import {BytesUtils} from "./lib/BytesUtils.sol";
+import {MerkleTrie} from "./lib/MerkleTrie.sol";
+import {RLPReader} from "./lib/RLPReader.sol";
+
+/**
+ * @title ReceiptProver
+ * @notice Proves the inclusion of a receipt in a receipts trie and extracts one of its logs.
+ */
+contract ReceiptProver {
+    struct Log {
+        address emitter;
+        bytes32[] topics;
+        bytes data;
+    }
+
+    /**
+     * @notice Proves the receipt at `_key` against `_root` and returns its log at `_logIndex`.
+     * @param _key RLP encoded index of the receipt.
+     * @param _proof RLP list of the proof nodes.
+     * @param _root Receipts root of the block.
+     * @param _logIndex Index of the log within the receipt.
+     * @return The proven log.
+     */
+    function getLog(bytes memory _key, bytes memory _proof, bytes32 _root, uint256 _logIndex)
+        public
+        pure
+        returns (Log memory)
+    {
+        (bool exists, bytes memory receipt) = MerkleTrie.get(_key, _proof, _root);
+        require(exists, "Receipt not included.");
+        return decodeLog(receipt, _logIndex);
+    }
+
+    /**
+     * @notice Proves a log and checks it against the expected emitter, topics and data.
+     * @return Whether the proven log matches.
+     */
+    function verifyLog(
+        bytes memory _key,
+        bytes memory _proof,
+        bytes32 _root,
+        uint256 _logIndex,
+        address _emitter,
+        bytes32[] memory _topics,
+        bytes memory _data
+    ) public pure returns (bool) {
+        Log memory log = getLog(_key, _proof, _root, _logIndex);
+        if (log.emitter != _emitter || log.topics.length != _topics.length) {
+            return false;
+        }
+        for (uint256 i = 0; i < _topics.length; i++) {
+            if (log.topics[i] != _topics[i]) {
+                return false;
+            }
+        }
+        return keccak256(log.data) == keccak256(_data);
+    }
+
+    /**
+     * @notice Decodes the log at `_logIndex` of an EIP-2718 encoded receipt.
+     * @param _receipt Receipt as stored in the receipts trie.
+     * @param _logIndex Index of the log within the receipt.
+     * @return log The decoded log.
+     */
+    function decodeLog(bytes memory _receipt, uint256 _logIndex) public pure returns (Log memory log) {
+        require(_receipt.length > 0, "Empty receipt.");
+
+        // Typed receipts are prefixed with their type byte, legacy receipts are a bare RLP list.
+        if (uint8(_receipt[0]) < 0x80) {
+            _receipt = BytesUtils.slice(_receipt, 1);
+        }
+
+        // [status or post state, cumulative gas used, logs bloom, logs, ...]
+        RLPReader.RLPItem[] memory fields = RLPReader.readList(_receipt);
+        RLPReader.RLPItem[] memory logs = RLPReader.readList(fields[3]);
+        require(_logIndex < logs.length, "Log not found.");
+
+        // [address, topics, data]
+        RLPReader.RLPItem[] memory logFields = RLPReader.readList(logs[_logIndex]);
+        log.emitter = RLPReader.readAddress(logFields[0]);
+
+        RLPReader.RLPItem[] memory topics = RLPReader.readList(logFields[1]);
+        log.topics = new bytes32[](topics.length);
+        for (uint256 i = 0; i < topics.length; i++) {
+            log.topics[i] = RLPReader.readBytes32(topics[i]);
+        }
+
+        log.data = RLPReader.readBytes(logFields[2]);
+    }
+}
@@ -30,7 +30,7 @@ contract ProverTest is Test {
         string memory res = string(vm.ffi(cmd));
 
         // Parse the proof response from JSON:
-        bytes[] memory parsedProof = vm.parseJsonBytesArray(res, ".proof");
+        bytes[] memory parsedProof = vm.parseJsonBytesArray(res, ".nodes");
         uint256 index = vm.parseJsonUint(res, ".index");
         bytes32 root = vm.parseJsonBytes32(res, ".root");
 
@@ -45,6 +45,29 @@ contract ProverTest is Test {
         assertEq(keccak256(txRLP), txHash);
     }
 
+    function test_e2eTxInclusionProofAbi() public {
+        bytes32 txHash = 0x011fdfcd44319747eb06301a4cde66d9a03b69fefe8fd736fedbf1b3caa21d93;
+
+        // Let the Rust CLI tool encode the key and proof for the verifier:
+        string[] memory cmd = new string[](9);
+        cmd[0] = "cargo";
+        cmd[1] = "run";
+        cmd[2] = "--bin";
+        cmd[3] = "cli";
+        cmd[4] = "tx";
+        cmd[5] = vm.toString(txHash);
+        cmd[6] = vm.envOr("RPC_URL", string("https://cloudflare-eth.com"));
+        cmd[7] = "--format";
+        cmd[8] = "abi";
+
+        (bytes memory key, bytes memory proofData, bytes32 root) = abi.decode(vm.ffi(cmd), (bytes, bytes, bytes32));
+
+        (bool exists, bytes memory txRLP) = prover.get(key, proofData, root);
+
+        assertEq(exists, true);
+        assertEq(keccak256(txRLP), txHash);
+    }
+
     // Helper to encode a list of bytes items into RLP with each item RLP-encoded as well
     function _RLPEncodeList(bytes[] memory _items) internal pure returns (bytes memory) {
         bytes[] memory encodedItems = new bytes[](_items.length);
@@ -0,0 +1,286 @@
+// SPDX-License-Identifier: MIT
+pragma solidity 0.8.25;
+
+// Generated by eth-trie-proofs, do not edit.
+
+import {Test} from "forge-std/Test.sol";
+import {ReceiptProver} from "../src/ReceiptProver.sol";
+
+contract ReceiptProverTest is Test {
+    ReceiptProver prover;
+
+    function setUp() public {
+        prover = new ReceiptProver();
+    }
+
+    function test_log_0_0() public view {
+        bytes32[] memory topics = new bytes32[](0);
+
+        bool valid = prover.verifyLog(
+            hex"80",
+            hex"f901e2b8b3f8b1a0dda3dbaf4899fc089ccbea9dc2d3c2b8ac6b97bfefcc5f39b4e3d2218e64cf62a01090ce332a0853141bcd1fcef62800cfde1701284cfebcff090de347d2d6cd6fa0a98b33b0ce07e3d2ff7bab93fee78e27ead1900ad64df2967687382a4a7d7ca0a0bcf0a8f1568d1a6cd7dcb1c5be5af3311e949cd503cdca4eb68c10f28a194d9980808080a0ca57e8cfb6a6e8acd03d5ea00b5aaf650ab99ffee3110491a321d13084d5b4ef8080808080808080b9012af9012730b90123f9012080825208b9010000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000002000000000000000000000000000000000000000000000000000000000200000000800000000000000000000000000000000000000000000000000000000000000000000000000000000d8d7940101010101010101010101010101010101010101c080",
+            0xb99fa81f895b208f24c9cb31b69aed313b279dd3914fcaece4f62ac25a6db0a7,
+            0,
+            0x0101010101010101010101010101010101010101,
+            topics,
+            hex""
+        );
+        assertTrue(valid);
+    }
+
+    function test_log_1_0() public view {
+        bytes32[] memory topics = new bytes32[](1);
+        topics[0] = 0x0000000000000000000000000000000000000000000000000000000000000000;
+
+        bool valid = prover.verifyLog(
+            hex"01",
+            hex"f90478b8b3f8b1a0dda3dbaf4899fc089ccbea9dc2d3c2b8ac6b97bfefcc5f39b4e3d2218e64cf62a01090ce332a0853141bcd1fcef62800cfde1701284cfebcff090de347d2d6cd6fa0a98b33b0ce07e3d2ff7bab93fee78e27ead1900ad64df2967687382a4a7d7ca0a0bcf0a8f1568d1a6cd7dcb1c5be5af3311e949cd503cdca4eb68c10f28a194d9980808080a0ca57e8cfb6a6e8acd03d5ea00b5aaf650ab99ffee3110491a321d13084d5b4ef8080808080808080b901f4f901f180a0de078d2b5a67d1f4178a57086079e534ff06570f4191f402dd6900ee4e77ebbda08f6426b6fe181e7fc7760d4bd6112ba03ea4d579b662c8776695ede3a5a7766fa01b0c6ed166bb7f54d1d6c9eee2c7874fa47c8847c2947ddcf70dd4c36dbc7b19a0107a8dad3e2d55eb0def3d00563f0cf9b5ef427ef9de5d8c1fa0d5008de0791fa0c135231b491d0fbfa9a3374c4b537166a89d6f00667b079d334d8a5738cf83e5a0ba918467479572dbd418185cbeadfa004d641bd3881deee61669e6a78713a23aa01e8d410350015298d1e7a2925ab8312f686f5c3fa3d4e142415f003de239df6ba0962f81bef7b466d7fd77fd3efeb01afc0cd802d5fa570530d5c25f36c18853c3a00abe25788d31809e7aac120a4922983b5b363c415d6d8bb540ddf795c70cf6d0a0b47c9ec39a48a778e399a9c23c0db2fd0e9901aefa57e604be242ad33b7cb928a003b807f25173d109a141ced25450b57b2db5f355b5242e05e5eb31455fc490d0a0c4f87e108fea750767e93ec27f328b718928d6387d48625ed37f3eee6768e6b4a07d3fce0eaa010bac0423864a0e30b17e60873f0facb7c3dd22c84fc5c25029eaa057f24c176aa9a241948dcc066068609a9088b4f78ea512fd479608d5b06c1dc1a0c3f5e1fad5557d2e1a4e3fc3685b3c975bd8fa994f5824bcbfc12dac4758b5e380b901c9f901c620b901c201f901be0182a410b9010000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000080080000000000000000000000000000000000000000000000000000000000000000020000000000000001000900000000000000000000000000000000000000000000000000000000000000000004000000000000010000000000000000000000000000000400000000000000000401000000000000000000000000000000000000000000000000000000000000000000000000000000000000000020000000000000000000000000000000000000000000000000000000000000000000f8b5f838940202020202020202020202020202020202020202e1a000000000000000000000000000000000000000000000000000000000000000001ff879940303030303030303030303030303030303030303f842a00000000000000000000000000000000000000000000000000000000000000000a003030303030303030303030303030303030303030303030303030303030303039f1f202122232425262728292a2b2c2d2e2f303132333435363738393a3b3c3d",
+            0xb99fa81f895b208f24c9cb31b69aed313b279dd3914fcaece4f62ac25a6db0a7,
+            0,
+            0x0202020202020202020202020202020202020202,
+            topics,
+            hex"1f"
+        );
+        assertTrue(valid);
+    }
+
+    function test_log_1_1() public view {
+        bytes32[] memory topics = new bytes32[](2);
+        topics[0] = 0x0000000000000000000000000000000000000000000000000000000000000000;
+        topics[1] = 0x0303030303030303030303030303030303030303030303030303030303030303;
+
+        bool valid = prover.verifyLog(
+            hex"01",
+            hex"f90478b8b3f8b1a0dda3dbaf4899fc089ccbea9dc2d3c2b8ac6b97bfefcc5f39b4e3d2218e64cf62a01090ce332a0853141bcd1fcef62800cfde1701284cfebcff090de347d2d6cd6fa0a98b33b0ce07e3d2ff7bab93fee78e27ead1900ad64df2967687382a4a7d7ca0a0bcf0a8f1568d1a6cd7dcb1c5be5af3311e949cd503cdca4eb68c10f28a194d9980808080a0ca57e8cfb6a6e8acd03d5ea00b5aaf650ab99ffee3110491a321d13084d5b4ef8080808080808080b901f4f901f180a0de078d2b5a67d1f4178a57086079e534ff06570f4191f402dd6900ee4e77ebbda08f6426b6fe181e7fc7760d4bd6112ba03ea4d579b662c8776695ede3a5a7766fa01b0c6ed166bb7f54d1d6c9eee2c7874fa47c8847c2947ddcf70dd4c36dbc7b19a0107a8dad3e2d55eb0def3d00563f0cf9b5ef427ef9de5d8c1fa0d5008de0791fa0c135231b491d0fbfa9a3374c4b537166a89d6f00667b079d334d8a5738cf83e5a0ba918467479572dbd418185cbeadfa004d641bd3881deee61669e6a78713a23aa01e8d410350015298d1e7a2925ab8312f686f5c3fa3d4e142415f003de239df6ba0962f81bef7b466d7fd77fd3efeb01afc0cd802d5fa570530d5c25f36c18853c3a00abe25788d31809e7aac120a4922983b5b363c415d6d8bb540ddf795c70cf6d0a0b47c9ec39a48a778e399a9c23c0db2fd0e9901aefa57e604be242ad33b7cb928a003b807f25173d109a141ced25450b57b2db5f355b5242e05e5eb31455fc490d0a0c4f87e108fea750767e93ec27f328b718928d6387d48625ed37f3eee6768e6b4a07d3fce0eaa010bac0423864a0e30b17e60873f0facb7c3dd22c84fc5c25029eaa057f24c176aa9a241948dcc066068609a9088b4f78ea512fd479608d5b06c1dc1a0c3f5e1fad5557d2e1a4e3fc3685b3c975bd8fa994f5824bcbfc12dac4758b5e380b901c9f901c620b901c201f901be0182a410b9010000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000080080000000000000000000000000000000000000000000000000000000000000000020000000000000001000900000000000000000000000000000000000000000000000000000000000000000004000000000000010000000000000000000000000000000400000000000000000401000000000000000000000000000000000000000000000000000000000000000000000000000000000000000020000000000000000000000000000000000000000000000000000000000000000000f8b5f838940202020202020202020202020202020202020202e1a000000000000000000000000000000000000000000000000000000000000000001ff879940303030303030303030303030303030303030303f842a00000000000000000000000000000000000000000000000000000000000000000a003030303030303030303030303030303030303030303030303030303030303039f1f202122232425262728292a2b2c2d2e2f303132333435363738393a3b3c3d",
+            0xb99fa81f895b208f24c9cb31b69aed313b279dd3914fcaece4f62ac25a6db0a7,
+            1,
+            0x0303030303030303030303030303030303030303,
+            topics,
+            hex"1f202122232425262728292a2b2c2d2e2f303132333435363738393a3b3c3d"
+        );
+        assertTrue(valid);
+    }
+
+    function test_log_2_0() public view {
+        bytes32[] memory topics = new bytes32[](2);
+        topics[0] = 0x0303030303030303030303030303030303030303030303030303030303030303;
+        topics[1] = 0x0000000000000000000000000000000000000000000000000000000000000000;
+
+        bool valid = prover.verifyLog(
+            hex"02",
+            hex"f905b3b8b3f8b1a0dda3dbaf4899fc089ccbea9dc2d3c2b8ac6b97bfefcc5f39b4e3d2218e64cf62a01090ce332a0853141bcd1fcef62800cfde1701284cfebcff090de347d2d6cd6fa0a98b33b0ce07e3d2ff7bab93fee78e27ead1900ad64df2967687382a4a7d7ca0a0bcf0a8f1568d1a6cd7dcb1c5be5af3311e949cd503cdca4eb68c10f28a194d9980808080a0ca57e8cfb6a6e8acd03d5ea00b5aaf650ab99ffee3110491a321d13084d5b4ef8080808080808080b901f4f901f180a0de078d2b5a67d1f4178a57086079e534ff06570f4191f402dd6900ee4e77ebbda08f6426b6fe181e7fc7760d4bd6112ba03ea4d579b662c8776695ede3a5a7766fa01b0c6ed166bb7f54d1d6c9eee2c7874fa47c8847c2947ddcf70dd4c36dbc7b19a0107a8dad3e2d55eb0def3d00563f0cf9b5ef427ef9de5d8c1fa0d5008de0791fa0c135231b491d0fbfa9a3374c4b537166a89d6f00667b079d334d8a5738cf83e5a0ba918467479572dbd418185cbeadfa004d641bd3881deee61669e6a78713a23aa01e8d410350015298d1e7a2925ab8312f686f5c3fa3d4e142415f003de239df6ba0962f81bef7b466d7fd77fd3efeb01afc0cd802d5fa570530d5c25f36c18853c3a00abe25788d31809e7aac120a4922983b5b363c415d6d8bb540ddf795c70cf6d0a0b47c9ec39a48a778e399a9c23c0db2fd0e9901aefa57e604be242ad33b7cb928a003b807f25173d109a141ced25450b57b2db5f355b5242e05e5eb31455fc490d0a0c4f87e108fea750767e93ec27f328b718928d6387d48625ed37f3eee6768e6b4a07d3fce0eaa010bac0423864a0e30b17e60873f0facb7c3dd22c84fc5c25029eaa057f24c176aa9a241948dcc066068609a9088b4f78ea512fd479608d5b06c1dc1a0c3f5e1fad5557d2e1a4e3fc3685b3c975bd8fa994f5824bcbfc12dac4758b5e380b90304f9030120b902fd02f902f90182f618b9010000000000000000000000000000000000000000000000001000000000000000000000000000000800100000000000000000000100000000000000000000000000000080080000000000000000000000000000000000010000000000000000000000000000020000000000000001000800000000000000000000000000000000000000000000000000000000000000000004000000000000000000000000000000000000008000000400000000000000000400000000000000000000000000000000000000000000000000000000000000000400020000000000000000000020000000000000000000000000000000000400000000401000000000000000000000f901eff879940303030303030303030303030303030303030303f842a00303030303030303030303030303030303030303030303030303030303030303a000000000000000000000000000000000000000000000000000000000000000009f3e3f404142434445464748494a4b4c4d4e4f505152535455565758595a5b5cf89b940404040404040404040404040404040404040404f863a00303030303030303030303030303030303030303030303030303030303030303a00000000000000000000000000000000000000000000000000000000000000000a00101010101010101010101010101010101010101010101010101010101010101a03e3f404142434445464748494a4b4c4d4e4f505152535455565758595a5b5c5df8d5940505050505050505050505050505050505050505f884a00303030303030303030303030303030303030303030303030303030303030303a00000000000000000000000000000000000000000000000000000000000000000a00101010101010101010101010101010101010101010101010101010101010101a00606060606060606060606060606060606060606060606060606060606060606b8383e3f404142434445464748494a4b4c4d4e4f505152535455565758595a5b5c5d5e5f606162636465666768696a6b6c6d6e6f707172737475",
+            0xb99fa81f895b208f24c9cb31b69aed313b279dd3914fcaece4f62ac25a6db0a7,
+            0,
+            0x0303030303030303030303030303030303030303,
+            topics,
+            hex"3e3f404142434445464748494a4b4c4d4e4f505152535455565758595a5b5c"
+        );
+        assertTrue(valid);
+    }
+
+    function test_log_2_1() public view {
+        bytes32[] memory topics = new bytes32[](3);
+        topics[0] = 0x0303030303030303030303030303030303030303030303030303030303030303;
+        topics[1] = 0x0000000000000000000000000000000000000000000000000000000000000000;
+        topics[2] = 0x0101010101010101010101010101010101010101010101010101010101010101;
+
+        bool valid = prover.verifyLog(
+            hex"02",
+            hex"f905b3b8b3f8b1a0dda3dbaf4899fc089ccbea9dc2d3c2b8ac6b97bfefcc5f39b4e3d2218e64cf62a01090ce332a0853141bcd1fcef62800cfde1701284cfebcff090de347d2d6cd6fa0a98b33b0ce07e3d2ff7bab93fee78e27ead1900ad64df2967687382a4a7d7ca0a0bcf0a8f1568d1a6cd7dcb1c5be5af3311e949cd503cdca4eb68c10f28a194d9980808080a0ca57e8cfb6a6e8acd03d5ea00b5aaf650ab99ffee3110491a321d13084d5b4ef8080808080808080b901f4f901f180a0de078d2b5a67d1f4178a57086079e534ff06570f4191f402dd6900ee4e77ebbda08f6426b6fe181e7fc7760d4bd6112ba03ea4d579b662c8776695ede3a5a7766fa01b0c6ed166bb7f54d1d6c9eee2c7874fa47c8847c2947ddcf70dd4c36dbc7b19a0107a8dad3e2d55eb0def3d00563f0cf9b5ef427ef9de5d8c1fa0d5008de0791fa0c135231b491d0fbfa9a3374c4b537166a89d6f00667b079d334d8a5738cf83e5a0ba918467479572dbd418185cbeadfa004d641bd3881deee61669e6a78713a23aa01e8d410350015298d1e7a2925ab8312f686f5c3fa3d4e142415f003de239df6ba0962f81bef7b466d7fd77fd3efeb01afc0cd802d5fa570530d5c25f36c18853c3a00abe25788d31809e7aac120a4922983b5b363c415d6d8bb540ddf795c70cf6d0a0b47c9ec39a48a778e399a9c23c0db2fd0e9901aefa57e604be242ad33b7cb928a003b807f25173d109a141ced25450b57b2db5f355b5242e05e5eb31455fc490d0a0c4f87e108fea750767e93ec27f328b718928d6387d48625ed37f3eee6768e6b4a07d3fce0eaa010bac0423864a0e30b17e60873f0facb7c3dd22c84fc5c25029eaa057f24c176aa9a241948dcc066068609a9088b4f78ea512fd479608d5b06c1dc1a0c3f5e1fad5557d2e1a4e3fc3685b3c975bd8fa994f5824bcbfc12dac4758b5e380b90304f9030120b902fd02f902f90182f618b9010000000000000000000000000000000000000000000000001000000000000000000000000000000800100000000000000000000100000000000000000000000000000080080000000000000000000000000000000000010000000000000000000000000000020000000000000001000800000000000000000000000000000000000000000000000000000000000000000004000000000000000000000000000000000000008000000400000000000000000400000000000000000000000000000000000000000000000000000000000000000400020000000000000000000020000000000000000000000000000000000400000000401000000000000000000000f901eff879940303030303030303030303030303030303030303f842a00303030303030303030303030303030303030303030303030303030303030303a000000000000000000000000000000000000000000000000000000000000000009f3e3f404142434445464748494a4b4c4d4e4f505152535455565758595a5b5cf89b940404040404040404040404040404040404040404f863a00303030303030303030303030303030303030303030303030303030303030303a00000000000000000000000000000000000000000000000000000000000000000a00101010101010101010101010101010101010101010101010101010101010101a03e3f404142434445464748494a4b4c4d4e4f505152535455565758595a5b5c5df8d5940505050505050505050505050505050505050505f884a00303030303030303030303030303030303030303030303030303030303030303a00000000000000000000000000000000000000000000000000000000000000000a00101010101010101010101010101010101010101010101010101010101010101a00606060606060606060606060606060606060606060606060606060606060606b8383e3f404142434445464748494a4b4c4d4e4f505152535455565758595a5b5c5d5e5f606162636465666768696a6b6c6d6e6f707172737475",
+            0xb99fa81f895b208f24c9cb31b69aed313b279dd3914fcaece4f62ac25a6db0a7,
+            1,
+            0x0404040404040404040404040404040404040404,
+            topics,
+            hex"3e3f404142434445464748494a4b4c4d4e4f505152535455565758595a5b5c5d"
+        );
+        assertTrue(valid);
+    }
+
+    function test_log_2_2() public view {
+        bytes32[] memory topics = new bytes32[](4);
+        topics[0] = 0x0303030303030303030303030303030303030303030303030303030303030303;
+        topics[1] = 0x0000000000000000000000000000000000000000000000000000000000000000;
+        topics[2] = 0x0101010101010101010101010101010101010101010101010101010101010101;
+        topics[3] = 0x0606060606060606060606060606060606060606060606060606060606060606;
+
+        bool valid = prover.verifyLog(
+            hex"02",
+            hex"f905b3b8b3f8b1a0dda3dbaf4899fc089ccbea9dc2d3c2b8ac6b97bfefcc5f39b4e3d2218e64cf62a01090ce332a0853141bcd1fcef62800cfde1701284cfebcff090de347d2d6cd6fa0a98b33b0ce07e3d2ff7bab93fee78e27ead1900ad64df2967687382a4a7d7ca0a0bcf0a8f1568d1a6cd7dcb1c5be5af3311e949cd503cdca4eb68c10f28a194d9980808080a0ca57e8cfb6a6e8acd03d5ea00b5aaf650ab99ffee3110491a321d13084d5b4ef8080808080808080b901f4f901f180a0de078d2b5a67d1f4178a57086079e534ff06570f4191f402dd6900ee4e77ebbda08f6426b6fe181e7fc7760d4bd6112ba03ea4d579b662c8776695ede3a5a7766fa01b0c6ed166bb7f54d1d6c9eee2c7874fa47c8847c2947ddcf70dd4c36dbc7b19a0107a8dad3e2d55eb0def3d00563f0cf9b5ef427ef9de5d8c1fa0d5008de0791fa0c135231b491d0fbfa9a3374c4b537166a89d6f00667b079d334d8a5738cf83e5a0ba918467479572dbd418185cbeadfa004d641bd3881deee61669e6a78713a23aa01e8d410350015298d1e7a2925ab8312f686f5c3fa3d4e142415f003de239df6ba0962f81bef7b466d7fd77fd3efeb01afc0cd802d5fa570530d5c25f36c18853c3a00abe25788d31809e7aac120a4922983b5b363c415d6d8bb540ddf795c70cf6d0a0b47c9ec39a48a778e399a9c23c0db2fd0e9901aefa57e604be242ad33b7cb928a003b807f25173d109a141ced25450b57b2db5f355b5242e05e5eb31455fc490d0a0c4f87e108fea750767e93ec27f328b718928d6387d48625ed37f3eee6768e6b4a07d3fce0eaa010bac0423864a0e30b17e60873f0facb7c3dd22c84fc5c25029eaa057f24c176aa9a241948dcc066068609a9088b4f78ea512fd479608d5b06c1dc1a0c3f5e1fad5557d2e1a4e3fc3685b3c975bd8fa994f5824bcbfc12dac4758b5e380b90304f9030120b902fd02f902f90182f618b9010000000000000000000000000000000000000000000000001000000000000000000000000000000800100000000000000000000100000000000000000000000000000080080000000000000000000000000000000000010000000000000000000000000000020000000000000001000800000000000000000000000000000000000000000000000000000000000000000004000000000000000000000000000000000000008000000400000000000000000400000000000000000000000000000000000000000000000000000000000000000400020000000000000000000020000000000000000000000000000000000400000000401000000000000000000000f901eff879940303030303030303030303030303030303030303f842a00303030303030303030303030303030303030303030303030303030303030303a000000000000000000000000000000000000000000000000000000000000000009f3e3f404142434445464748494a4b4c4d4e4f505152535455565758595a5b5cf89b940404040404040404040404040404040404040404f863a00303030303030303030303030303030303030303030303030303030303030303a00000000000000000000000000000000000000000000000000000000000000000a00101010101010101010101010101010101010101010101010101010101010101a03e3f404142434445464748494a4b4c4d4e4f505152535455565758595a5b5c5df8d5940505050505050505050505050505050505050505f884a00303030303030303030303030303030303030303030303030303030303030303a00000000000000000000000000000000000000000000000000000000000000000a00101010101010101010101010101010101010101010101010101010101010101a00606060606060606060606060606060606060606060606060606060606060606b8383e3f404142434445464748494a4b4c4d4e4f505152535455565758595a5b5c5d5e5f606162636465666768696a6b6c6d6e6f707172737475",
+            0xb99fa81f895b208f24c9cb31b69aed313b279dd3914fcaece4f62ac25a6db0a7,
+            2,
+            0x0505050505050505050505050505050505050505,
+            topics,
+            hex"3e3f404142434445464748494a4b4c4d4e4f505152535455565758595a5b5c5d5e5f606162636465666768696a6b6c6d6e6f707172737475"
+        );
+        assertTrue(valid);
+    }
+
+    function test_log_3_0() public view {
+        bytes32[] memory topics = new bytes32[](3);
+        topics[0] = 0x0202020202020202020202020202020202020202020202020202020202020202;
+        topics[1] = 0x0101010101010101010101010101010101010101010101010101010101010101;
+        topics[2] = 0x0000000000000000000000000000000000000000000000000000000000000000;
+
+        bool valid = prover.verifyLog(
+            hex"03",
+            hex"f90461b8b3f8b1a0dda3dbaf4899fc089ccbea9dc2d3c2b8ac6b97bfefcc5f39b4e3d2218e64cf62a01090ce332a0853141bcd1fcef62800cfde1701284cfebcff090de347d2d6cd6fa0a98b33b0ce07e3d2ff7bab93fee78e27ead1900ad64df2967687382a4a7d7ca0a0bcf0a8f1568d1a6cd7dcb1c5be5af3311e949cd503cdca4eb68c10f28a194d9980808080a0ca57e8cfb6a6e8acd03d5ea00b5aaf650ab99ffee3110491a321d13084d5b4ef8080808080808080b901f4f901f180a0de078d2b5a67d1f4178a57086079e534ff06570f4191f402dd6900ee4e77ebbda08f6426b6fe181e7fc7760d4bd6112ba03ea4d579b662c8776695ede3a5a7766fa01b0c6ed166bb7f54d1d6c9eee2c7874fa47c8847c2947ddcf70dd4c36dbc7b19a0107a8dad3e2d55eb0def3d00563f0cf9b5ef427ef9de5d8c1fa0d5008de0791fa0c135231b491d0fbfa9a3374c4b537166a89d6f00667b079d334d8a5738cf83e5a0ba918467479572dbd418185cbeadfa004d641bd3881deee61669e6a78713a23aa01e8d410350015298d1e7a2925ab8312f686f5c3fa3d4e142415f003de239df6ba0962f81bef7b466d7fd77fd3efeb01afc0cd802d5fa570530d5c25f36c18853c3a00abe25788d31809e7aac120a4922983b5b363c415d6d8bb540ddf795c70cf6d0a0b47c9ec39a48a778e399a9c23c0db2fd0e9901aefa57e604be242ad33b7cb928a003b807f25173d109a141ced25450b57b2db5f355b5242e05e5eb31455fc490d0a0c4f87e108fea750767e93ec27f328b718928d6387d48625ed37f3eee6768e6b4a07d3fce0eaa010bac0423864a0e30b17e60873f0facb7c3dd22c84fc5c25029eaa057f24c176aa9a241948dcc066068609a9088b4f78ea512fd479608d5b06c1dc1a0c3f5e1fad5557d2e1a4e3fc3685b3c975bd8fa994f5824bcbfc12dac4758b5e380b901b2f901af20b901ab03f901a70183014820b9010000000000000000000000000080000000000000000000000000000000000000000000000000000800100000000000000000000000000004000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000020000000000000000000800000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000100000000000008000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000020000000000000000000020000000000000000000000000000000000400000000001000000000000000000000f89df89b940404040404040404040404040404040404040404f863a00202020202020202020202020202020202020202020202020202020202020202a00101010101010101010101010101010101010101010101010101010101010101a00000000000000000000000000000000000000000000000000000000000000000a05d5e5f606162636465666768696a6b6c6d6e6f707172737475767778797a7b7c",
+            0xb99fa81f895b208f24c9cb31b69aed313b279dd3914fcaece4f62ac25a6db0a7,
+            0,
+            0x0404040404040404040404040404040404040404,
+            topics,
+            hex"5d5e5f606162636465666768696a6b6c6d6e6f707172737475767778797a7b7c"
+        );
+        assertTrue(valid);
+    }
+
+    function test_log_4_0() public view {
+        bytes32[] memory topics = new bytes32[](4);
+        topics[0] = 0x0505050505050505050505050505050505050505050505050505050505050505;
+        topics[1] = 0x0606060606060606060606060606060606060606060606060606060606060606;
+        topics[2] = 0x0707070707070707070707070707070707070707070707070707070707070707;
+        topics[3] = 0x0000000000000000000000000000000000000000000000000000000000000000;
+
+        bool valid = prover.verifyLog(
+            hex"04",
+            hex"f90580b8b3f8b1a0dda3dbaf4899fc089ccbea9dc2d3c2b8ac6b97bfefcc5f39b4e3d2218e64cf62a01090ce332a0853141bcd1fcef62800cfde1701284cfebcff090de347d2d6cd6fa0a98b33b0ce07e3d2ff7bab93fee78e27ead1900ad64df2967687382a4a7d7ca0a0bcf0a8f1568d1a6cd7dcb1c5be5af3311e949cd503cdca4eb68c10f28a194d9980808080a0ca57e8cfb6a6e8acd03d5ea00b5aaf650ab99ffee3110491a321d13084d5b4ef8080808080808080b901f4f901f180a0de078d2b5a67d1f4178a57086079e534ff06570f4191f402dd6900ee4e77ebbda08f6426b6fe181e7fc7760d4bd6112ba03ea4d579b662c8776695ede3a5a7766fa01b0c6ed166bb7f54d1d6c9eee2c7874fa47c8847c2947ddcf70dd4c36dbc7b19a0107a8dad3e2d55eb0def3d00563f0cf9b5ef427ef9de5d8c1fa0d5008de0791fa0c135231b491d0fbfa9a3374c4b537166a89d6f00667b079d334d8a5738cf83e5a0ba918467479572dbd418185cbeadfa004d641bd3881deee61669e6a78713a23aa01e8d410350015298d1e7a2925ab8312f686f5c3fa3d4e142415f003de239df6ba0962f81bef7b466d7fd77fd3efeb01afc0cd802d5fa570530d5c25f36c18853c3a00abe25788d31809e7aac120a4922983b5b363c415d6d8bb540ddf795c70cf6d0a0b47c9ec39a48a778e399a9c23c0db2fd0e9901aefa57e604be242ad33b7cb928a003b807f25173d109a141ced25450b57b2db5f355b5242e05e5eb31455fc490d0a0c4f87e108fea750767e93ec27f328b718928d6387d48625ed37f3eee6768e6b4a07d3fce0eaa010bac0423864a0e30b17e60873f0facb7c3dd22c84fc5c25029eaa057f24c176aa9a241948dcc066068609a9088b4f78ea512fd479608d5b06c1dc1a0c3f5e1fad5557d2e1a4e3fc3685b3c975bd8fa994f5824bcbfc12dac4758b5e380b902d1f902ce20b902ca7ef902c60183019a28b9010000000000000000000000000000000000000000000000001000000000000000000000000000000000000000000000000000000100000000000000000000000000000000000000000000000000020000200000000000110001000000000000000000000000020000000000000000000800000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000040000000000000000000000020000000000000000000000000000000000000000000000000000000000000000000000000000400020400000000000000000020000000000000000000000000000000000000000010401000000000000000000000f901b9f8d5940505050505050505050505050505050505050505f884a00505050505050505050505050505050505050505050505050505050505050505a00606060606060606060606060606060606060606060606060606060606060606a00707070707070707070707070707070707070707070707070707070707070707a00000000000000000000000000000000000000000000000000000000000000000b8387c7d7e7f808182838485868788898a8b8c8d8e8f909192939495969798999a9b9c9d9e9fa0a1a2a3a4a5a6a7a8a9aaabacadaeafb0b1b2b3f8e0940606060606060606060606060606060606060606c0b8c87c7d7e7f808182838485868788898a8b8c8d8e8f909192939495969798999a9b9c9d9e9fa0a1a2a3a4a5a6a7a8a9aaabacadaeafb0b1b2b3b4b5b6b7b8b9babbbcbdbebfc0c1c2c3c4c5c6c7c8c9cacbcccdcecfd0d1d2d3d4d5d6d7d8d9dadbdcdddedfe0e1e2e3e4e5e6e7e8e9eaebecedeeeff0f1f2f3f4f5f6f7f8f9fafbfcfdfeff000102030405060708090a0b0c0d0e0f101112131415161718191a1b1c1d1e1f202122232425262728292a2b2c2d2e2f303132333435363738393a3b3c3d3e3f404142430401",
+            0xb99fa81f895b208f24c9cb31b69aed313b279dd3914fcaece4f62ac25a6db0a7,
+            0,
+            0x0505050505050505050505050505050505050505,
+            topics,
+            hex"7c7d7e7f808182838485868788898a8b8c8d8e8f909192939495969798999a9b9c9d9e9fa0a1a2a3a4a5a6a7a8a9aaabacadaeafb0b1b2b3"
+        );
+        assertTrue(valid);
+    }
+
+    function test_log_4_1() public view {
+        bytes32[] memory topics = new bytes32[](0);
+
+        bool valid = prover.verifyLog(
+            hex"04",
+            hex"f90580b8b3f8b1a0dda3dbaf4899fc089ccbea9dc2d3c2b8ac6b97bfefcc5f39b4e3d2218e64cf62a01090ce332a0853141bcd1fcef62800cfde1701284cfebcff090de347d2d6cd6fa0a98b33b0ce07e3d2ff7bab93fee78e27ead1900ad64df2967687382a4a7d7ca0a0bcf0a8f1568d1a6cd7dcb1c5be5af3311e949cd503cdca4eb68c10f28a194d9980808080a0ca57e8cfb6a6e8acd03d5ea00b5aaf650ab99ffee3110491a321d13084d5b4ef8080808080808080b901f4f901f180a0de078d2b5a67d1f4178a57086079e534ff06570f4191f402dd6900ee4e77ebbda08f6426b6fe181e7fc7760d4bd6112ba03ea4d579b662c8776695ede3a5a7766fa01b0c6ed166bb7f54d1d6c9eee2c7874fa47c8847c2947ddcf70dd4c36dbc7b19a0107a8dad3e2d55eb0def3d00563f0cf9b5ef427ef9de5d8c1fa0d5008de0791fa0c135231b491d0fbfa9a3374c4b537166a89d6f00667b079d334d8a5738cf83e5a0ba918467479572dbd418185cbeadfa004d641bd3881deee61669e6a78713a23aa01e8d410350015298d1e7a2925ab8312f686f5c3fa3d4e142415f003de239df6ba0962f81bef7b466d7fd77fd3efeb01afc0cd802d5fa570530d5c25f36c18853c3a00abe25788d31809e7aac120a4922983b5b363c415d6d8bb540ddf795c70cf6d0a0b47c9ec39a48a778e399a9c23c0db2fd0e9901aefa57e604be242ad33b7cb928a003b807f25173d109a141ced25450b57b2db5f355b5242e05e5eb31455fc490d0a0c4f87e108fea750767e93ec27f328b718928d6387d48625ed37f3eee6768e6b4a07d3fce0eaa010bac0423864a0e30b17e60873f0facb7c3dd22c84fc5c25029eaa057f24c176aa9a241948dcc066068609a9088b4f78ea512fd479608d5b06c1dc1a0c3f5e1fad5557d2e1a4e3fc3685b3c975bd8fa994f5824bcbfc12dac4758b5e380b902d1f902ce20b902ca7ef902c60183019a28b9010000000000000000000000000000000000000000000000001000000000000000000000000000000000000000000000000000000100000000000000000000000000000000000000000000000000020000200000000000110001000000000000000000000000020000000000000000000800000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000040000000000000000000000020000000000000000000000000000000000000000000000000000000000000000000000000000400020400000000000000000020000000000000000000000000000000000000000010401000000000000000000000f901b9f8d5940505050505050505050505050505050505050505f884a00505050505050505050505050505050505050505050505050505050505050505a00606060606060606060606060606060606060606060606060606060606060606a00707070707070707070707070707070707070707070707070707070707070707a00000000000000000000000000000000000000000000000000000000000000000b8387c7d7e7f808182838485868788898a8b8c8d8e8f909192939495969798999a9b9c9d9e9fa0a1a2a3a4a5a6a7a8a9aaabacadaeafb0b1b2b3f8e0940606060606060606060606060606060606060606c0b8c87c7d7e7f808182838485868788898a8b8c8d8e8f909192939495969798999a9b9c9d9e9fa0a1a2a3a4a5a6a7a8a9aaabacadaeafb0b1b2b3b4b5b6b7b8b9babbbcbdbebfc0c1c2c3c4c5c6c7c8c9cacbcccdcecfd0d1d2d3d4d5d6d7d8d9dadbdcdddedfe0e1e2e3e4e5e6e7e8e9eaebecedeeeff0f1f2f3f4f5f6f7f8f9fafbfcfdfeff000102030405060708090a0b0c0d0e0f101112131415161718191a1b1c1d1e1f202122232425262728292a2b2c2d2e2f303132333435363738393a3b3c3d3e3f404142430401",
+            0xb99fa81f895b208f24c9cb31b69aed313b279dd3914fcaece4f62ac25a6db0a7,
+            1,
+            0x0606060606060606060606060606060606060606,
+            topics,
+            hex"7c7d7e7f808182838485868788898a8b8c8d8e8f909192939495969798999a9b9c9d9e9fa0a1a2a3a4a5a6a7a8a9aaabacadaeafb0b1b2b3b4b5b6b7b8b9babbbcbdbebfc0c1c2c3c4c5c6c7c8c9cacbcccdcecfd0d1d2d3d4d5d6d7d8d9dadbdcdddedfe0e1e2e3e4e5e6e7e8e9eaebecedeeeff0f1f2f3f4f5f6f7f8f9fafbfcfdfeff000102030405060708090a0b0c0d0e0f101112131415161718191a1b1c1d1e1f202122232425262728292a2b2c2d2e2f303132333435363738393a3b3c3d3e3f40414243"
+        );
+        assertTrue(valid);
+    }
+
+    function test_log_5_0() public view {
+        bytes32[] memory topics = new bytes32[](0);
+
+        bool valid = prover.verifyLog(
+            hex"05",
+            hex"f9055db8b3f8b1a0dda3dbaf4899fc089ccbea9dc2d3c2b8ac6b97bfefcc5f39b4e3d2218e64cf62a01090ce332a0853141bcd1fcef62800cfde1701284cfebcff090de347d2d6cd6fa0a98b33b0ce07e3d2ff7bab93fee78e27ead1900ad64df2967687382a4a7d7ca0a0bcf0a8f1568d1a6cd7dcb1c5be5af3311e949cd503cdca4eb68c10f28a194d9980808080a0ca57e8cfb6a6e8acd03d5ea00b5aaf650ab99ffee3110491a321d13084d5b4ef8080808080808080b901f4f901f180a0de078d2b5a67d1f4178a57086079e534ff06570f4191f402dd6900ee4e77ebbda08f6426b6fe181e7fc7760d4bd6112ba03ea4d579b662c8776695ede3a5a7766fa01b0c6ed166bb7f54d1d6c9eee2c7874fa47c8847c2947ddcf70dd4c36dbc7b19a0107a8dad3e2d55eb0def3d00563f0cf9b5ef427ef9de5d8c1fa0d5008de0791fa0c135231b491d0fbfa9a3374c4b537166a89d6f00667b079d334d8a5738cf83e5a0ba918467479572dbd418185cbeadfa004d641bd3881deee61669e6a78713a23aa01e8d410350015298d1e7a2925ab8312f686f5c3fa3d4e142415f003de239df6ba0962f81bef7b466d7fd77fd3efeb01afc0cd802d5fa570530d5c25f36c18853c3a00abe25788d31809e7aac120a4922983b5b363c415d6d8bb540ddf795c70cf6d0a0b47c9ec39a48a778e399a9c23c0db2fd0e9901aefa57e604be242ad33b7cb928a003b807f25173d109a141ced25450b57b2db5f355b5242e05e5eb31455fc490d0a0c4f87e108fea750767e93ec27f328b718928d6387d48625ed37f3eee6768e6b4a07d3fce0eaa010bac0423864a0e30b17e60873f0facb7c3dd22c84fc5c25029eaa057f24c176aa9a241948dcc066068609a9088b4f78ea512fd479608d5b06c1dc1a0c3f5e1fad5557d2e1a4e3fc3685b3c975bd8fa994f5824bcbfc12dac4758b5e380b902aef902ab20b902a7f902a4a005050505050505050505050505050505050505050505050505050505050505058301ec30b9010000000000000000000000000000000000000000000000000000000000000080000000000000000000000000000000000000000000000800000008000000000000000000000000000000000000000000000000000000100001000010000000000000000000000000000000000000040000000000000000000000000000010000000000000000000000000000000000000000000000200000000000000000000040000000000000000000000020000000000000000000000000000000000000100000000000000000000000000000000000000000000400000000000000000000000000000000000000800000000000000000000010000000000000000000000000f90179f8e0940606060606060606060606060606060606060606c0b8c89b9c9d9e9fa0a1a2a3a4a5a6a7a8a9aaabacadaeafb0b1b2b3b4b5b6b7b8b9babbbcbdbebfc0c1c2c3c4c5c6c7c8c9cacbcccdcecfd0d1d2d3d4d5d6d7d8d9dadbdcdddedfe0e1e2e3e4e5e6e7e8e9eaebecedeeeff0f1f2f3f4f5f6f7f8f9fafbfcfdfeff000102030405060708090a0b0c0d0e0f101112131415161718191a1b1c1d1e1f202122232425262728292a2b2c2d2e2f303132333435363738393a3b3c3d3e3f404142434445464748494a4b4c4d4e4f505152535455565758595a5b5c5d5e5f606162f838940707070707070707070707070707070707070707e1a0040404040404040404040404040404040404040404040404040404040404040480f85b940808080808080808080808080808080808080808f842a00404040404040404040404040404040404040404040404040404040404040404a00707070707070707070707070707070707070707070707070707070707070707819b",
+            0xb99fa81f895b208f24c9cb31b69aed313b279dd3914fcaece4f62ac25a6db0a7,
+            0,
+            0x0606060606060606060606060606060606060606,
+            topics,
+            hex"9b9c9d9e9fa0a1a2a3a4a5a6a7a8a9aaabacadaeafb0b1b2b3b4b5b6b7b8b9babbbcbdbebfc0c1c2c3c4c5c6c7c8c9cacbcccdcecfd0d1d2d3d4d5d6d7d8d9dadbdcdddedfe0e1e2e3e4e5e6e7e8e9eaebecedeeeff0f1f2f3f4f5f6f7f8f9fafbfcfdfeff000102030405060708090a0b0c0d0e0f101112131415161718191a1b1c1d1e1f202122232425262728292a2b2c2d2e2f303132333435363738393a3b3c3d3e3f404142434445464748494a4b4c4d4e4f505152535455565758595a5b5c5d5e5f606162"
+        );
+        assertTrue(valid);
+    }
+
+    function test_log_5_1() public view {
+        bytes32[] memory topics = new bytes32[](1);
+        topics[0] = 0x0404040404040404040404040404040404040404040404040404040404040404;
+
+        bool valid = prover.verifyLog(
+            hex"05",
+            hex"f9055db8b3f8b1a0dda3dbaf4899fc089ccbea9dc2d3c2b8ac6b97bfefcc5f39b4e3d2218e64cf62a01090ce332a0853141bcd1fcef62800cfde1701284cfebcff090de347d2d6cd6fa0a98b33b0ce07e3d2ff7bab93fee78e27ead1900ad64df2967687382a4a7d7ca0a0bcf0a8f1568d1a6cd7dcb1c5be5af3311e949cd503cdca4eb68c10f28a194d9980808080a0ca57e8cfb6a6e8acd03d5ea00b5aaf650ab99ffee3110491a321d13084d5b4ef8080808080808080b901f4f901f180a0de078d2b5a67d1f4178a57086079e534ff06570f4191f402dd6900ee4e77ebbda08f6426b6fe181e7fc7760d4bd6112ba03ea4d579b662c8776695ede3a5a7766fa01b0c6ed166bb7f54d1d6c9eee2c7874fa47c8847c2947ddcf70dd4c36dbc7b19a0107a8dad3e2d55eb0def3d00563f0cf9b5ef427ef9de5d8c1fa0d5008de0791fa0c135231b491d0fbfa9a3374c4b537166a89d6f00667b079d334d8a5738cf83e5a0ba918467479572dbd418185cbeadfa004d641bd3881deee61669e6a78713a23aa01e8d410350015298d1e7a2925ab8312f686f5c3fa3d4e142415f003de239df6ba0962f81bef7b466d7fd77fd3efeb01afc0cd802d5fa570530d5c25f36c18853c3a00abe25788d31809e7aac120a4922983b5b363c415d6d8bb540ddf795c70cf6d0a0b47c9ec39a48a778e399a9c23c0db2fd0e9901aefa57e604be242ad33b7cb928a003b807f25173d109a141ced25450b57b2db5f355b5242e05e5eb31455fc490d0a0c4f87e108fea750767e93ec27f328b718928d6387d48625ed37f3eee6768e6b4a07d3fce0eaa010bac0423864a0e30b17e60873f0facb7c3dd22c84fc5c25029eaa057f24c176aa9a241948dcc066068609a9088b4f78ea512fd479608d5b06c1dc1a0c3f5e1fad5557d2e1a4e3fc3685b3c975bd8fa994f5824bcbfc12dac4758b5e380b902aef902ab20b902a7f902a4a005050505050505050505050505050505050505050505050505050505050505058301ec30b9010000000000000000000000000000000000000000000000000000000000000080000000000000000000000000000000000000000000000800000008000000000000000000000000000000000000000000000000000000100001000010000000000000000000000000000000000000040000000000000000000000000000010000000000000000000000000000000000000000000000200000000000000000000040000000000000000000000020000000000000000000000000000000000000100000000000000000000000000000000000000000000400000000000000000000000000000000000000800000000000000000000010000000000000000000000000f90179f8e0940606060606060606060606060606060606060606c0b8c89b9c9d9e9fa0a1a2a3a4a5a6a7a8a9aaabacadaeafb0b1b2b3b4b5b6b7b8b9babbbcbdbebfc0c1c2c3c4c5c6c7c8c9cacbcccdcecfd0d1d2d3d4d5d6d7d8d9dadbdcdddedfe0e1e2e3e4e5e6e7e8e9eaebecedeeeff0f1f2f3f4f5f6f7f8f9fafbfcfdfeff000102030405060708090a0b0c0d0e0f101112131415161718191a1b1c1d1e1f202122232425262728292a2b2c2d2e2f303132333435363738393a3b3c3d3e3f404142434445464748494a4b4c4d4e4f505152535455565758595a5b5c5d5e5f606162f838940707070707070707070707070707070707070707e1a0040404040404040404040404040404040404040404040404040404040404040480f85b940808080808080808080808080808080808080808f842a00404040404040404040404040404040404040404040404040404040404040404a00707070707070707070707070707070707070707070707070707070707070707819b",
+            0xb99fa81f895b208f24c9cb31b69aed313b279dd3914fcaece4f62ac25a6db0a7,
+            1,
+            0x0707070707070707070707070707070707070707,
+            topics,
+            hex""
+        );
+        assertTrue(valid);
+    }
+
+    function test_log_5_2() public view {
+        bytes32[] memory topics = new bytes32[](2);
+        topics[0] = 0x0404040404040404040404040404040404040404040404040404040404040404;
+        topics[1] = 0x0707070707070707070707070707070707070707070707070707070707070707;
+
+        bool valid = prover.verifyLog(
+            hex"05",
+            hex"f9055db8b3f8b1a0dda3dbaf4899fc089ccbea9dc2d3c2b8ac6b97bfefcc5f39b4e3d2218e64cf62a01090ce332a0853141bcd1fcef62800cfde1701284cfebcff090de347d2d6cd6fa0a98b33b0ce07e3d2ff7bab93fee78e27ead1900ad64df2967687382a4a7d7ca0a0bcf0a8f1568d1a6cd7dcb1c5be5af3311e949cd503cdca4eb68c10f28a194d9980808080a0ca57e8cfb6a6e8acd03d5ea00b5aaf650ab99ffee3110491a321d13084d5b4ef8080808080808080b901f4f901f180a0de078d2b5a67d1f4178a57086079e534ff06570f4191f402dd6900ee4e77ebbda08f6426b6fe181e7fc7760d4bd6112ba03ea4d579b662c8776695ede3a5a7766fa01b0c6ed166bb7f54d1d6c9eee2c7874fa47c8847c2947ddcf70dd4c36dbc7b19a0107a8dad3e2d55eb0def3d00563f0cf9b5ef427ef9de5d8c1fa0d5008de0791fa0c135231b491d0fbfa9a3374c4b537166a89d6f00667b079d334d8a5738cf83e5a0ba918467479572dbd418185cbeadfa004d641bd3881deee61669e6a78713a23aa01e8d410350015298d1e7a2925ab8312f686f5c3fa3d4e142415f003de239df6ba0962f81bef7b466d7fd77fd3efeb01afc0cd802d5fa570530d5c25f36c18853c3a00abe25788d31809e7aac120a4922983b5b363c415d6d8bb540ddf795c70cf6d0a0b47c9ec39a48a778e399a9c23c0db2fd0e9901aefa57e604be242ad33b7cb928a003b807f25173d109a141ced25450b57b2db5f355b5242e05e5eb31455fc490d0a0c4f87e108fea750767e93ec27f328b718928d6387d48625ed37f3eee6768e6b4a07d3fce0eaa010bac0423864a0e30b17e60873f0facb7c3dd22c84fc5c25029eaa057f24c176aa9a241948dcc066068609a9088b4f78ea512fd479608d5b06c1dc1a0c3f5e1fad5557d2e1a4e3fc3685b3c975bd8fa994f5824bcbfc12dac4758b5e380b902aef902ab20b902a7f902a4a005050505050505050505050505050505050505050505050505050505050505058301ec30b9010000000000000000000000000000000000000000000000000000000000000080000000000000000000000000000000000000000000000800000008000000000000000000000000000000000000000000000000000000100001000010000000000000000000000000000000000000040000000000000000000000000000010000000000000000000000000000000000000000000000200000000000000000000040000000000000000000000020000000000000000000000000000000000000100000000000000000000000000000000000000000000400000000000000000000000000000000000000800000000000000000000010000000000000000000000000f90179f8e0940606060606060606060606060606060606060606c0b8c89b9c9d9e9fa0a1a2a3a4a5a6a7a8a9aaabacadaeafb0b1b2b3b4b5b6b7b8b9babbbcbdbebfc0c1c2c3c4c5c6c7c8c9cacbcccdcecfd0d1d2d3d4d5d6d7d8d9dadbdcdddedfe0e1e2e3e4e5e6e7e8e9eaebecedeeeff0f1f2f3f4f5f6f7f8f9fafbfcfdfeff000102030405060708090a0b0c0d0e0f101112131415161718191a1b1c1d1e1f202122232425262728292a2b2c2d2e2f303132333435363738393a3b3c3d3e3f404142434445464748494a4b4c4d4e4f505152535455565758595a5b5c5d5e5f606162f838940707070707070707070707070707070707070707e1a0040404040404040404040404040404040404040404040404040404040404040480f85b940808080808080808080808080808080808080808f842a00404040404040404040404040404040404040404040404040404040404040404a00707070707070707070707070707070707070707070707070707070707070707819b",
+            0xb99fa81f895b208f24c9cb31b69aed313b279dd3914fcaece4f62ac25a6db0a7,
+            2,
+            0x0808080808080808080808080808080808080808,
+            topics,
+            hex"9b"
+        );
+        assertTrue(valid);
+    }
+
+    function test_log_6_0() public view {
+        bytes32[] memory topics = new bytes32[](1);
+        topics[0] = 0x0707070707070707070707070707070707070707070707070707070707070707;
+
+        bool valid = prover.verifyLog(
+            hex"06",
+            hex"f903feb8b3f8b1a0dda3dbaf4899fc089ccbea9dc2d3c2b8ac6b97bfefcc5f39b4e3d2218e64cf62a01090ce332a0853141bcd1fcef62800cfde1701284cfebcff090de347d2d6cd6fa0a98b33b0ce07e3d2ff7bab93fee78e27ead1900ad64df2967687382a4a7d7ca0a0bcf0a8f1568d1a6cd7dcb1c5be5af3311e949cd503cdca4eb68c10f28a194d9980808080a0ca57e8cfb6a6e8acd03d5ea00b5aaf650ab99ffee3110491a321d13084d5b4ef8080808080808080b901f4f901f180a0de078d2b5a67d1f4178a57086079e534ff06570f4191f402dd6900ee4e77ebbda08f6426b6fe181e7fc7760d4bd6112ba03ea4d579b662c8776695ede3a5a7766fa01b0c6ed166bb7f54d1d6c9eee2c7874fa47c8847c2947ddcf70dd4c36dbc7b19a0107a8dad3e2d55eb0def3d00563f0cf9b5ef427ef9de5d8c1fa0d5008de0791fa0c135231b491d0fbfa9a3374c4b537166a89d6f00667b079d334d8a5738cf83e5a0ba918467479572dbd418185cbeadfa004d641bd3881deee61669e6a78713a23aa01e8d410350015298d1e7a2925ab8312f686f5c3fa3d4e142415f003de239df6ba0962f81bef7b466d7fd77fd3efeb01afc0cd802d5fa570530d5c25f36c18853c3a00abe25788d31809e7aac120a4922983b5b363c415d6d8bb540ddf795c70cf6d0a0b47c9ec39a48a778e399a9c23c0db2fd0e9901aefa57e604be242ad33b7cb928a003b807f25173d109a141ced25450b57b2db5f355b5242e05e5eb31455fc490d0a0c4f87e108fea750767e93ec27f328b718928d6387d48625ed37f3eee6768e6b4a07d3fce0eaa010bac0423864a0e30b17e60873f0facb7c3dd22c84fc5c25029eaa057f24c176aa9a241948dcc066068609a9088b4f78ea512fd479608d5b06c1dc1a0c3f5e1fad5557d2e1a4e3fc3685b3c975bd8fa994f5824bcbfc12dac4758b5e380b9014ff9014c20b9014801f901440183023e38b9010000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000040000000000000000000000000000010000000000000000000000000000000000000000000000000000000000000000000040000000000000000000000000000000000000000000000000000000000000100000000000000000000000000000000000000000000400000000000000000000000000000000000000000000000000000000000010000000000000000000000000f83af838940707070707070707070707070707070707070707e1a0070707070707070707070707070707070707070707070707070707070707070780",
+            0xb99fa81f895b208f24c9cb31b69aed313b279dd3914fcaece4f62ac25a6db0a7,
+            0,
+            0x0707070707070707070707070707070707070707,
+            topics,
+            hex""
+        );
+        assertTrue(valid);
+    }
+
+    function test_log_7_0() public view {
+        bytes32[] memory topics = new bytes32[](2);
+        topics[0] = 0x0606060606060606060606060606060606060606060606060606060606060606;
+        topics[1] = 0x0505050505050505050505050505050505050505050505050505050505050505;
+
+        bool valid = prover.verifyLog(
+            hex"07",
+            hex"f904bdb8b3f8b1a0dda3dbaf4899fc089ccbea9dc2d3c2b8ac6b97bfefcc5f39b4e3d2218e64cf62a01090ce332a0853141bcd1fcef62800cfde1701284cfebcff090de347d2d6cd6fa0a98b33b0ce07e3d2ff7bab93fee78e27ead1900ad64df2967687382a4a7d7ca0a0bcf0a8f1568d1a6cd7dcb1c5be5af3311e949cd503cdca4eb68c10f28a194d9980808080a0ca57e8cfb6a6e8acd03d5ea00b5aaf650ab99ffee3110491a321d13084d5b4ef8080808080808080b901f4f901f180a0de078d2b5a67d1f4178a57086079e534ff06570f4191f402dd6900ee4e77ebbda08f6426b6fe181e7fc7760d4bd6112ba03ea4d579b662c8776695ede3a5a7766fa01b0c6ed166bb7f54d1d6c9eee2c7874fa47c8847c2947ddcf70dd4c36dbc7b19a0107a8dad3e2d55eb0def3d00563f0cf9b5ef427ef9de5d8c1fa0d5008de0791fa0c135231b491d0fbfa9a3374c4b537166a89d6f00667b079d334d8a5738cf83e5a0ba918467479572dbd418185cbeadfa004d641bd3881deee61669e6a78713a23aa01e8d410350015298d1e7a2925ab8312f686f5c3fa3d4e142415f003de239df6ba0962f81bef7b466d7fd77fd3efeb01afc0cd802d5fa570530d5c25f36c18853c3a00abe25788d31809e7aac120a4922983b5b363c415d6d8bb540ddf795c70cf6d0a0b47c9ec39a48a778e399a9c23c0db2fd0e9901aefa57e604be242ad33b7cb928a003b807f25173d109a141ced25450b57b2db5f355b5242e05e5eb31455fc490d0a0c4f87e108fea750767e93ec27f328b718928d6387d48625ed37f3eee6768e6b4a07d3fce0eaa010bac0423864a0e30b17e60873f0facb7c3dd22c84fc5c25029eaa057f24c176aa9a241948dcc066068609a9088b4f78ea512fd479608d5b06c1dc1a0c3f5e1fad5557d2e1a4e3fc3685b3c975bd8fa994f5824bcbfc12dac4758b5e380b9020ef9020b20b9020702f902038083029040b9010000000000000000000000000000000000000000000000001000000000000080000000000000000000000000000000000000000000000800000008000000000000000000000000000000000000020000200000000000010000000010000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000008200000000000000000000000000000000000000000000000000000000000008000000000000000000000000000000000000000000000000000000000000000020000000000001000000000000000000000000000800000000000000000000000001000000000000000000000f8f9f85b940808080808080808080808080808080808080808f842a00606060606060606060606060606060606060606060606060606060606060606a0050505050505050505050505050505050505050505050505050505050505050581d9f89a940909090909090909090909090909090909090909f863a00606060606060606060606060606060606060606060606060606060606060606a00505050505050505050505050505050505050505050505050505050505050505a004040404040404040404040404040404040404040404040404040404040404049fd9dadbdcdddedfe0e1e2e3e4e5e6e7e8e9eaebecedeeeff0f1f2f3f4f5f6f7",
+            0xb99fa81f895b208f24c9cb31b69aed313b279dd3914fcaece4f62ac25a6db0a7,
+            0,
+            0x0808080808080808080808080808080808080808,
+            topics,
+            hex"d9"
+        );
+        assertTrue(valid);
+    }
+
+    function test_log_7_1() public view {
+        bytes32[] memory topics = new bytes32[](3);
+        topics[0] = 0x0606060606060606060606060606060606060606060606060606060606060606;
+        topics[1] = 0x0505050505050505050505050505050505050505050505050505050505050505;
+        topics[2] = 0x0404040404040404040404040404040404040404040404040404040404040404;
+
+        bool valid = prover.verifyLog(
+            hex"07",
+            hex"f904bdb8b3f8b1a0dda3dbaf4899fc089ccbea9dc2d3c2b8ac6b97bfefcc5f39b4e3d2218e64cf62a01090ce332a0853141bcd1fcef62800cfde1701284cfebcff090de347d2d6cd6fa0a98b33b0ce07e3d2ff7bab93fee78e27ead1900ad64df2967687382a4a7d7ca0a0bcf0a8f1568d1a6cd7dcb1c5be5af3311e949cd503cdca4eb68c10f28a194d9980808080a0ca57e8cfb6a6e8acd03d5ea00b5aaf650ab99ffee3110491a321d13084d5b4ef8080808080808080b901f4f901f180a0de078d2b5a67d1f4178a57086079e534ff06570f4191f402dd6900ee4e77ebbda08f6426b6fe181e7fc7760d4bd6112ba03ea4d579b662c8776695ede3a5a7766fa01b0c6ed166bb7f54d1d6c9eee2c7874fa47c8847c2947ddcf70dd4c36dbc7b19a0107a8dad3e2d55eb0def3d00563f0cf9b5ef427ef9de5d8c1fa0d5008de0791fa0c135231b491d0fbfa9a3374c4b537166a89d6f00667b079d334d8a5738cf83e5a0ba918467479572dbd418185cbeadfa004d641bd3881deee61669e6a78713a23aa01e8d410350015298d1e7a2925ab8312f686f5c3fa3d4e142415f003de239df6ba0962f81bef7b466d7fd77fd3efeb01afc0cd802d5fa570530d5c25f36c18853c3a00abe25788d31809e7aac120a4922983b5b363c415d6d8bb540ddf795c70cf6d0a0b47c9ec39a48a778e399a9c23c0db2fd0e9901aefa57e604be242ad33b7cb928a003b807f25173d109a141ced25450b57b2db5f355b5242e05e5eb31455fc490d0a0c4f87e108fea750767e93ec27f328b718928d6387d48625ed37f3eee6768e6b4a07d3fce0eaa010bac0423864a0e30b17e60873f0facb7c3dd22c84fc5c25029eaa057f24c176aa9a241948dcc066068609a9088b4f78ea512fd479608d5b06c1dc1a0c3f5e1fad5557d2e1a4e3fc3685b3c975bd8fa994f5824bcbfc12dac4758b5e380b9020ef9020b20b9020702f902038083029040b9010000000000000000000000000000000000000000000000001000000000000080000000000000000000000000000000000000000000000800000008000000000000000000000000000000000000020000200000000000010000000010000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000008200000000000000000000000000000000000000000000000000000000000008000000000000000000000000000000000000000000000000000000000000000020000000000001000000000000000000000000000800000000000000000000000001000000000000000000000f8f9f85b940808080808080808080808080808080808080808f842a00606060606060606060606060606060606060606060606060606060606060606a0050505050505050505050505050505050505050505050505050505050505050581d9f89a940909090909090909090909090909090909090909f863a00606060606060606060606060606060606060606060606060606060606060606a00505050505050505050505050505050505050505050505050505050505050505a004040404040404040404040404040404040404040404040404040404040404049fd9dadbdcdddedfe0e1e2e3e4e5e6e7e8e9eaebecedeeeff0f1f2f3f4f5f6f7",
+            0xb99fa81f895b208f24c9cb31b69aed313b279dd3914fcaece4f62ac25a6db0a7,
+            1,
+            0x0909090909090909090909090909090909090909,
+            topics,
+            hex"d9dadbdcdddedfe0e1e2e3e4e5e6e7e8e9eaebecedeeeff0f1f2f3f4f5f6f7"
+        );
+        assertTrue(valid);
+    }
+
+    function test_log_63_0() public view {
+        bytes32[] memory topics = new bytes32[](3);
+        topics[0] = 0x3e3e3e3e3e3e3e3e3e3e3e3e3e3e3e3e3e3e3e3e3e3e3e3e3e3e3e3e3e3e3e3e;
+        topics[1] = 0x3d3d3d3d3d3d3d3d3d3d3d3d3d3d3d3d3d3d3d3d3d3d3d3d3d3d3d3d3d3d3d3d;
+        topics[2] = 0x3c3c3c3c3c3c3c3c3c3c3c3c3c3c3c3c3c3c3c3c3c3c3c3c3c3c3c3c3c3c3c3c;
+
+        bool valid = prover.verifyLog(
+            hex"3f",
+            hex"f90481b8b3f8b1a0dda3dbaf4899fc089ccbea9dc2d3c2b8ac6b97bfefcc5f39b4e3d2218e64cf62a01090ce332a0853141bcd1fcef62800cfde1701284cfebcff090de347d2d6cd6fa0a98b33b0ce07e3d2ff7bab93fee78e27ead1900ad64df2967687382a4a7d7ca0a0bcf0a8f1568d1a6cd7dcb1c5be5af3311e949cd503cdca4eb68c10f28a194d9980808080a0ca57e8cfb6a6e8acd03d5ea00b5aaf650ab99ffee3110491a321d13084d5b4ef8080808080808080b90214f90211a0777fdc2121068ee9258469bd9ca0611b7dbb4b8ded9af5970b5d808701d9b7bfa010805ac8decc4467e007811ebb9c833bb57bf5823181e20f8f84477a40582406a02444a8db6e7c1193023e9c95aac5150eecb1cf2814c968f627fc6bf59e40cd73a0419b636244e063ba30c048bef1b72d776f72520c8a8709155ea3d88cf11446b1a0aed04542f6cf57adfd6caab0114f3c5a9c21354331000aa4014173b8f3ba8ce4a03d731a43fcb7f057c60913e7b41d8d32435e55ae9b12f8cdc8bb0fe1585dd49fa042c8cfc17420ba144dc5f7cf6416b40b89e6ca07deed5f8c4835aba14723e484a0c3ad0b80b68ea01c2eca14703fdfea83be962fffd06eee24b375c8f14c963b32a0bdfe0262c186521421951430a6b880e4018c4e0267e876ac8d03c8fb773bed86a07643f7228b8f65788514a8b49aba71e08f7c7e30378c401d3bf7fccce52a2ce0a0bb87466c8466472ec9d631e510092b422d7c851be89050454bac28421746de09a00115c88f34df75899e5e006afcdf4deefa8545c43cae2bff4510f8be356aec4ba074930ba35d1a9784f077e073590405619a534ce60a4d77e44c999c2380030463a041df4a4c851fac6aa9584d04328d054b1fe47e0ed54ac32a64c2a11e56ef4035a01bbac5621348bb984b5becd3ea1d0cfc5dcbaa2f276145111ccac4dbe8244f7da0aa43893f9c48dfa197749be0eb6ca17378910e0b8af3477c7bf38dd0fdf0fbb480b901b2f901af20b901ab03f901a78083148200b901000000000000000000000000002000000400000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000080002000000080080000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000010000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000010000000000000000000000000000000000000000000000000000000000a000000000000000000000010000010000000000000000000000000000000000000000000000000000000000000000000000000000000f89df89b944040404040404040404040404040404040404040f863a03e3e3e3e3e3e3e3e3e3e3e3e3e3e3e3e3e3e3e3e3e3e3e3e3e3e3e3e3e3e3e3ea03d3d3d3d3d3d3d3d3d3d3d3d3d3d3d3d3d3d3d3d3d3d3d3d3d3d3d3d3d3d3d3da03c3c3c3c3c3c3c3c3c3c3c3c3c3c3c3c3c3c3c3c3c3c3c3c3c3c3c3c3c3c3c3ca0a1a2a3a4a5a6a7a8a9aaabacadaeafb0b1b2b3b4b5b6b7b8b9babbbcbdbebfc0",
+            0xb99fa81f895b208f24c9cb31b69aed313b279dd3914fcaece4f62ac25a6db0a7,
+            0,
+            0x4040404040404040404040404040404040404040,
+            topics,
+            hex"a1a2a3a4a5a6a7a8a9aaabacadaeafb0b1b2b3b4b5b6b7b8b9babbbcbdbebfc0"
+        );
+        assertTrue(valid);
+    }
+}